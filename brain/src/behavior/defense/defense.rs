@@ -1,11 +1,12 @@
 use crate::{
     behavior::{
-        defense::{retreat::Retreat, retreating_save::RetreatingSave, PanicDefense},
+        defense::{retreat::Retreat, retreating_save::RetreatingSave, PanicDefense, ShadowDefense},
         offense::TepidHit,
         strike::{GroundedHitAimContext, GroundedHitTarget, GroundedHitTargetAdjust},
     },
     eeg::Event,
     helpers::hit_angle::blocking_angle,
+    params,
     strategy::{Action, Behavior, Context, Game, Scenario},
     utils::{geometry::ExtendF32, WallRayCalculator},
 };
@@ -78,7 +79,7 @@ impl Defense {
         let ball_loc = intercept.ball_loc.to_2d();
         let goal = ctx.game.own_goal();
         let dist_ball_to_goal = (ball_loc - goal.center_2d).norm();
-        if ctx.scenario.possession() >= -Scenario::POSSESSION_CONTESTABLE {
+        if ctx.scenario.possession() >= -params::POSSESSION_CONTESTABLE.get() {
             return false;
         }
         ctx.enemy_cars().any(|enemy| {
@@ -88,14 +89,24 @@ impl Defense {
                 .negated_difference_and_angle_to(ball_loc);
             let angle_ball_goal = ball_loc.negated_difference_and_angle_to(goal.center_2d);
             let angle_diff = (angle_car_ball - angle_ball_goal).normalize_angle().abs();
-            let max_angle_diff =
-                linear_interpolate(&[2500.0, 7500.0], &[PI / 2.0, PI / 4.0], dist_ball_to_goal);
+            let max_angle_diff = linear_interpolate(
+                &[2500.0, 7500.0],
+                &[
+                    params::ENEMY_SHOT_ANGLE_NEAR.get(),
+                    params::ENEMY_SHOT_ANGLE_FAR.get(),
+                ],
+                dist_ball_to_goal,
+            );
             angle_diff < max_angle_diff
         })
     }
 
     pub fn enemy_can_attack(ctx: &mut Context<'_>) -> bool {
-        if ctx.scenario.possession() >= -2.0 {
+        let threshold = ctx
+            .scenario
+            .match_state()
+            .loosen(params::ENEMY_ATTACK_POSSESSION.get());
+        if ctx.scenario.possession() >= -threshold {
             return false;
         }
         let (enemy, intercept) = match ctx.scenario.enemy_intercept() {
@@ -141,6 +152,13 @@ impl Behavior for Defense {
             return Action::tail_call(Retreat::new());
         }
 
+        // The enemy has the ball but isn't threatening yet. Stay goalside and wait
+        // for them to make a move.
+        if ctx.scenario.possession() < -params::POSSESSION_CONTESTABLE.get() {
+            ctx.eeg.log(self.name(), "shadowing");
+            return Action::tail_call(ShadowDefense::new());
+        }
+
         // If we're already in goal, try to take control of the ball.
         Action::tail_call(TepidHit::new())
     }
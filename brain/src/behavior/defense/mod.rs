@@ -4,6 +4,7 @@ pub use self::{
     panic_defense::PanicDefense,
     push_to_own_corner::PushToOwnCorner,
     retreat::Retreat,
+    shadow_defense::ShadowDefense,
 };
 
 #[allow(clippy::module_inception)]
@@ -13,3 +14,4 @@ mod panic_defense;
 mod push_to_own_corner;
 mod retreat;
 mod retreating_save;
+mod shadow_defense;
@@ -14,6 +14,7 @@ use std::f32::consts::PI;
 
 pub struct PanicDefense {
     use_boost: bool,
+    avoid_opponents: bool,
     same_ball_trajectory: SameBallTrajectory,
     phase: Phase,
 }
@@ -36,10 +37,17 @@ impl PanicDefense {
     pub fn new() -> Self {
         Self {
             use_boost: true,
+            avoid_opponents: false,
             same_ball_trajectory: SameBallTrajectory::new(),
             phase: Phase::Start,
         }
     }
+
+    /// Steer around opponents on the way back to goal.
+    pub fn avoid_opponents(mut self, avoid_opponents: bool) -> Self {
+        self.avoid_opponents = avoid_opponents;
+        self
+    }
 }
 
 impl Behavior for PanicDefense {
@@ -79,7 +87,7 @@ impl Behavior for PanicDefense {
                     ..Default::default()
                 })
             }
-            Phase::Finished => Action::Return,
+            Phase::Finished => Action::return_("cleared the goal"),
         }
     }
 }
@@ -167,7 +175,7 @@ impl PanicDefense {
             return Some(Phase::Rush {
                 // Powerslide towards the post opposite the one we're driving to.
                 aim_hint: Point2::new(blitz_loc.x.signum() * -2000.0, own_goal.center_2d.y),
-                child: BlitzToLocation::new(blitz_loc),
+                child: BlitzToLocation::new(blitz_loc).avoid_opponents(self.avoid_opponents),
             });
         }
 
@@ -6,7 +6,8 @@ use crate::{
     },
     eeg::{color, Drawable},
     helpers::intercept::naive_ground_intercept_2,
-    strategy::{Action, Behavior, Context, Goal, Priority, Scenario},
+    params,
+    strategy::{Action, Behavior, Context, Goal, Priority},
     utils::geometry::ExtendF32,
 };
 use common::{prelude::*, Time};
@@ -108,21 +109,16 @@ impl Behavior for PushToOwnCorner {
         match (me_intercept, enemy_shootable_intercept) {
             (_, None) => {
                 ctx.eeg.log(self.name(), "safe for now");
-                Action::Return
-            }
-            (None, _) => {
-                ctx.eeg.log(self.name(), "can't reach ball");
-                Action::Abort
+                Action::return_("safe for now")
             }
+            (None, _) => Action::abort("can't reach ball"),
             (Some(me_intercept), Some(_enemy_intercept)) => {
                 if ctx.scenario.possession() >= 3.0 {
-                    ctx.eeg
-                        .log(self.name(), "we have all the time in the world");
-                    return Action::Abort;
-                } else if ctx.scenario.possession() >= Scenario::POSSESSION_CONTESTABLE {
+                    return Action::abort("we have all the time in the world");
+                } else if ctx.scenario.possession() >= params::POSSESSION_CONTESTABLE.get() {
                     ctx.eeg.log(self.name(), "swatting ball away from enemy");
                     return Action::tail_call(hit_to_safety(ctx));
-                } else if ctx.scenario.possession() >= -Scenario::POSSESSION_CONTESTABLE {
+                } else if ctx.scenario.possession() >= -params::POSSESSION_CONTESTABLE.get() {
                     ctx.eeg.log(self.name(), "defensive race");
                     return Action::tail_call(hit_to_safety(ctx));
                 }
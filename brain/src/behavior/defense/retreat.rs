@@ -61,7 +61,8 @@ impl Behavior for Retreat {
         } else {
             choices.push(Box::new(RetreatingSave::new()));
         }
-        choices.push(Box::new(PanicDefense::new()));
+        // On a retreat, opponents tend to be coming the other way.
+        choices.push(Box::new(PanicDefense::new().avoid_opponents(true)));
         // We should never get this far, but it's here as a fail-safe:
         choices.push(Box::new(TepidHit::new()));
 
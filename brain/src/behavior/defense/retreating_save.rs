@@ -11,7 +11,11 @@ use crate::{
         ball::BallFrame, drive::rough_time_drive_to_loc, hit_angle::feasible_angle_near,
         intercept::naive_ground_intercept_2,
     },
-    routing::{behavior::FollowRoute, models::CarState, plan::GroundIntercept},
+    routing::{
+        behavior::FollowRoute,
+        models::CarState,
+        plan::{avoid_opponents_waypoint, GroundIntercept},
+    },
     sim::{SimGroundDrive, SimJump},
     strategy::{Action, Behavior, Context, Game, Priority},
     utils::{geometry::Line2, WallRayCalculator},
@@ -108,18 +112,15 @@ impl Behavior for RetreatingSave {
 
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
         if let Err(reason) = Self::applicable(ctx) {
-            ctx.eeg.log(self.name(), reason);
-            return Action::Abort;
+            return Action::abort(reason);
         }
 
         if !GetToFlatGround::on_flat_ground(ctx.me()) {
-            ctx.eeg.log(self.name(), "not on flat ground");
-            return Action::Abort;
+            return Action::abort("not on flat ground");
         }
 
         let plan = some_or_else!(self.intercept(ctx), {
-            ctx.eeg.log(self.name(), "no intercept");
-            return Action::Abort;
+            return Action::abort("no intercept");
         });
 
         if self.striking_would_be_better(ctx, &plan) {
@@ -130,7 +131,8 @@ impl Behavior for RetreatingSave {
             ctx.eeg
                 .log(self.name(), "yeah, I'm not gonna sit around all day");
             return Action::tail_call(Chain::new(Priority::Idle, vec_box![
-                FollowRoute::new(GroundIntercept::new()).same_ball_trajectory(true),
+                FollowRoute::new(GroundIntercept::new().avoid_opponents(true))
+                    .same_ball_trajectory(true),
                 GroundedHit::hit_towards(defensive_hit),
             ]));
         }
@@ -152,6 +154,11 @@ impl Behavior for RetreatingSave {
             plan.target_loc,
             ctx.me().Physics.rot(),
         ));
+        if plan.target_steer_loc != plan.target_loc {
+            ctx.eeg
+                .draw(Drawable::print("avoiding opponent", color::GREEN));
+            ctx.eeg.draw(Drawable::Crosshair(plan.target_steer_loc));
+        }
 
         if self.time_to_jump(ctx) {
             ctx.eeg.log(self.name(), "the time is now");
@@ -205,11 +212,16 @@ impl RetreatingSave {
             feasible_angle_near(intercept_ball_loc, block_loc, target_loc, clamp_angle);
         // Target a fixed distance away from the ball.
         let target_loc = intercept_ball_loc + (target_loc - intercept_ball_loc).normalize() * 200.0;
+        // We're usually driving towards our own goal, and any opponents are usually
+        // driving the other way, so a head-on collision is a real risk.
+        let target_steer_loc =
+            avoid_opponents_waypoint(ctx.game, &ctx.me().into(), target_loc, 0.0)
+                .unwrap_or(target_loc);
 
         Some(Plan {
             intercept_ball_loc: intercept.ball_loc,
             target_loc,
-            target_steer_loc: target_loc,
+            target_steer_loc,
             target_time: intercept.time,
         })
     }
@@ -288,7 +300,7 @@ impl RetreatingSave {
         let drive = SimGroundDrive::new(plan.target_loc);
         let jump = SimJump;
 
-        let axis = (plan.target_steer_loc - ctx.me().Physics.loc_2d()).to_axis();
+        let axis = (plan.target_loc - ctx.me().Physics.loc_2d()).to_axis();
 
         let calc_offset = |throttle, boost| {
             let state = ctx.me().into();
@@ -498,6 +510,7 @@ mod integration_tests {
         println!("ball_loc = {:?}", ball_loc);
         assert!(ball_loc.x < -1000.0);
 
+        test.assert_inside("RetreatingSave", 0.5);
         test.examine_events(|events| {
             assert!(events.contains(&Event::RetreatingSave));
             assert!(!events.contains(&Event::RetreatingSaveStopAndWait));
@@ -0,0 +1,221 @@
+use crate::{
+    behavior::{
+        defense::{retreating_save::RetreatingSave, Defense, Retreat},
+        higher_order::TryChoose,
+        movement::{drive_towards, simple_yaw_diff, GetToFlatGround},
+        offense::TepidHit,
+        strike::FiftyFifty,
+    },
+    eeg::Event,
+    helpers::telepathy::predict_ball_travel_direction,
+    params,
+    strategy::{Action, Behavior, Context, Goal, Priority},
+};
+use common::{prelude::*, rl};
+use nalgebra::{Point2, Unit, Vector2};
+use nameof::name_of_type;
+use std::f32::consts::PI;
+use vec_box::vec_box;
+
+/// Stay goalside of an attacking enemy, matching their speed, and wait for
+/// them to make a mistake.
+pub struct ShadowDefense;
+
+impl ShadowDefense {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// The spot on the enemy's likely line of attack, `distance` back from the
+    /// ball along `travel_direction` (the way the ball will go once they hit
+    /// it).
+    fn shadow_loc(
+        goal: &Goal,
+        ball_loc: Point2<f32>,
+        travel_direction: Unit<Vector2<f32>>,
+        distance: f32,
+    ) -> Point2<f32> {
+        // If the ball is closer than that to goal, split the difference instead of
+        // backing into the net.
+        let distance = distance.min((ball_loc - goal.center_2d).norm() / 2.0);
+        let loc = ball_loc + travel_direction.as_ref() * distance;
+        Point2::new(
+            loc.x.max(-rl::FIELD_MAX_X).min(rl::FIELD_MAX_X),
+            loc.y.max(-rl::FIELD_MAX_Y).min(rl::FIELD_MAX_Y),
+        )
+    }
+
+    /// The speed which keeps pace with the enemy, plus enough to close the gap
+    /// to where we want to be within about a second.
+    fn target_speed(enemy_speed: f32, gap: f32) -> f32 {
+        (enemy_speed + gap).min(rl::CAR_MAX_SPEED)
+    }
+}
+
+impl Behavior for ShadowDefense {
+    fn name(&self) -> &str {
+        name_of_type!(ShadowDefense)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg.track(Event::ShadowDefense);
+
+        if !GetToFlatGround::on_flat_ground(ctx.me()) {
+            return Action::tail_call(GetToFlatGround::new());
+        }
+
+        if !Defense::is_between_ball_and_own_goal(ctx.game, ctx.me(), ctx.scenario) {
+            ctx.eeg.log(self.name(), "lost position");
+            return Action::tail_call(Retreat::new());
+        }
+
+        // The enemy has committed to a shot, so make the save.
+        if RetreatingSave::applicable(ctx).is_ok() || Defense::enemy_can_shoot(ctx) {
+            ctx.eeg.log(self.name(), "enemy committed");
+            return Action::tail_call(Retreat::new());
+        }
+
+        // The enemy lost control of the ball, so go and challenge for it.
+        if ctx.scenario.possession() >= -params::POSSESSION_CONTESTABLE.get() {
+            ctx.eeg.log(self.name(), "ball is up for grabs");
+            return Action::tail_call(TryChoose::new(Priority::Idle, vec_box![
+                FiftyFifty::new(),
+                TepidHit::new(),
+            ]));
+        }
+
+        let (enemy, intercept) = some_or_else!(ctx.scenario.enemy_intercept(), {
+            return Action::abort("no enemy intercept");
+        });
+        let ball_loc = intercept.ball_loc.to_2d();
+        let enemy_speed = enemy.Physics.vel_2d().norm();
+
+        // Mirror the enemy: as they line up a different angle, shift over to cover it.
+        let goal_loc = ctx.game.own_goal().center_2d;
+        let travel_direction =
+            predict_ball_travel_direction(ctx).unwrap_or_else(|| (goal_loc - ball_loc).to_axis());
+        let target_loc = Self::shadow_loc(
+            ctx.game.own_goal(),
+            ball_loc,
+            travel_direction,
+            params::SHADOW_DISTANCE.get(),
+        );
+
+        let me = ctx.me();
+        let gap = (target_loc - me.Physics.loc_2d()).norm();
+        let target_speed = Self::target_speed(enemy_speed, gap);
+        let speed = me.Physics.vel_2d().dot(&me.Physics.forward_axis_2d());
+        let facing = simple_yaw_diff(&me.Physics, target_loc).abs() < PI / 2.0;
+
+        ctx.eeg.print_value("target_speed", target_speed);
+
+        let mut input = drive_towards(ctx, target_loc);
+        if facing {
+            input.Throttle = ((target_speed - speed) / 500.0).max(-1.0).min(1.0);
+        }
+        // Keep a reserve so we can react when the enemy commits.
+        input.Boost = facing
+            && target_speed > rl::CAR_NORMAL_SPEED
+            && speed < target_speed - 300.0
+            && me.Boost as f32 > params::SHADOW_BOOST_RESERVE.get();
+
+        Action::Yield(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        behavior::defense::ShadowDefense,
+        helpers::{
+            ball::ChipBallPrediction,
+            hit_angle::feasible_hit_angle_toward,
+            telepathy::{predict_ball_travel_direction_2, predict_enemy_hit_direction_2},
+        },
+        routing::test::fixed_packet,
+        strategy::{Context2, Game, Scenario, SOCCAR_GOAL_BLUE},
+    };
+    use common::{prelude::*, rl};
+    use nalgebra::Point2;
+    use std::f32::consts::PI;
+
+    #[test]
+    fn shadow_loc_follows_the_line_of_attack() {
+        let goal = &*SOCCAR_GOAL_BLUE;
+        let ball_loc = Point2::new(0.0, 0.0);
+
+        let straight = (goal.center_2d - ball_loc).to_axis();
+        let loc = ShadowDefense::shadow_loc(goal, ball_loc, straight, 1500.0);
+        assert!(loc.x.abs() < 1.0);
+        assert!((loc.y + 1500.0).abs() < 1.0);
+
+        // The enemy is angling towards the far post, so we shift over too.
+        let post = (Point2::new(-rl::GOALPOST_X, goal.center_2d.y) - ball_loc).to_axis();
+        let loc = ShadowDefense::shadow_loc(goal, ball_loc, post, 1500.0);
+        assert!(loc.x < -100.0);
+    }
+
+    #[test]
+    fn shadow_loc_stays_out_of_the_net() {
+        let goal = &*SOCCAR_GOAL_BLUE;
+        let ball_loc = Point2::new(0.0, -4000.0);
+        let straight = (goal.center_2d - ball_loc).to_axis();
+        let loc = ShadowDefense::shadow_loc(goal, ball_loc, straight, 1500.0);
+        assert!(loc.y > goal.center_2d.y);
+        assert!(loc.y < ball_loc.y);
+    }
+
+    #[test]
+    fn target_speed_matches_the_enemy() {
+        assert!((ShadowDefense::target_speed(1000.0, 0.0) - 1000.0).abs() < 1.0);
+        assert!(ShadowDefense::target_speed(1000.0, 500.0) > 1000.0);
+        assert!(ShadowDefense::target_speed(2000.0, 5000.0) <= rl::CAR_MAX_SPEED);
+    }
+
+    #[test]
+    fn shadow_loc_follows_the_predicted_ball_path() {
+        // An enemy coming in from the side, so the path of the ball differs from
+        // the direction the enemy is driving.
+        let mut packet = fixed_packet();
+        let mut enemy = packet.GameCars[0];
+        enemy.Team = 1;
+        enemy.Physics.Location.X = 2000.0;
+        enemy.Physics.Location.Y = 1000.0;
+        enemy.Physics.Rotation.Yaw = (-1.0f32).atan2(-2.0);
+        enemy.Physics.Velocity.X = 0.0;
+        enemy.Physics.Velocity.Y = 0.0;
+        packet.GameCars.push(enemy);
+        packet.NumCars = 2;
+
+        let game = Game::fake_soccar(&packet, 0);
+        let ball_predictor = ChipBallPrediction::new();
+        let scenario = Scenario::new(&game, &ball_predictor, &packet);
+        let ctx = Context2 {
+            packet: &packet,
+            game: &game,
+            scenario: &scenario,
+            last_quick_chat: 0.0,
+        };
+
+        let (enemy, intercept) = scenario.enemy_intercept().unwrap();
+        let ball_loc = intercept.ball_loc.to_2d();
+        let aim = feasible_hit_angle_toward(
+            ball_loc,
+            enemy.Physics.loc_2d(),
+            game.own_goal().center_2d,
+            PI / 4.0,
+        );
+
+        let travel = predict_ball_travel_direction_2(&ctx).unwrap();
+        let loc = ShadowDefense::shadow_loc(game.own_goal(), ball_loc, travel, 1500.0);
+        // We wait on the line from the ball to where the enemy will send it.
+        let along = (aim - ball_loc).to_axis();
+        let offset = loc - ball_loc;
+        assert!((offset.x * along.y - offset.y * along.x).abs() < 1.0);
+        assert!(offset.dot(&along) > 1000.0);
+
+        // The enemy's own heading would have put us somewhere else.
+        let heading = predict_enemy_hit_direction_2(&ctx).unwrap();
+        assert!(heading.angle_to(&travel).abs() > 10.0_f32.to_radians());
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     eeg::{color, Drawable},
-    strategy::{Action, Behavior, Context, Priority},
+    strategy::{Action, Behavior, Context, Priority, Prospect},
 };
 use itertools::Itertools;
 use nameof::name_of_type;
@@ -10,6 +10,9 @@ use std::{collections::VecDeque, iter, mem};
 pub struct Chain {
     priority: Priority,
     children: VecDeque<Box<dyn Behavior>>,
+    /// How many children have already returned. This is the index of the
+    /// front child among the children passed to `new`.
+    completed: usize,
     /// Cache the full name of the Behavior, including names of `children`. This
     /// must be kept up to date whenever `children` is modified.
     blurb: String,
@@ -28,6 +31,7 @@ impl Chain {
             blurb: Self::blurb(children.iter()),
             priority,
             children: children.into_iter().collect(),
+            completed: 0,
         }
     }
 
@@ -53,6 +57,18 @@ impl Behavior for Chain {
         self.priority
     }
 
+    fn prospect(&self) -> Prospect {
+        match self.children.front() {
+            Some(child) => child.prospect(),
+            None => Prospect::default(),
+        }
+    }
+
+    fn active_child(&self) -> Option<(usize, &dyn Behavior)> {
+        let child = self.children.front()?;
+        Some((self.completed, &**child))
+    }
+
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
         ctx.eeg.draw(Drawable::print(
             self.children
@@ -64,7 +80,7 @@ impl Behavior for Chain {
         ));
 
         let front = match self.children.front_mut() {
-            None => return Action::Return,
+            None => return Action::return_("nothing left to do"),
             Some(b) => b,
         };
         ctx.eeg.draw(Drawable::print(front.blurb(), color::YELLOW));
@@ -81,20 +97,29 @@ impl Behavior for Chain {
                 self.execute_old(ctx)
             }
             Action::RootCall(x) => Action::RootCall(x),
-            Action::Return => {
+            Action::Return(reason) => {
                 let front = self.children.pop_front().unwrap();
+                self.completed += 1;
                 self.blurb = Self::blurb(self.children.iter());
                 ctx.eeg.log(
                     self.name(),
-                    format!("Return from {}; becoming {}", front.name(), self.blurb),
+                    format!(
+                        "Return from {} ({}); becoming {}",
+                        front.name(),
+                        reason,
+                        self.blurb,
+                    ),
                 );
+                if self.children.is_empty() {
+                    return Action::Return(reason);
+                }
                 self.execute_old(ctx)
             }
-            Action::Abort => {
+            Action::Abort(reason) => {
                 let front = self.children.front().unwrap();
                 ctx.eeg
                     .log(self.name(), format!("Abort from {}", front.name()));
-                Action::Abort
+                Action::Abort(reason)
             }
         }
     }
@@ -0,0 +1,373 @@
+use crate::{
+    eeg::{color, Drawable},
+    strategy::{Action, Behavior, Context, Priority},
+};
+use common::halfway_house::PlayerInput;
+use itertools::Itertools;
+use nameof::name_of_type;
+use std::mem;
+
+/// One field of `PlayerInput`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Channel {
+    Throttle,
+    Steer,
+    Pitch,
+    Yaw,
+    Roll,
+    Jump,
+    Boost,
+    Handbrake,
+}
+
+/// Every channel, for a layer that fills in whatever the layers before it left
+/// unclaimed.
+pub const ALL: &[Channel] = &[
+    Channel::Throttle,
+    Channel::Steer,
+    Channel::Pitch,
+    Channel::Yaw,
+    Channel::Roll,
+    Channel::Jump,
+    Channel::Boost,
+    Channel::Handbrake,
+];
+/// The channels an orientation controller needs.
+#[cfg(test)]
+pub const ORIENT: &[Channel] = &[Channel::Pitch, Channel::Yaw, Channel::Roll];
+
+/// A child of `Merge`, along with the channels it controls.
+pub struct Layer {
+    channels: &'static [Channel],
+    child: Box<dyn Behavior>,
+    optional: bool,
+}
+
+impl Layer {
+    pub fn new(channels: &'static [Channel], child: impl Behavior + 'static) -> Self {
+        Self {
+            channels,
+            child: Box::new(child),
+            optional: false,
+        }
+    }
+
+    /// By default, when a layer returns, the whole `Merge` returns. If the
+    /// layer is optional, it is dropped instead and its channels go to the
+    /// next layer that claims them (or are left at their defaults).
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+}
+
+/// Run every layer each frame, and assemble the input from each layer's
+/// channels.
+///
+/// Rules:
+///
+/// - If more than one layer claims a channel, the earliest layer wins.
+/// - If any layer aborts, the whole `Merge` aborts.
+/// - If a required layer returns, the whole `Merge` returns. If an optional
+///   layer returns, it is dropped. Once every layer is gone, `Merge` returns.
+/// - Tail calls replace the layer's child; root calls bubble up immediately.
+pub struct Merge {
+    priority: Priority,
+    layers: Vec<Layer>,
+    blurb: String,
+}
+
+impl Merge {
+    pub fn new(priority: Priority, layers: Vec<Layer>) -> Self {
+        Self {
+            blurb: Self::blurb(&layers),
+            priority,
+            layers,
+        }
+    }
+
+    fn blurb(layers: &[Layer]) -> String {
+        let names = layers.iter().map(|l| l.child.name()).join(", ");
+        format!("{} ({})", name_of_type!(Merge), names)
+    }
+}
+
+impl Behavior for Merge {
+    fn name(&self) -> &str {
+        name_of_type!(Merge)
+    }
+
+    fn blurb(&self) -> &str {
+        &self.blurb
+    }
+
+    fn priority(&self) -> Priority {
+        self.priority
+    }
+
+    /// Only the earliest layer is reported, since it has the final say over
+    /// any channel it claims.
+    fn active_child(&self) -> Option<(usize, &dyn Behavior)> {
+        let layer = self.layers.first()?;
+        Some((0, &*layer.child))
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        if self.layers.is_empty() {
+            return Action::return_("every layer finished");
+        }
+
+        let mut input = PlayerInput::default();
+        let mut claimed = Vec::new();
+        let mut index = 0;
+        while index < self.layers.len() {
+            ctx.eeg.draw(Drawable::print(
+                self.layers[index].child.blurb(),
+                color::YELLOW,
+            ));
+            match self.layers[index].child.execute_old(ctx) {
+                Action::Yield(layer_input) => {
+                    let layer = &self.layers[index];
+                    for &channel in layer.channels {
+                        if !claimed.contains(&channel) {
+                            copy_channel(&mut input, &layer_input, channel);
+                            claimed.push(channel);
+                        }
+                    }
+                    index += 1;
+                }
+                Action::TailCall(b) => {
+                    let prev = mem::replace(&mut self.layers[index].child, b);
+                    self.blurb = Self::blurb(&self.layers);
+                    ctx.eeg.log(
+                        self.name(),
+                        format!("TailCall from {}; becoming {}", prev.name(), self.blurb),
+                    );
+                    // Run the same index again with the new child.
+                }
+                Action::RootCall(b) => return Action::RootCall(b),
+                Action::Return(reason) => {
+                    let layer = self.layers.remove(index);
+                    self.blurb = Self::blurb(&self.layers);
+                    ctx.eeg.log(
+                        self.name(),
+                        format!("Return from {} ({})", layer.child.name(), reason),
+                    );
+                    if !layer.optional {
+                        return Action::Return(reason);
+                    }
+                }
+                Action::Abort(reason) => {
+                    ctx.eeg.log(
+                        self.name(),
+                        format!("Abort from {}", self.layers[index].child.name()),
+                    );
+                    return Action::Abort(reason);
+                }
+            }
+        }
+
+        if self.layers.is_empty() {
+            return Action::return_("every layer finished");
+        }
+        Action::Yield(input)
+    }
+}
+
+fn copy_channel(dest: &mut PlayerInput, src: &PlayerInput, channel: Channel) {
+    match channel {
+        Channel::Throttle => dest.Throttle = src.Throttle,
+        Channel::Steer => dest.Steer = src.Steer,
+        Channel::Pitch => dest.Pitch = src.Pitch,
+        Channel::Yaw => dest.Yaw = src.Yaw,
+        Channel::Roll => dest.Roll = src.Roll,
+        Channel::Jump => dest.Jump = src.Jump,
+        Channel::Boost => dest.Boost = src.Boost,
+        Channel::Handbrake => dest.Handbrake = src.Handbrake,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        behavior::higher_order::{
+            merge::{copy_channel, Channel, Layer, Merge, ALL, ORIENT},
+            NullBehavior,
+        },
+        eeg::EEG,
+        helpers::ball::ChipBallPrediction,
+        routing::test::fixed_packet,
+        strategy::{Action, Behavior, Context, Game, Priority, Scenario},
+    };
+    use common::halfway_house::PlayerInput;
+
+    /// Yields the same input forever.
+    struct Steady(PlayerInput);
+
+    impl Behavior for Steady {
+        fn name(&self) -> &str {
+            stringify!(Steady)
+        }
+
+        fn execute_old(&mut self, _ctx: &mut Context<'_>) -> Action {
+            Action::Yield(self.0)
+        }
+    }
+
+    struct Finisher;
+
+    impl Behavior for Finisher {
+        fn name(&self) -> &str {
+            stringify!(Finisher)
+        }
+
+        fn execute_old(&mut self, _ctx: &mut Context<'_>) -> Action {
+            Action::return_("done")
+        }
+    }
+
+    struct Quitter;
+
+    impl Behavior for Quitter {
+        fn name(&self) -> &str {
+            stringify!(Quitter)
+        }
+
+        fn execute_old(&mut self, _ctx: &mut Context<'_>) -> Action {
+            Action::abort("giving up")
+        }
+    }
+
+    /// Run `behavior` for one frame.
+    fn tick(behavior: &mut dyn Behavior) -> Action {
+        let packet = fixed_packet();
+        let game = Game::fake_soccar(&packet, 0);
+        let ball_predictor = ChipBallPrediction::new();
+        let scenario = Scenario::new(&game, &ball_predictor, &packet);
+        let mut eeg = EEG::new();
+        let mut last_quick_chat = 0.0;
+        eeg.begin(&packet);
+        let mut ctx = Context::new(&game, &packet, &scenario, &mut eeg, &mut last_quick_chat);
+        let action = behavior.execute_old(&mut ctx);
+        eeg.show(&packet);
+        action
+    }
+
+    fn steady(throttle: f32, roll: f32) -> Steady {
+        Steady(PlayerInput {
+            Throttle: throttle,
+            Roll: roll,
+            Boost: true,
+            ..Default::default()
+        })
+    }
+
+    fn expect_yield(action: Action) -> PlayerInput {
+        match action {
+            Action::Yield(input) => input,
+            _ => panic!("expected a yield"),
+        }
+    }
+
+    #[test]
+    fn copies_only_the_given_channels() {
+        let src = PlayerInput {
+            Throttle: 1.0,
+            Steer: -1.0,
+            Roll: 0.5,
+            Boost: true,
+            ..Default::default()
+        };
+        let mut dest = PlayerInput::default();
+        for &channel in ORIENT {
+            copy_channel(&mut dest, &src, channel);
+        }
+        assert_eq!(dest.Roll, 0.5);
+        assert_eq!(dest.Throttle, 0.0);
+        assert_eq!(dest.Steer, 0.0);
+        assert!(!dest.Boost);
+
+        copy_channel(&mut dest, &src, Channel::Boost);
+        assert!(dest.Boost);
+        assert_eq!(dest.Throttle, 0.0);
+
+        for &channel in ALL {
+            copy_channel(&mut dest, &src, channel);
+        }
+        assert_eq!(dest.Throttle, 1.0);
+        assert_eq!(dest.Steer, -1.0);
+    }
+
+    #[test]
+    fn earliest_layer_wins_conflicts() {
+        let mut merge = Merge::new(Priority::Idle, vec![
+            Layer::new(ORIENT, steady(1.0, 1.0)),
+            Layer::new(ALL, steady(-1.0, -1.0)),
+        ]);
+        let input = expect_yield(tick(&mut merge));
+        assert_eq!(input.Roll, 1.0);
+        assert_eq!(input.Throttle, -1.0);
+        assert!(input.Boost);
+    }
+
+    #[test]
+    fn unclaimed_channels_are_left_at_defaults() {
+        let mut merge = Merge::new(Priority::Idle, vec![Layer::new(ORIENT, steady(1.0, 1.0))]);
+        let input = expect_yield(tick(&mut merge));
+        assert_eq!(input.Roll, 1.0);
+        assert_eq!(input.Throttle, 0.0);
+        assert!(!input.Boost);
+    }
+
+    #[test]
+    fn optional_layer_is_dropped_on_return() {
+        let mut merge = Merge::new(Priority::Idle, vec![
+            Layer::new(ORIENT, Finisher).optional(),
+            Layer::new(ALL, steady(1.0, -1.0)),
+        ]);
+        // The `Finisher`'s channels go to the next layer that claims them.
+        let input = expect_yield(tick(&mut merge));
+        assert_eq!(input.Roll, -1.0);
+        assert_eq!(input.Throttle, 1.0);
+        assert_eq!(merge.blurb(), "Merge (Steady)");
+        assert_eq!(merge.active_child().unwrap().1.name(), "Steady");
+
+        expect_yield(tick(&mut merge));
+    }
+
+    #[test]
+    fn required_layer_return_ends_the_merge() {
+        let mut merge = Merge::new(Priority::Idle, vec![
+            Layer::new(ALL, steady(1.0, 1.0)),
+            Layer::new(ORIENT, Finisher),
+        ]);
+        match tick(&mut merge) {
+            Action::Return(reason) => assert_eq!(reason, "done"),
+            _ => panic!("expected a return"),
+        }
+    }
+
+    #[test]
+    fn merge_returns_once_every_optional_layer_is_gone() {
+        let mut merge = Merge::new(Priority::Idle, vec![
+            Layer::new(ORIENT, Finisher).optional(),
+            Layer::new(ALL, Finisher).optional(),
+        ]);
+        match tick(&mut merge) {
+            Action::Return(reason) => assert_eq!(reason, "every layer finished"),
+            _ => panic!("expected a return"),
+        }
+    }
+
+    #[test]
+    fn abort_propagates_even_from_an_optional_layer() {
+        let mut merge = Merge::new(Priority::Idle, vec![
+            Layer::new(ALL, NullBehavior::new()),
+            Layer::new(ORIENT, Quitter).optional(),
+        ]);
+        match tick(&mut merge) {
+            Action::Abort(reason) => assert_eq!(reason, "giving up"),
+            _ => panic!("expected an abort"),
+        }
+    }
+}
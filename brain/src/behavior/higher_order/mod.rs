@@ -1,6 +1,8 @@
 pub use self::{
     chain::Chain,
+    merge::{Channel, Layer, Merge, ALL},
     run_while::{Predicate, While},
+    scored_choose::{earliest_arrival, ChoiceMetric, ScoredChoose},
     time_limit::TimeLimit,
     try_choose::TryChoose,
     with_draw::WithDraw,
@@ -12,11 +14,13 @@ pub use self::{fuse::Fuse, null::NullBehavior, repeat::Repeat};
 mod chain;
 #[cfg(test)]
 mod fuse;
+mod merge;
 #[cfg(test)]
 mod null;
 #[allow(dead_code)]
 mod repeat;
 mod run_while;
+mod scored_choose;
 mod time_limit;
 mod try_choose;
 #[allow(dead_code)]
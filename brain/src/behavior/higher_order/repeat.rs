@@ -33,6 +33,10 @@ where
         stringify!(Repeat)
     }
 
+    fn active_child(&self) -> Option<(usize, &dyn Behavior)> {
+        Some((0, &self.current))
+    }
+
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
         ctx.eeg
             .draw(Drawable::print(self.current.blurb(), color::YELLOW));
@@ -40,7 +44,7 @@ where
             Action::Yield(i) => Action::Yield(i),
             Action::TailCall(b) => Action::TailCall(b),
             Action::RootCall(i) => Action::RootCall(i),
-            Action::Return | Action::Abort => {
+            Action::Return(_) | Action::Abort(_) => {
                 ctx.eeg.log(self.name(), "repeating");
                 self.current = (self.factory)();
                 Action::Yield(Default::default())
@@ -22,10 +22,21 @@ where
     P: Predicate,
 {
     pub fn new(predicate: P, child: impl Behavior + 'static) -> Self {
-        Self {
-            predicate,
-            child: Box::new(child),
-        }
+        Self::boxed(predicate, Box::new(child))
+    }
+
+    pub fn boxed(predicate: P, child: Box<dyn Behavior>) -> Self {
+        Self { predicate, child }
+    }
+}
+
+impl Predicate for Box<dyn Predicate> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn evaluate(&mut self, ctx: &mut Context<'_>) -> bool {
+        (**self).evaluate(ctx)
     }
 }
 
@@ -41,10 +52,14 @@ where
         self.child.priority()
     }
 
+    fn active_child(&self) -> Option<(usize, &dyn Behavior)> {
+        Some((0, &*self.child))
+    }
+
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
         if !self.predicate.evaluate(ctx) {
             ctx.eeg.log(self.name(), "terminating");
-            return Action::Return;
+            return Action::return_("condition no longer holds");
         }
 
         ctx.eeg
@@ -60,8 +75,8 @@ where
                 self.execute_old(ctx)
             }
             Action::RootCall(b) => Action::RootCall(b),
-            Action::Return => Action::Return,
-            Action::Abort => Action::Abort,
+            Action::Return(reason) => Action::Return(reason),
+            Action::Abort(reason) => Action::Abort(reason),
         }
     }
 }
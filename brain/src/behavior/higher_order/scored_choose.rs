@@ -0,0 +1,373 @@
+use crate::{
+    eeg::{color, Drawable},
+    strategy::{Action, Behavior, Context, Priority, Prospect},
+};
+use itertools::Itertools;
+use nameof::name_of_type;
+
+/// Rates a child after its first tick. Higher is better; `Err` explains why
+/// the child can't be rated.
+pub type ChoiceMetric = fn(&mut Context<'_>, &dyn Behavior) -> Result<f32, String>;
+
+/// Dry-run every child for one frame, then commit to whichever one `metric`
+/// rates highest. Unlike `TryChoose`, the order of `choices` only matters for
+/// breaking ties.
+///
+/// Every child really runs against the live `Context`, so each one pays its
+/// full first-tick cost (e.g. a `FollowRoute` plans its whole route) and its
+/// log lines and tracked events go through. Only the winner's drawings and
+/// quick chat are kept.
+pub struct ScoredChoose {
+    priority: Priority,
+    metric: ChoiceMetric,
+    choices: Vec<Box<dyn Behavior>>,
+    chosen_index: Option<usize>,
+    choice_names: String,
+    blurb: String,
+}
+
+impl ScoredChoose {
+    pub fn new(priority: Priority, metric: ChoiceMetric, choices: Vec<Box<dyn Behavior>>) -> Self {
+        let choice_names = choices.iter().map(|b| b.name()).join(", ");
+        let blurb = format!("{} ({})", name_of_type!(ScoredChoose), choice_names);
+        Self {
+            priority,
+            metric,
+            choices,
+            chosen_index: None,
+            choice_names,
+            blurb,
+        }
+    }
+
+    /// Choose the child which expects to finish soonest.
+    pub fn earliest_arrival(priority: Priority, choices: Vec<Box<dyn Behavior>>) -> Self {
+        Self::new(priority, earliest_arrival, choices)
+    }
+}
+
+/// A `ChoiceMetric` which prefers the earliest `Prospect::arrival_time`.
+pub fn earliest_arrival(_ctx: &mut Context<'_>, behavior: &dyn Behavior) -> Result<f32, String> {
+    match behavior.prospect().arrival_time {
+        Some(time) => Ok(-time),
+        None => Err("no arrival time".to_string()),
+    }
+}
+
+impl Behavior for ScoredChoose {
+    fn name(&self) -> &str {
+        name_of_type!(ScoredChoose)
+    }
+
+    fn blurb(&self) -> &str {
+        &self.blurb
+    }
+
+    fn priority(&self) -> Priority {
+        self.priority
+    }
+
+    fn prospect(&self) -> Prospect {
+        match self.chosen_index {
+            Some(index) => self.choices[index].prospect(),
+            None => Prospect::default(),
+        }
+    }
+
+    fn active_child(&self) -> Option<(usize, &dyn Behavior)> {
+        let index = self.chosen_index?;
+        Some((index, &*self.choices[index]))
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg
+            .draw(Drawable::print(self.choice_names.as_str(), color::GREEN));
+
+        if let Some(chosen_index) = self.chosen_index {
+            let behavior = &mut self.choices[chosen_index];
+            ctx.eeg
+                .draw(Drawable::print(behavior.name(), color::YELLOW));
+            return behavior.execute_old(ctx);
+        }
+
+        // If we get here, we need to choose a child behavior. This will only happen on
+        // the first frame.
+
+        ctx.eeg
+            .log(self.name(), format!("scoring {}", self.choice_names));
+
+        self.exec(ctx)
+    }
+}
+
+impl ScoredChoose {
+    fn exec(&mut self, ctx: &mut Context<'_>) -> Action {
+        // Each child gets one tick. Their inputs, drawings and quick chats are held
+        // back until we know which one wins.
+        let quick_chat = ctx.eeg.quick_chat.take();
+        let mut best: Option<(usize, f32, DryRun)> = None;
+        for index in 0..self.choices.len() {
+            let run = match self.dry_run(ctx, index) {
+                Ok(run) => run,
+                Err(reason) => {
+                    self.reject(ctx, index, &reason);
+                    continue;
+                }
+            };
+            // Hack: Let recovery behaviors bubble to the root
+            if let Action::RootCall(_) = run.action {
+                return run.commit(ctx, quick_chat);
+            }
+
+            let score = match (self.metric)(ctx, &*self.choices[index]) {
+                Ok(score) => score,
+                Err(reason) => {
+                    self.reject(ctx, index, &reason);
+                    continue;
+                }
+            };
+            ctx.eeg
+                .log(self.name(), format!("index {} scored {:.2}", index, score));
+
+            if let Some((best_index, best_score, _)) = best {
+                if score <= best_score {
+                    let reason = format!(
+                        "{:.2} <= {:.2} from index {}",
+                        score, best_score, best_index
+                    );
+                    self.reject(ctx, index, &reason);
+                    continue;
+                }
+                let reason = format!("{:.2} from index {} is better", score, index);
+                self.reject(ctx, best_index, &reason);
+            }
+            best = Some((index, score, run));
+        }
+
+        match best {
+            Some((index, _, run)) => {
+                ctx.eeg.log(
+                    self.name(),
+                    format!("choosing index {}: {}", index, self.choices[index].blurb()),
+                );
+                self.chosen_index = Some(index);
+                run.commit(ctx, quick_chat)
+            }
+            None => {
+                ctx.eeg.quick_chat = quick_chat;
+                Action::abort("none suitable")
+            }
+        }
+    }
+
+    /// Tick a child once, following tail calls, and set aside whatever it drew
+    /// or said. Returns `Err` if the child finishes immediately.
+    fn dry_run(&mut self, ctx: &mut Context<'_>, index: usize) -> Result<DryRun, String> {
+        let mark = ctx.eeg.draw_mark();
+        let action = self.tick(ctx, index);
+        let drawn = ctx.eeg.take_drawn_since(mark);
+        let quick_chat = ctx.eeg.quick_chat.take();
+        Ok(DryRun {
+            action: action?,
+            drawn,
+            quick_chat,
+        })
+    }
+
+    fn tick(&mut self, ctx: &mut Context<'_>, index: usize) -> Result<Action, String> {
+        match self.choices[index].execute_old(ctx) {
+            Action::Yield(input) => Ok(Action::Yield(input)),
+            Action::TailCall(behavior) => {
+                self.choices[index] = behavior;
+                self.tick(ctx, index)
+            }
+            Action::RootCall(b) => Ok(Action::RootCall(b)),
+            Action::Return(reason) => Err(format!("returned immediately: {}", reason)),
+            Action::Abort(reason) => Err(format!("aborted immediately: {}", reason)),
+        }
+    }
+
+    fn reject(&self, ctx: &mut Context<'_>, index: usize, reason: &str) {
+        ctx.eeg.log(
+            self.name(),
+            format!(
+                "rejecting index {} ({}): {}",
+                index,
+                self.choices[index].blurb(),
+                reason,
+            ),
+        );
+    }
+}
+
+/// The held-back output of one child's first tick.
+struct DryRun {
+    action: Action,
+    drawn: Vec<Drawable>,
+    quick_chat: Option<rlbot::flat::QuickChatSelection>,
+}
+
+impl DryRun {
+    /// Let this child's output through, as if it were the only one that ran.
+    fn commit(
+        self,
+        ctx: &mut Context<'_>,
+        quick_chat: Option<rlbot::flat::QuickChatSelection>,
+    ) -> Action {
+        ctx.eeg.draw_all(self.drawn);
+        ctx.eeg.quick_chat = self.quick_chat.or(quick_chat);
+        self.action
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        behavior::higher_order::{NullBehavior, ScoredChoose},
+        eeg::{color, Drawable, EEG},
+        helpers::ball::ChipBallPrediction,
+        routing::test::fixed_packet,
+        strategy::{Action, Behavior, Context, Game, Priority, Prospect, Scenario},
+    };
+    use common::halfway_house::PlayerInput;
+
+    /// Draws and chats on every tick, and claims it will arrive after `time`.
+    struct Arriving {
+        time: f32,
+        throttle: f32,
+    }
+
+    impl Behavior for Arriving {
+        fn name(&self) -> &str {
+            stringify!(Arriving)
+        }
+
+        fn prospect(&self) -> Prospect {
+            Prospect {
+                arrival_time: Some(self.time),
+            }
+        }
+
+        fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+            ctx.eeg
+                .draw(Drawable::print(format!("{}", self.time), color::GREEN));
+            ctx.eeg
+                .quick_chat(rlbot::flat::QuickChatSelection::Information_IGotIt);
+            Action::Yield(PlayerInput {
+                Throttle: self.throttle,
+                ..Default::default()
+            })
+        }
+    }
+
+    struct Quitter;
+
+    impl Behavior for Quitter {
+        fn name(&self) -> &str {
+            stringify!(Quitter)
+        }
+
+        fn execute_old(&mut self, _ctx: &mut Context<'_>) -> Action {
+            Action::abort("giving up")
+        }
+    }
+
+    struct Panicker;
+
+    impl Behavior for Panicker {
+        fn name(&self) -> &str {
+            stringify!(Panicker)
+        }
+
+        fn execute_old(&mut self, _ctx: &mut Context<'_>) -> Action {
+            Action::RootCall(Box::new(NullBehavior::new()))
+        }
+    }
+
+    /// Run `behavior` for one frame. Returns its action and how many things
+    /// were drawn.
+    fn tick(behavior: &mut dyn Behavior, eeg: &mut EEG) -> (Action, usize) {
+        let packet = fixed_packet();
+        let game = Game::fake_soccar(&packet, 0);
+        let ball_predictor = ChipBallPrediction::new();
+        let scenario = Scenario::new(&game, &ball_predictor, &packet);
+        let mut last_quick_chat = 0.0;
+        eeg.begin(&packet);
+        let mut ctx = Context::new(&game, &packet, &scenario, eeg, &mut last_quick_chat);
+        let action = behavior.execute_old(&mut ctx);
+        let drawn = ctx.eeg.draw_mark();
+        eeg.show(&packet);
+        (action, drawn)
+    }
+
+    fn arriving(time: f32, throttle: f32) -> Box<dyn Behavior> {
+        Box::new(Arriving { time, throttle })
+    }
+
+    #[test]
+    fn chooses_the_earliest_arrival() {
+        let mut behavior = ScoredChoose::earliest_arrival(Priority::Strike, vec![
+            arriving(2.0, 0.25),
+            Box::new(Quitter),
+            arriving(1.0, 0.5),
+            arriving(3.0, 0.75),
+        ]);
+        let mut eeg = EEG::new();
+
+        let (action, drawn) = tick(&mut behavior, &mut eeg);
+        match action {
+            Action::Yield(input) => assert_eq!(input.Throttle, 0.5),
+            _ => panic!("expected a yield"),
+        }
+        assert_eq!(behavior.active_child().map(|(i, _)| i), Some(2));
+        assert_eq!(behavior.prospect().arrival_time, Some(1.0));
+        // Our own list of names, plus the winner's drawing. The losers' drawings
+        // are discarded.
+        assert_eq!(drawn, 2);
+        assert!(eeg.quick_chat.is_some());
+
+        // After the first frame, only the winner runs.
+        let (action, drawn) = tick(&mut behavior, &mut eeg);
+        match action {
+            Action::Yield(input) => assert_eq!(input.Throttle, 0.5),
+            _ => panic!("expected a yield"),
+        }
+        assert_eq!(drawn, 3);
+    }
+
+    #[test]
+    fn aborts_if_every_child_is_rejected() {
+        let mut behavior = ScoredChoose::earliest_arrival(Priority::Strike, vec![
+            Box::new(Quitter),
+            // Never finishes, but has no arrival time to score.
+            Box::new(NullBehavior::new()),
+        ]);
+        let mut eeg = EEG::new();
+
+        let (action, _) = tick(&mut behavior, &mut eeg);
+        match action {
+            Action::Abort(reason) => assert_eq!(reason, "none suitable"),
+            _ => panic!("expected an abort"),
+        }
+        assert!(behavior.active_child().is_none());
+    }
+
+    #[test]
+    fn root_calls_bubble_up_immediately() {
+        let mut behavior = ScoredChoose::earliest_arrival(Priority::Strike, vec![
+            arriving(2.0, 0.25),
+            Box::new(Panicker),
+            arriving(1.0, 0.5),
+        ]);
+        let mut eeg = EEG::new();
+
+        let (action, drawn) = tick(&mut behavior, &mut eeg);
+        match action {
+            Action::RootCall(b) => assert_eq!(b.name(), "NullBehavior"),
+            _ => panic!("expected a root call"),
+        }
+        // Nothing drawn by the children that lost out to the root call.
+        assert_eq!(drawn, 1);
+        assert!(eeg.quick_chat.is_none());
+    }
+}
@@ -23,12 +23,16 @@ impl Behavior for TimeLimit {
         name_of_type!(TimeLimit)
     }
 
+    fn active_child(&self) -> Option<(usize, &dyn Behavior)> {
+        Some((0, &*self.child))
+    }
+
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
         let now = ctx.packet.GameInfo.TimeSeconds;
         let start = *self.start.get_or_insert(now);
         let elapsed = now - start;
         if elapsed >= self.limit {
-            Action::Abort
+            Action::abort(format!("over the time limit of {}s", self.limit))
         } else {
             self.child.execute_old(ctx)
         }
@@ -1,6 +1,6 @@
 use crate::{
     eeg::{color, Drawable},
-    strategy::{Action, Behavior, Context, Priority},
+    strategy::{Action, Behavior, Context, Priority, Prospect},
 };
 use itertools::Itertools;
 use nameof::name_of_type;
@@ -46,6 +46,18 @@ impl Behavior for TryChoose {
         self.priority
     }
 
+    fn prospect(&self) -> Prospect {
+        match self.chosen_index {
+            Some(index) => self.choices[index].prospect(),
+            None => Prospect::default(),
+        }
+    }
+
+    fn active_child(&self) -> Option<(usize, &dyn Behavior)> {
+        let index = self.chosen_index?;
+        Some((index, &*self.choices[index]))
+    }
+
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
         ctx.eeg
             .draw(Drawable::print(self.choice_names.as_str(), color::GREEN));
@@ -78,8 +90,7 @@ impl TryChoose {
             }
         }
 
-        ctx.eeg.log(self.name(), "none suitable");
-        Action::Abort
+        Action::abort("none suitable")
     }
 
     fn try_index(&mut self, ctx: &mut Context<'_>, index: usize) -> Option<Action> {
@@ -105,8 +116,16 @@ impl TryChoose {
             }
             // Hack: Let recovery behaviors bubble to the root
             Action::RootCall(b) => Some(Action::RootCall(b)),
-            Action::Return => None,
-            Action::Abort => None,
+            Action::Return(reason) => {
+                ctx.eeg
+                    .log(self.name(), format!("index {} returned: {}", index, reason));
+                None
+            }
+            Action::Abort(reason) => {
+                ctx.eeg
+                    .log(self.name(), format!("index {} aborted: {}", index, reason));
+                None
+            }
         }
     }
 }
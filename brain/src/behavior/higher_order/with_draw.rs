@@ -23,6 +23,10 @@ impl<B: Behavior> Behavior for WithDraw<B> {
         self.behavior.priority()
     }
 
+    fn active_child(&self) -> Option<(usize, &dyn Behavior)> {
+        Some((0, &self.behavior))
+    }
+
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
         for d in self.draw.iter() {
             ctx.eeg.draw(d.clone());
@@ -89,8 +89,7 @@ impl Behavior for Kickoff {
 
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
         if !PreKickoff::is_kickoff(&ctx.packet.GameBall) {
-            ctx.eeg.log(self.name(), "not a kickoff");
-            return Action::Abort;
+            return Action::abort("not a kickoff");
         }
 
         // Add a "random" component to kickoffs, to keep things unpredictable.
@@ -109,8 +108,9 @@ impl Behavior for Kickoff {
                 (500.0 + rand1 * 25.0) * x_signum,
                 (950.0 + rand2 * 25.0) * y_signum,
             );
-            let straight =
-                GroundStraightPlanner::new(straight_loc, StraightMode::Asap).allow_dodging(false);
+            let straight = GroundStraightPlanner::new(straight_loc, StraightMode::Asap)
+                .allow_dodging(false)
+                .allow_speed_flip(true);
             let turn_loc = Point2::new((100.0 + rand3 * 25.0) * x_signum, 0.0);
             let turn = TurnPlanner::new(turn_loc, None);
             Box::new(ChainedPlanner::chain(vec![
@@ -123,7 +123,9 @@ impl Behavior for Kickoff {
                 (2500.0 + rand2 * 25.0) * y_signum,
             );
             Box::new(
-                GroundStraightPlanner::new(target_loc, StraightMode::Asap).allow_dodging(false),
+                GroundStraightPlanner::new(target_loc, StraightMode::Asap)
+                    .allow_dodging(false)
+                    .allow_speed_flip(true),
             )
         } else {
             // This is basically a nop since the segment runs with `StraightMode::Fake`.
@@ -170,8 +172,7 @@ impl Behavior for KickoffStrike {
 
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
         if !PreKickoff::is_kickoff(&ctx.packet.GameBall) {
-            ctx.eeg.log(self.name(), "not a kickoff");
-            return Action::Abort;
+            return Action::abort("not a kickoff");
         }
 
         let ball_loc = ctx.packet.GameBall.Physics.loc_2d();
@@ -250,7 +251,7 @@ impl Behavior for RoughAngledChip {
 
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
         if !PreKickoff::is_kickoff(&ctx.packet.GameBall) {
-            return Action::Return;
+            return Action::return_("kickoff is over");
         }
 
         let target_loc = Point2::new(140.0 * ctx.me().Physics.loc().x.signum(), 0.0);
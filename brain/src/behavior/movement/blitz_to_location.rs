@@ -1,24 +1,36 @@
 use crate::{
     behavior::{
         higher_order::Chain,
-        movement::{simple_steer_towards, QuickJumpAndDodge, Yielder},
+        movement::{simple_steer_towards, QuickJumpAndDodge, SpeedFlip, Yielder},
     },
     eeg::Drawable,
+    routing::plan::avoid_opponents_waypoint,
     strategy::{Action, Behavior, Context},
 };
 use common::{prelude::*, rl, Distance};
 use nalgebra::Point2;
 use nameof::name_of_type;
+use simulate::CarSpeedFlip;
 use std::f32::consts::PI;
 use vec_box::vec_box;
 
 pub struct BlitzToLocation {
     target_loc: Point2<f32>,
+    avoid_opponents: bool,
 }
 
 impl BlitzToLocation {
     pub fn new(target_loc: Point2<f32>) -> BlitzToLocation {
-        BlitzToLocation { target_loc }
+        BlitzToLocation {
+            target_loc,
+            avoid_opponents: false,
+        }
+    }
+
+    /// Steer around opponents who are predicted to be in the way.
+    pub fn avoid_opponents(mut self, avoid_opponents: bool) -> Self {
+        self.avoid_opponents = avoid_opponents;
+        self
     }
 }
 
@@ -32,7 +44,13 @@ impl Behavior for BlitzToLocation {
         let distance = (me.Physics.loc_2d() - self.target_loc).norm();
         let speed = me.Physics.vel().norm();
 
-        let steer = simple_steer_towards(&me.Physics, self.target_loc);
+        let steer_loc = if self.avoid_opponents {
+            avoid_opponents_waypoint(ctx.game, &me.into(), self.target_loc, 0.0)
+                .unwrap_or(self.target_loc)
+        } else {
+            self.target_loc
+        };
+        let steer = simple_steer_towards(&me.Physics, steer_loc);
 
         ctx.eeg.draw(Drawable::ghost_car_ground(
             self.target_loc,
@@ -40,6 +58,21 @@ impl Behavior for BlitzToLocation {
         ));
         ctx.eeg.print_value("distance", Distance(distance));
 
+        // Should we speed flip? When starting out slow, this gets up to speed
+        // quicker than boosting alone.
+        if me.OnGround
+            && me.Physics.rot().pitch().to_degrees() < 1.0
+            && speed < 1500.0
+            && steer.abs() < PI / 24.0
+        {
+            if let Some(flip) = CarSpeedFlip::calc_1d(speed, me.Boost as f32) {
+                // Leave some leeway for recovery, same as with the dodge below.
+                if distance > flip.end_dist * 1.5 {
+                    return Action::tail_call(SpeedFlip::new().side(steer));
+                }
+            }
+        }
+
         // Should we boost?
         if distance > 1000.0
             && me.OnGround
@@ -55,8 +55,7 @@ impl Behavior for Dodge {
 
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
         if ctx.me().OnGround {
-            ctx.eeg.log(self.name(), "can't dodge while on ground");
-            return Action::Abort;
+            return Action::abort("can't dodge while on ground");
         }
 
         let (pitch, yaw) = match self.direction {
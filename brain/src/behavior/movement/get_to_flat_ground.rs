@@ -1,6 +1,6 @@
 use crate::{
     behavior::{
-        higher_order::Chain,
+        higher_order::{Chain, Channel, Layer, Merge, ALL},
         movement::{dodge::Dodge, drive_towards::drive_towards, land::Land},
     },
     eeg::{color, Drawable},
@@ -34,7 +34,7 @@ impl Behavior for GetToFlatGround {
 
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
         if Self::on_flat_ground(ctx.me()) {
-            return Action::Return;
+            return Action::return_("on flat ground");
         }
 
         let me = ctx.me();
@@ -149,15 +149,19 @@ fn jump_down_from_the_wall(ctx: &mut Context<'_>) -> Action {
                 ..Default::default()
             },
         )));
-        // Release jump.
-        inputs.push(Box::new(YieldAndMaybePanicBoost::new(
-            0.1,
-            common::halfway_house::PlayerInput {
-                Pitch: 1.0,
-                Jump: false,
-                ..Default::default()
-            },
-        )));
+        // Release jump. We're in the air by now, so let `Land` start rolling us
+        // towards our landing surface. Only roll, so we keep pitching back.
+        inputs.push(Box::new(Merge::new(Priority::Idle, vec![
+            Layer::new(&[Channel::Roll], Land::new()).optional(),
+            Layer::new(
+                ALL,
+                YieldAndMaybePanicBoost::new(0.1, common::halfway_house::PlayerInput {
+                    Pitch: 1.0,
+                    Jump: false,
+                    ..Default::default()
+                }),
+            ),
+        ])));
         // Maybe dodge.
         if let Some(target_loc) = dodge_target(ctx) {
             inputs.push(Box::new(Dodge::new().towards(target_loc)));
@@ -231,7 +235,7 @@ impl Behavior for YieldAndMaybePanicBoost {
                 ..self.input
             })
         } else {
-            Action::Return
+            Action::return_("time is up")
         }
     }
 }
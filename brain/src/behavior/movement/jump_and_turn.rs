@@ -38,12 +38,11 @@ impl Behavior for JumpAndTurn {
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
         let elapsed = self.time.tick(ctx.packet.GameInfo.TimeSeconds);
         if elapsed >= self.total_duration {
-            return Action::Return;
+            return Action::return_("time is up");
         }
 
         if elapsed >= 0.1 && ctx.me().OnGround {
-            ctx.eeg.log(self.name(), "I thought I jumped");
-            return Action::Abort;
+            return Action::abort("I thought I jumped");
         }
 
         ctx.eeg.print_value("target_rot", self.target_rot);
@@ -25,7 +25,7 @@ impl Behavior for Land {
         let me = ctx.me();
 
         if me.OnGround {
-            return Action::Return;
+            return Action::return_("landed");
         }
 
         if !self.chatted {
@@ -8,6 +8,7 @@ pub use self::{
     quick_jump_and_dodge::QuickJumpAndDodge,
     simple_steer_towards::{simple_steer_towards, simple_yaw_diff},
     skid_recover::SkidRecover,
+    speed_flip::SpeedFlip,
     yielder::Yielder,
 };
 
@@ -22,6 +23,7 @@ mod land;
 mod quick_jump_and_dodge;
 mod simple_steer_towards;
 mod skid_recover;
+mod speed_flip;
 #[cfg(test)]
 mod wall_drive;
 mod yielder;
@@ -72,8 +72,7 @@ impl Behavior for QuickJumpAndDodge {
 
         if self.phase == Phase::Jump || elapsed < self.dodge_time - Self::MIN_PHASE_TIME {
             if self.phase == Phase::Jump && !ctx.me().OnGround {
-                ctx.eeg.log(self.name(), "wheels must be on ground");
-                return Action::Abort;
+                return Action::abort("wheels must be on ground");
             }
 
             self.phase = Phase::And;
@@ -84,8 +83,7 @@ impl Behavior for QuickJumpAndDodge {
             })
         } else if self.phase == Phase::And || elapsed < self.dodge_time {
             if ctx.me().DoubleJumped {
-                ctx.eeg.log(self.name(), "must have air charge");
-                return Action::Abort;
+                return Action::abort("must have air charge");
             }
 
             self.phase = Phase::Dodge;
@@ -93,8 +91,7 @@ impl Behavior for QuickJumpAndDodge {
             Action::Yield(Default::default())
         } else if self.phase == Phase::Dodge || elapsed < self.dodge_time + 0.1 {
             if ctx.me().OnGround {
-                ctx.eeg.log(self.name(), "goomba stomped?");
-                return Action::Abort;
+                return Action::abort("goomba stomped?");
             }
 
             self.phase = Phase::FollowThrough;
@@ -105,14 +102,14 @@ impl Behavior for QuickJumpAndDodge {
         {
             if ctx.me().OnGround {
                 ctx.eeg.log(self.name(), "we landed early somehow");
-                return Action::Return;
+                return Action::return_("landed early");
             }
 
             self.phase = Phase::Finished;
 
             Action::Yield(Default::default())
         } else {
-            Action::Return
+            Action::return_("finished dodging")
         }
     }
 }
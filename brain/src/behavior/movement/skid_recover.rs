@@ -21,12 +21,11 @@ impl Behavior for SkidRecover {
 
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
         if !GetToFlatGround::on_flat_ground(ctx.me()) {
-            ctx.eeg.log(self.name(), "must be on flat ground");
-            return Action::Abort;
+            return Action::abort("must be on flat ground");
         }
 
         if !IsSkidding.evaluate(&ctx.me().into()) {
-            return Action::Return;
+            return Action::return_("not skidding");
         }
 
         let me = ctx.me();
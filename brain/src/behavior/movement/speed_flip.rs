@@ -0,0 +1,102 @@
+use crate::strategy::{Action, Behavior, Context, Priority};
+use nameof::name_of_type;
+
+/// A diagonal dodge which is canceled immediately so the car keeps its nose
+/// down, while boosting the entire time.
+pub struct SpeedFlip {
+    start_time: Option<f32>,
+    /// Which way to dodge: `1.0` for right, `-1.0` for left.
+    side: f32,
+}
+
+impl SpeedFlip {
+    const JUMP_TIME: f32 = 0.05;
+    const WAIT_TIME: f32 = 2.0 / 120.0;
+    const DODGE_TIME: f32 = 0.05;
+    const DODGE_END_TIME: f32 = Self::JUMP_TIME + Self::WAIT_TIME + Self::DODGE_TIME;
+    /// Give up on waiting for the wheels to touch the ground after this long.
+    const MAX_DURATION: f32 = 1.0;
+
+    pub fn new() -> Self {
+        Self {
+            start_time: None,
+            side: -1.0,
+        }
+    }
+
+    /// Dodge towards the side with the same sign as `side`.
+    pub fn side(mut self, side: f32) -> Self {
+        self.side = if side >= 0.0 { 1.0 } else { -1.0 };
+        self
+    }
+}
+
+impl Behavior for SpeedFlip {
+    fn name(&self) -> &str {
+        name_of_type!(SpeedFlip)
+    }
+
+    fn priority(&self) -> Priority {
+        Priority::Force
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        if self.start_time.is_none() && !ctx.me().OnGround {
+            return Action::abort("wheels must be on ground");
+        }
+
+        let start_time = *self
+            .start_time
+            .get_or_insert(ctx.packet.GameInfo.TimeSeconds);
+        let elapsed = ctx.packet.GameInfo.TimeSeconds - start_time;
+
+        ctx.eeg.print_time("elapsed", elapsed);
+
+        if elapsed < Self::JUMP_TIME {
+            Action::Yield(common::halfway_house::PlayerInput {
+                Throttle: 1.0,
+                Jump: true,
+                Boost: true,
+                ..Default::default()
+            })
+        } else if elapsed < Self::JUMP_TIME + Self::WAIT_TIME {
+            Action::Yield(common::halfway_house::PlayerInput {
+                Throttle: 1.0,
+                Boost: true,
+                ..Default::default()
+            })
+        } else if elapsed < Self::DODGE_END_TIME {
+            if ctx.me().OnGround {
+                return Action::abort("goomba stomped?");
+            }
+
+            Action::Yield(common::halfway_house::PlayerInput {
+                Throttle: 1.0,
+                // A full diagonal on the stick dodges at `simulate::SPEED_FLIP_ANGLE`.
+                Pitch: -1.0,
+                Yaw: self.side,
+                Jump: true,
+                Boost: true,
+                ..Default::default()
+            })
+        } else if elapsed < Self::MAX_DURATION {
+            // Wait a few frames before checking for landing, in case the game has not
+            // caught up with us yet.
+            if elapsed >= Self::DODGE_END_TIME + 0.1 && ctx.me().OnGround {
+                return Action::return_("landed");
+            }
+
+            // Cancel the flip by pulling back, and roll against the dodge to land flat.
+            Action::Yield(common::halfway_house::PlayerInput {
+                Throttle: 1.0,
+                Pitch: 1.0,
+                Roll: -self.side,
+                Boost: true,
+                ..Default::default()
+            })
+        } else {
+            ctx.eeg.log(self.name(), "we never landed?");
+            Action::return_("time is up")
+        }
+    }
+}
@@ -23,23 +23,19 @@ impl Behavior for WallDrive {
         let me_forward = me.Physics.forward_axis();
 
         if !me.OnGround {
-            ctx.eeg.log(self.name(), "not on ground");
-            return Action::Abort;
+            return Action::abort("not on ground");
         }
 
         let current_plane = ctx.game.pitch().closest_plane(&me.Physics.loc());
         let target_plane = ctx.game.pitch().closest_plane(&self.target_loc);
 
         if current_plane.distance_to_point(&me.Physics.loc()) >= 100.0 {
-            ctx.eeg
-                .log(self.name(), "not on the plane we think we are?");
-            return Action::Abort;
+            return Action::abort("not on the plane we think we are?");
         }
 
         let steer_target_loc = if current_plane.normal.dot(&target_plane.normal) < 0.95 {
             let unfold = some_or_else!(target_plane.unfold(current_plane).ok(), {
-                ctx.eeg.log(self.name(), "can't unfold wall");
-                return Action::Abort;
+                return Action::abort("can't unfold wall");
             });
             unfold * self.target_loc
         } else {
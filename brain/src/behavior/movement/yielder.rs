@@ -40,7 +40,7 @@ impl Behavior for Yielder {
         if elapsed < self.duration {
             Action::Yield(self.input)
         } else {
-            Action::Return
+            Action::return_("time is up")
         }
     }
 }
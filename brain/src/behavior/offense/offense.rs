@@ -2,8 +2,9 @@ use crate::{
     behavior::offense::{ResetBehindBall, Shoot, TepidHit},
     eeg::Event,
     helpers::{ball::BallFrame, intercept::naive_ground_intercept_2},
+    params,
     routing::{behavior::FollowRoute, models::CarState, plan::GetDollar},
-    strategy::{Action, Behavior, Context, Game, Scenario},
+    strategy::{Action, Behavior, Context, Game},
     utils::geometry::RayCoordinateSystem,
 };
 use common::{prelude::*, Angle, Distance};
@@ -103,7 +104,12 @@ fn can_we_shoot(ctx: &mut Context<'_>) -> bool {
     // Don't just sit there for days waiting for the ball to roll. The more
     // possession we have, the longer we're willing to wait.
     let naive_intercept = naive_intercept.time.min(shoot_intercept.time);
-    let acceptable_delay = ctx.scenario.possession().max(0.5).min(2.0);
+    // When we need a goal, wait longer for a shot rather than give up on it.
+    let max_delay = ctx
+        .scenario
+        .match_state()
+        .loosen(params::SHOT_MAX_DELAY.get());
+    let acceptable_delay = ctx.scenario.possession().max(0.5).min(max_delay);
 
     if shoot_intercept.time >= naive_intercept + acceptable_delay {
         ctx.eeg.log(
@@ -120,7 +126,7 @@ fn playing_goalie(game: &Game<'_>, ball: &BallFrame) -> bool {
     let safe = game.own_goal().center_2d;
     let danger = game.enemy_goal().center_2d;
     let defensiveness = (ball.loc.to_2d() - danger).norm() / (ball.loc.to_2d() - safe).norm();
-    defensiveness >= 5.0
+    defensiveness >= params::GOALIE_DEFENSIVENESS.get()
 }
 
 fn flat_and_defensive(ctx: &mut Context<'_>) -> bool {
@@ -144,8 +150,13 @@ fn flat_and_defensive(ctx: &mut Context<'_>) -> bool {
 }
 
 fn slow_play(ctx: &mut Context<'_>) -> Option<Action> {
-    // Only slow play if we have enough time.
-    if ctx.scenario.possession() < 2.0 {
+    // Only slow play if we have enough time. Protecting a lead late in the game
+    // calls for more of it.
+    let threshold = ctx
+        .scenario
+        .match_state()
+        .loosen(params::SLOW_PLAY_POSSESSION.get());
+    if ctx.scenario.possession() < threshold {
         ctx.eeg
             .log(name_of_type!(Offense), "slow_play: need possession");
         return None;
@@ -244,7 +255,7 @@ fn get_boost(ctx: &mut Context<'_>) -> Option<Box<dyn Behavior>> {
         return None;
     }
 
-    if ctx.scenario.possession() < -Scenario::POSSESSION_CONTESTABLE
+    if ctx.scenario.possession() < -params::POSSESSION_CONTESTABLE.get()
         && ctx.scenario.enemy_shoot_score_seconds() >= 7.0
     {
         ctx.eeg.log(
@@ -309,7 +320,7 @@ fn poor_angle_swing_around(ctx: &mut Context<'_>) -> Option<Action> {
         return None;
     }
 
-    if ctx.scenario.possession() < -Scenario::POSSESSION_CONTESTABLE {
+    if ctx.scenario.possession() < -params::POSSESSION_CONTESTABLE.get() {
         ctx.eeg.log(
             name_of_type!(Offense),
             "poor_angle_swing_around: no possession",
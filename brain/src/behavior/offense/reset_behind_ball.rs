@@ -1,7 +1,8 @@
 use crate::{
     routing::{
         behavior::FollowRoute,
-        plan::{GetDollar, GroundDrive},
+        models::RoutePlanner,
+        plan::{GetDollar, GroundDrive, SearchPlanner},
     },
     strategy::{Action, Behavior, BoostPickup, Context},
 };
@@ -9,6 +10,7 @@ use common::prelude::*;
 use nalgebra::{Point2, Vector2};
 use nameof::name_of_type;
 use simulate::linear_interpolate;
+use std::{f32::consts::PI, time::Duration};
 
 pub struct ResetBehindBall {
     loc: Point2<f32>,
@@ -42,11 +44,27 @@ impl Behavior for ResetBehindBall {
             return Action::tail_call(FollowRoute::new(GetDollar::new(self.loc).pickup(pickup)));
         }
 
-        let straight = GroundDrive::new(target_loc)
-            .end_chop(0.5)
-            .always_prefer_dodge(true);
+        // `GroundDrive` is a fixed turn-then-straight recipe, which is at its worst when
+        // we need to turn around. In that case, search for something better.
+        let me = ctx.me();
+        let facing_away = me
+            .Physics
+            .forward_axis_2d()
+            .angle_to(&(target_loc - me.Physics.loc_2d()))
+            .abs()
+            >= PI / 2.0;
+        let planner: Box<dyn RoutePlanner> = if facing_away {
+            Box::new(SearchPlanner::new(target_loc))
+        } else {
+            Box::new(
+                GroundDrive::new(target_loc)
+                    .end_chop(0.5)
+                    .always_prefer_dodge(true),
+            )
+        };
         Action::tail_call(
-            FollowRoute::new(straight)
+            FollowRoute::new_boxed(planner)
+                .incremental(Duration::from_millis(1))
                 .same_ball_trajectory(true)
                 .never_recover(self.never_recover),
         )
@@ -7,7 +7,10 @@ use crate::{
         },
     },
     helpers::intercept::{naive_ground_intercept_2, NaiveIntercept},
-    routing::{behavior::FollowRoute, plan::GroundIntercept},
+    routing::{
+        behavior::FollowRoute,
+        plan::{FastestPlanner, GroundIntercept},
+    },
     strategy::{Action, Behavior, Context, Game, Priority, Scenario},
 };
 use common::{prelude::*, Speed};
@@ -15,6 +18,7 @@ use nalgebra::{Point2, Point3};
 use nameof::name_of_type;
 use simulate::linear_interpolate;
 use std::f32::consts::PI;
+use vec_box::vec_box;
 
 pub struct Shoot;
 
@@ -175,12 +179,17 @@ impl Behavior for Shoot {
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
         let intercept = Self::aim_calc(ctx.game, &ctx.scenario, ctx.me());
         if intercept.is_none() {
-            ctx.eeg.log(self.name(), "no viable shot");
-            return Action::Abort;
+            return Action::abort("no viable shot");
         }
 
+        // Whether a dodge pays off depends on the shot, so plan it both ways and take
+        // whichever gets there first.
+        let intercept = FastestPlanner::new(vec_box![
+            GroundIntercept::new().allow_dodging(true),
+            GroundIntercept::new().allow_dodging(false),
+        ]);
         Action::tail_call(Chain::new(Priority::Strike, vec![
-            Box::new(FollowRoute::new(GroundIntercept::new()).same_ball_trajectory(true)),
+            Box::new(FollowRoute::new(intercept).same_ball_trajectory(true)),
             Box::new(GroundedHit::hit_towards(Self::aim)),
         ]))
     }
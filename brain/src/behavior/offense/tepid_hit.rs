@@ -1,24 +1,25 @@
 use crate::{
     behavior::{
         defense::Defense,
+        higher_order::ScoredChoose,
         strike::{
             GroundedHit, GroundedHitAimContext, GroundedHitTarget, GroundedHitTargetAdjust, WallHit,
         },
     },
     eeg::{color, Drawable, Event, EEG},
     helpers::hit_angle::{blocking_angle, feasible_hit_angle_away, feasible_hit_angle_toward},
+    params,
     routing::{
         behavior::FollowRoute,
-        plan::{GetDollar, GroundIntercept, WallIntercept},
+        plan::{cheapest_tradeoff, GetDollar, GroundIntercept, WallIntercept},
     },
-    strategy::{Action, Behavior, Context, Context2, Priority, Scenario},
+    strategy::{Action, Behavior, Context, Context2, Priority},
     utils::{Wall, WallRayCalculator},
 };
 use arrayvec::ArrayVec;
 use common::{prelude::*, PrettyPrint, Time};
 use nalgebra::{Point2, Point3, Vector2};
 use nameof::name_of_type;
-use ordered_float::NotNan;
 use std::f32::consts::PI;
 
 pub struct TepidHit;
@@ -41,32 +42,40 @@ impl Behavior for TepidHit {
         hits.push(ground(&ctx, eeg));
         hits.push(wall(&ctx, eeg));
 
-        let hit = hits
-            .into_iter()
-            .flatten()
-            .min_by_key(|&(duration, ref typ)| {
-                eeg.log(
-                    self.name(),
-                    format!("{:?} would take {}", typ, Time(duration).pretty()),
-                );
-                NotNan::new(duration).unwrap()
+        // The estimates above are only rough, so if more than one kind of hit is
+        // feasible, plan them all and go with whichever route actually arrives
+        // first.
+        let mut choices = Vec::<Box<dyn Behavior>>::new();
+        for (duration, typ) in hits.into_iter().flatten() {
+            eeg.log(
+                self.name(),
+                format!("{:?} would take {}", typ, Time(duration).pretty()),
+            );
+            choices.push(match typ {
+                HitType::Wall => Box::new(chain!(Priority::Strike, [
+                    FollowRoute::new(
+                        WallIntercept::new()
+                            .must_be_wall(true)
+                            .must_be_side_wall(true)
+                    )
+                    .same_ball_trajectory(true),
+                    WallHit::new(),
+                ])),
+                HitType::Ground => {
+                    let boost_reserve = ground_boost_reserve(&ctx, eeg);
+                    Box::new(chain!(Priority::Strike, [
+                        FollowRoute::new(GroundIntercept::new().boost_reserve(boost_reserve))
+                            .same_ball_trajectory(true),
+                        GroundedHit::hit_towards(time_wasting_hit),
+                    ]))
+                }
             });
+        }
 
-        match hit {
-            Some((_, HitType::Wall)) => Action::tail_call(chain!(Priority::Strike, [
-                FollowRoute::new(
-                    WallIntercept::new()
-                        .must_be_wall(true)
-                        .must_be_side_wall(true)
-                )
-                .same_ball_trajectory(true),
-                WallHit::new(),
-            ])),
-            Some((_, HitType::Ground)) => Action::tail_call(chain!(Priority::Strike, [
-                FollowRoute::new(GroundIntercept::new()).same_ball_trajectory(true),
-                GroundedHit::hit_towards(time_wasting_hit),
-            ])),
-            None => Action::tail_call(FollowRoute::new(GetDollar::smart(&ctx, eeg))),
+        match choices.len() {
+            0 => Action::tail_call(FollowRoute::new(GetDollar::smart(&ctx, eeg))),
+            1 => Action::TailCall(choices.pop().unwrap()),
+            _ => Action::tail_call(ScoredChoose::earliest_arrival(Priority::Strike, choices)),
         }
     }
 }
@@ -82,6 +91,26 @@ fn ground(ctx: &Context2<'_, '_>, eeg: &mut EEG) -> Option<(f32, HitType)> {
     Some((intercept.t, HitType::Ground))
 }
 
+/// We're not in a rush, so don't empty the tank just to arrive a split second
+/// sooner.
+fn ground_boost_reserve(ctx: &Context2<'_, '_>, eeg: &mut EEG) -> f32 {
+    const MAX_DELAY: f32 = 0.2;
+
+    let curve = GroundIntercept::boost_tradeoff(&ctx.me().into(), ctx.scenario.ball_prediction());
+    let choice = some_or_else!(cheapest_tradeoff(&curve, MAX_DELAY), {
+        return 0.0;
+    });
+    eeg.log(
+        name_of_type!(TepidHit),
+        format!(
+            "spending {:.0} boost to arrive in {}",
+            choice.boost_spent,
+            Time(choice.arrival_time).pretty(),
+        ),
+    );
+    choice.boost_reserve
+}
+
 fn dangerous_back_wall_with_little_boost(
     ctx: &Context2<'_, '_>,
     intercept_loc: Point3<f32>,
@@ -136,7 +165,7 @@ fn time_wasting_hit(ctx: &mut GroundedHitAimContext<'_, '_>) -> Result<GroundedH
     let (aim_loc, target_adjust);
     if (ball_loc.y - defense_avoid.y).abs() < 500.0
         || ((ball_loc.y - defense_avoid.y).abs() < 1500.0
-            && ctx.scenario.possession() < Scenario::POSSESSION_CONTESTABLE)
+            && ctx.scenario.possession() < params::POSSESSION_CONTESTABLE.get())
     {
         ctx.eeg.track(Event::TepidHitBlockAngleToGoal);
         ctx.eeg
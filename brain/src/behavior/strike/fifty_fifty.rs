@@ -12,6 +12,9 @@ use nameof::name_of_type;
 pub struct FiftyFifty;
 
 impl FiftyFifty {
+    /// With less risk than this (see `MatchState`), a 50/50 isn't worth it.
+    pub const MIN_RISK: f32 = -0.5;
+
     pub fn new() -> Self {
         Self
     }
@@ -22,7 +25,11 @@ impl Behavior for FiftyFifty {
         name_of_type!(FiftyFifty)
     }
 
-    fn execute_old(&mut self, _ctx: &mut Context<'_>) -> Action {
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        if ctx.scenario.match_state().risk < Self::MIN_RISK {
+            return Action::abort("too risky given the score and clock");
+        }
+
         Action::tail_call(While::new(
             WeDontWinTheRace,
             Chain::new(Priority::Idle, vec![
@@ -5,7 +5,11 @@ use crate::{
         strike::BounceShot,
     },
     eeg::{color, Drawable, EEG},
-    helpers::intercept::{naive_ground_intercept, NaiveIntercept},
+    helpers::{
+        hit_angle::best_dodge_angle,
+        intercept::{naive_ground_intercept, NaiveIntercept},
+    },
+    params,
     routing::recover::{IsSkidding, NotOnFlatGround},
     strategy::{Action, Behavior, Context, Game, Priority, Scenario},
     utils::intercept_memory::{InterceptMemory, InterceptMemoryResult},
@@ -66,17 +70,15 @@ where
         let me = ctx.me();
 
         if IsSkidding.evaluate(&me.into()) {
-            ctx.eeg.log(self.name(), name_of_type!(IsSkidding));
-            return Action::Abort;
+            return Action::abort(name_of_type!(IsSkidding));
         }
         if NotOnFlatGround.evaluate(&me.into()) {
-            ctx.eeg.log(self.name(), name_of_type!(NotOnFlatGround));
-            return Action::Abort;
+            return Action::abort(name_of_type!(NotOnFlatGround));
         }
 
         let mut intercept = match self.intercept_loc(ctx) {
             Ok(i) => i,
-            Err(()) => return Action::Abort,
+            Err(()) => return Action::abort("no intercept"),
         };
 
         // Don't give up when there's a momentary blip in ball prediction
@@ -84,24 +86,21 @@ where
         intercept.ball_loc = match self.intercept.update(now, intercept.ball_loc, ctx.eeg) {
             InterceptMemoryResult::Stable(loc) => loc,
             InterceptMemoryResult::Unstable(_) => {
-                ctx.eeg.log(self.name(), "unstable intercept");
-                return Action::Abort;
+                return Action::abort("unstable intercept");
             }
         };
 
         let plan = match self.plan(ctx, &intercept) {
             Ok(x) => x,
             Err(()) => {
-                ctx.eeg.log(self.name(), "error finding target_loc");
-                return Action::Abort;
+                return Action::abort("error finding target_loc");
             }
         };
 
         let me_forward = me.Physics.forward_axis_2d();
         let steer = me_forward.angle_to(&(plan.target_loc - me.Physics.loc()).to_2d().to_axis());
         if steer.abs() >= PI / 3.0 {
-            ctx.eeg.log(self.name(), "not facing the target");
-            return Action::Abort;
+            return Action::abort("not facing the target");
         }
 
         let angle_to_ball =
@@ -160,7 +159,7 @@ where
         // code in tree)
         let own_goal = ctx.game.own_goal();
         let in_defense = own_goal.is_y_within_range(me.Physics.loc().y, ..1000.0);
-        if in_defense && ctx.scenario.possession().abs() < Scenario::POSSESSION_CONTESTABLE {
+        if in_defense && ctx.scenario.possession().abs() < params::POSSESSION_CONTESTABLE.get() {
             // Proceed below.
         } else {
             return Ok(intercept);
@@ -224,6 +223,7 @@ where
             intercept_time: target.intercept_time,
             target_loc,
             target_rot,
+            aim_loc: target.aim_loc,
             jump: target.jump,
             dodge: target.dodge,
         })
@@ -358,7 +358,7 @@ where
         )));
         steps.push(Box::new(AbortIfNotNearBall::new()));
         if plan.dodge {
-            steps.push(Box::new(Dodge::new().towards_ball()));
+            steps.push(Box::new(AimedDodge::new(plan.aim_loc)));
         } else {
             // If we're not dodging, force pushing the nose down, since sometimes the air
             // recovery does wonky things here.
@@ -420,13 +420,52 @@ impl Behavior for AbortIfNotNearBall {
         let car_loc = ctx.me().Physics.loc();
         let distance = (ball_loc - car_loc).norm();
         if distance < 600.0 {
-            Action::Return
+            Action::return_("close to the ball")
         } else {
-            ctx.eeg.log(
-                name_of_type!(AbortIfNotNearBall),
-                format!("distance is {:.0}", distance),
-            );
-            Action::Abort
+            Action::abort(format!("distance is {:.0}", distance))
+        }
+    }
+}
+
+/// Dodge in whichever direction best sends the ball towards `aim_loc`. The
+/// choice is made at the last moment, using the car's actual state at the peak
+/// of the jump.
+#[derive(new)]
+struct AimedDodge {
+    aim_loc: Point2<f32>,
+}
+
+impl Behavior for AimedDodge {
+    fn name(&self) -> &str {
+        stringify!(AimedDodge)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        let me = ctx.me();
+        let ball = &ctx.packet.GameBall;
+        let contact_dist = ctx.game.ball_radius() + ctx.game.me_vehicle().pivot_to_front_dist();
+        let angle = best_dodge_angle(
+            me.Physics.loc_2d(),
+            me.Physics.vel_2d(),
+            me.Physics.quat().to_2d(),
+            ball.Physics.loc_2d(),
+            ball.Physics.vel_2d(),
+            self.aim_loc,
+            contact_dist,
+        );
+        match angle {
+            Some(angle) => {
+                ctx.eeg.log(
+                    self.name(),
+                    format!("dodge angle {:.0}°", angle.angle().to_degrees()),
+                );
+                Action::tail_call(Dodge::new().angle(angle))
+            }
+            None => {
+                ctx.eeg
+                    .log(self.name(), "no angle connects; dodging at ball");
+                Action::tail_call(Dodge::new().towards_ball())
+            }
         }
     }
 }
@@ -475,6 +514,7 @@ struct Plan {
     intercept_time: f32,
     target_loc: Point3<f32>,
     target_rot: UnitQuaternion<f32>,
+    aim_loc: Point2<f32>,
     jump: bool,
     dodge: bool,
 }
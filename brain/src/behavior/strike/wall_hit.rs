@@ -50,13 +50,11 @@ impl Behavior for WallHit {
         let (ref ctx, ref mut eeg) = ctx.split();
 
         if !ctx.me().OnGround {
-            eeg.log(self.name(), "not on ground");
-            return Action::Abort;
+            return Action::abort("not on ground");
         }
 
         let intercept = some_or_else!(intercept(ctx), {
-            eeg.log(self.name(), "no viable intercept");
-            return Action::Abort;
+            return Action::abort("no viable intercept");
         });
 
         let intercept_time = intercept.t;
@@ -72,8 +70,7 @@ impl Behavior for WallHit {
         let path = match flat_target(ctx, eeg, &intercept_ball_loc) {
             Ok(x) => x,
             Err(()) => {
-                eeg.log(self.name(), "error finding target");
-                return Action::Abort;
+                return Action::abort("error finding target");
             }
         };
 
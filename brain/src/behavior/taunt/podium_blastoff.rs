@@ -66,7 +66,7 @@ impl PodiumBlastoff {
                 Action::Yield(i) => {
                     return Some(Action::Yield(i));
                 }
-                Action::Return => {
+                Action::Return(_) => {
                     self.child = None;
                     return None;
                 }
@@ -99,7 +99,7 @@ impl PodiumSpew {
                 Action::Yield(i) => {
                     return Some(Action::Yield(i));
                 }
-                Action::Return => {
+                Action::Return(_) => {
                     self.child = None;
                     return None;
                 }
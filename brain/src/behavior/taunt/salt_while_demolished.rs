@@ -1,15 +1,19 @@
-use crate::strategy::{Action, Behavior, Context, Priority};
+use crate::strategy::{Behavior2, Context2, Outcome, Priority, Sink};
 use nameof::name_of_type;
 
-pub struct SaltWhileDemolished;
+pub struct SaltWhileDemolished {
+    demolished: bool,
+}
 
 impl SaltWhileDemolished {
     pub fn new() -> Self {
-        Self
+        Self { demolished: false }
     }
 }
 
-impl Behavior for SaltWhileDemolished {
+impl Behavior2 for SaltWhileDemolished {
+    type Output = ();
+
     fn name(&self) -> &str {
         name_of_type!(SaltWhileDemolished)
     }
@@ -18,17 +22,20 @@ impl Behavior for SaltWhileDemolished {
         Priority::Taunt
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &Context2<'_, '_>, sink: &mut dyn Sink) -> Outcome<()> {
         if !ctx.me().Demolished {
-            return Action::Return;
+            if self.demolished {
+                // We've respawned.
+                return Outcome::Return(());
+            }
+            return Outcome::Abort("not demolished".to_string());
         }
+        self.demolished = true;
 
-        if ctx.packet.GameInfo.TimeSeconds - *ctx.last_quick_chat >= 0.75 {
-            ctx.eeg
-                .quick_chat(rlbot::flat::QuickChatSelection::Reactions_Okay);
-            *ctx.last_quick_chat = ctx.packet.GameInfo.TimeSeconds;
+        if ctx.can_quick_chat() {
+            sink.quick_chat(rlbot::flat::QuickChatSelection::Reactions_Okay);
         }
 
-        Action::Yield(Default::default())
+        Outcome::yield_(Default::default())
     }
 }
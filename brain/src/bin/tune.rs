@@ -0,0 +1,179 @@
+//! Search for better `params` by playing out a suite of cases in an offline
+//! simulation.
+//!
+//! Usage:
+//!
+//! ```sh
+//! cargo run --release -p brain --features tune --bin tune -- brain/tuning/space.json \
+//!     [--method evolve|random] [--budget 64] [--seed 1] [--out tuning-out]
+//! ```
+//!
+//! Writes `best-params.json` (loadable with `FORMULA_NONE_PARAMS`) and
+//! `report.txt` to the output directory.
+
+#![warn(future_incompatible, rust_2018_compatibility, rust_2018_idioms, unused)]
+#![cfg_attr(feature = "strict", deny(warnings))]
+#![warn(clippy::all)]
+
+use brain::{
+    params,
+    tune::{self, Evaluation, Method, SearchSpace, Trial, Weights},
+};
+use std::{env, error::Error, fmt::Write, fs, path::PathBuf, ptr};
+
+const USAGE: &str =
+    "usage: tune <space.json> [--method evolve|random] [--budget N] [--seed N] [--out DIR]";
+
+struct Args {
+    space: PathBuf,
+    method: Method,
+    budget: usize,
+    seed: u64,
+    out: PathBuf,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = env::args().skip(1);
+    let mut space = None;
+    let mut method = Method::Evolve;
+    let mut budget = 64;
+    let mut seed = 1;
+    let mut out = PathBuf::from("tuning-out");
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--method" => {
+                method = match value()?.as_str() {
+                    "evolve" => Method::Evolve,
+                    "random" => Method::Random,
+                    m => return Err(format!("unknown method {:?}", m)),
+                }
+            }
+            "--budget" => budget = value()?.parse().map_err(|_| "bad --budget")?,
+            "--seed" => seed = value()?.parse().map_err(|_| "bad --seed")?,
+            "--out" => out = PathBuf::from(value()?),
+            _ if space.is_none() && !arg.starts_with("--") => space = Some(PathBuf::from(&arg)),
+            _ => return Err(format!("unexpected argument {:?}", arg)),
+        }
+    }
+
+    let space = space.ok_or(USAGE)?;
+    Ok(Args {
+        space,
+        method,
+        budget,
+        seed,
+        out,
+    })
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = parse_args()?;
+    params::load_overrides()?;
+    let space = SearchSpace::load(&args.space)?;
+    let cases = tune::standard_suite();
+    let weights = Weights::default();
+    let start = space.current();
+
+    println!(
+        "Tuning {} parameters over {} cases, {} evaluations",
+        space.dimensions.len(),
+        cases.len(),
+        args.budget,
+    );
+
+    let mut evaluations = Vec::new();
+    let trials = tune::search(
+        &space,
+        &start,
+        args.method,
+        args.budget,
+        args.seed,
+        |values| {
+            space.apply(values).unwrap();
+            let evaluation = tune::evaluate(&cases, &weights);
+            let fitness = evaluation.fitness();
+            println!(
+                "{:>4}: {:>8.3}  {}",
+                evaluations.len(),
+                fitness,
+                describe(&space, values),
+            );
+            evaluations.push(evaluation);
+            fitness
+        },
+    );
+
+    let best = tune::best(&trials).unwrap();
+    let best_index = trials.iter().position(|t| ptr::eq(t, best)).unwrap();
+    let best_evaluation = &evaluations[best_index];
+    space.apply(&best.values)?;
+
+    fs::create_dir_all(&args.out)?;
+    fs::write(args.out.join("best-params.json"), params::dump())?;
+    let report = report(
+        &args,
+        &space,
+        &trials,
+        &evaluations[0],
+        best,
+        best_evaluation,
+    )?;
+    fs::write(args.out.join("report.txt"), &report)?;
+
+    print!("{}", report);
+    println!("Wrote results to {}", args.out.display());
+    Ok(())
+}
+
+fn describe(space: &SearchSpace, values: &[f32]) -> String {
+    space
+        .dimensions
+        .iter()
+        .zip(values)
+        .map(|(dim, value)| format!("{}={}", dim.name, value))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn report(
+    args: &Args,
+    space: &SearchSpace,
+    trials: &[Trial],
+    baseline: &Evaluation,
+    best: &Trial,
+    best_evaluation: &Evaluation,
+) -> Result<String, Box<dyn Error>> {
+    let mut r = String::new();
+    writeln!(
+        r,
+        "method {:?}, budget {}, seed {}",
+        args.method, args.budget, args.seed,
+    )?;
+    writeln!(r)?;
+    writeln!(r, "Parameters (start -> best):")?;
+    for (i, dim) in space.dimensions.iter().enumerate() {
+        writeln!(
+            r,
+            "  {:<40} {:>10} -> {:<10}",
+            dim.name, trials[0].values[i], best.values[i],
+        )?;
+    }
+    writeln!(r)?;
+    writeln!(r, "Baseline: {}", baseline)?;
+    writeln!(r, "Best: {}", best_evaluation)?;
+
+    let mut ranked: Vec<_> = trials.iter().collect();
+    ranked.sort_by(|a, b| b.fitness.partial_cmp(&a.fitness).unwrap());
+    writeln!(r, "Top trials:")?;
+    for trial in ranked.iter().take(10) {
+        writeln!(
+            r,
+            "  {:>8.3}  {}",
+            trial.fitness,
+            describe(space, &trial.values),
+        )?;
+    }
+    Ok(r)
+}
@@ -3,7 +3,11 @@ use crate::strategy::Behavior;
 use crate::{
     eeg::{color, Drawable, EEG},
     helpers::ball::{BallPredictor, ChipBallPrediction, FrameworkBallPrediction},
-    strategy::{infer_game_mode, Context, Dropshot, Game, Runner, Scenario, Soccar},
+    params,
+    strategy::{
+        infer_game_mode, ConfiguredStrategy, Context, Dropshot, Game, Runner, Scenario, Soccar,
+        StackFrame, StrategyConfig,
+    },
     utils::FPSCounter,
 };
 use common::{prelude::*, ControllerInput, ExtendDuration};
@@ -16,8 +20,9 @@ pub struct Brain {
     ball_predictor: Box<dyn BallPredictor>,
     player_index: Option<i32>,
     fps_counter: FPSCounter,
-    /// This is not automated or enforced in any way, it's just a convenient
-    /// memory slot for optional use in behaviors.
+    /// The game time of our most recent quick chat. `Behavior2` chats are
+    /// throttled against this automatically; old-style behaviors have to check
+    /// it themselves.
     last_quick_chat: f32,
 }
 
@@ -41,6 +46,14 @@ impl Brain {
         Self::new(Runner::new(Soccar::new()), ChipBallPrediction::new())
     }
 
+    /// Play soccar using a strategy loaded from a file.
+    pub fn soccar_with_strategy(config: StrategyConfig) -> Self {
+        Self::new(
+            Runner::new(ConfiguredStrategy::new(config)),
+            ChipBallPrediction::new(),
+        )
+    }
+
     pub fn dropshot(rlbot: &'static rlbot::RLBot) -> Self {
         Self::new(
             Runner::new(Dropshot::new()),
@@ -66,6 +79,11 @@ impl Brain {
         self.runner = Runner::with_current(behavior);
     }
 
+    /// The behaviors that ran during the last frame, from the root down.
+    pub fn behavior_stack(&self) -> &[StackFrame] {
+        self.runner.stack()
+    }
+
     pub fn set_player_index(&mut self, player_index: i32) {
         self.player_index = Some(player_index);
     }
@@ -120,6 +138,11 @@ impl Brain {
         let mut ctx = Context::new(&game, packet, &scenario, eeg, &mut self.last_quick_chat);
 
         ctx.eeg.print_time("possession", ctx.scenario.possession());
+        let match_state = ctx.scenario.match_state();
+        ctx.eeg.print_value(
+            "match",
+            format!("{:?} (risk {:.2})", match_state.phase, match_state.risk),
+        );
 
         let result = self.runner.execute_old(&mut ctx);
 
@@ -127,7 +150,7 @@ impl Brain {
         let duration = stop - start;
         let calc_ms = duration.as_millis_polyfill();
         // RL's physics runs at 120Hz, which leaves us ~8ms to make a decision.
-        if calc_ms >= 8 {
+        if calc_ms >= u128::from(params::SLOW_FRAME_MS.get()) {
             ctx.eeg.log(
                 name_of_type!(Brain),
                 format!("slow frame took {}ms", calc_ms),
@@ -1,4 +1,7 @@
-use crate::eeg::{color, window::Window};
+use crate::{
+    eeg::{color, window::Window},
+    strategy::StackFrame,
+};
 use common::{prelude::*, rl, Angle, Distance, PrettyPrint, Time};
 use graphics::types::Color;
 use nalgebra::{Point2, Point3, Rotation3};
@@ -10,6 +13,7 @@ pub struct EEG {
     current_packet_time: f32,
     draw_list: DrawList,
     pub events: Option<HashSet<Event>>,
+    pub stack_history: Option<StackHistory>,
     // I added quick-chat here only for convenience before a tournament, but it should really be
     // somewhere else…
     pub quick_chat: Option<rlbot::flat::QuickChatSelection>,
@@ -29,6 +33,7 @@ pub enum Event {
     TepidHitBlockAngleToGoal,
     TepidHitAwayFromOwnGoal,
     PanicDefense,
+    ShadowDefense,
     WallHitFinishedWithoutJump,
     WallHitNotFacingTarget,
 }
@@ -42,6 +47,7 @@ impl EEG {
             current_packet_time: 0.0,
             draw_list: DrawList::new(),
             events: None,
+            stack_history: None,
             quick_chat: None,
         }
     }
@@ -56,6 +62,7 @@ impl EEG {
 
     pub fn track_events(&mut self) {
         self.events = Some(HashSet::new());
+        self.stack_history = Some(StackHistory::default());
     }
 }
 
@@ -83,6 +90,21 @@ impl EEG {
         self.draw_list.draw(drawable);
     }
 
+    /// Returns the number of things drawn so far this frame. Pass this to
+    /// `take_drawn_since` to undo any drawing that happens afterwards.
+    pub fn draw_mark(&self) -> usize {
+        self.draw_list.drawables.len()
+    }
+
+    /// Remove and return everything drawn since `draw_mark` returned `mark`.
+    pub fn take_drawn_since(&mut self, mark: usize) -> Vec<Drawable> {
+        self.draw_list.drawables.split_off(mark)
+    }
+
+    pub fn draw_all(&mut self, drawables: Vec<Drawable>) {
+        self.draw_list.drawables.extend(drawables);
+    }
+
     pub fn print_value(&mut self, label: &str, value: impl PrettyPrint) {
         self.draw_list.print_value(label, value);
     }
@@ -120,6 +142,49 @@ impl EEG {
             events.insert(event);
         }
     }
+
+    pub fn track_stack(&mut self, stack: &[StackFrame]) {
+        if let Some(ref mut history) = self.stack_history {
+            history.record(self.current_packet_time, stack);
+        }
+    }
+}
+
+/// Which behaviors were running, and when.
+#[derive(Default)]
+pub struct StackHistory {
+    /// The game time of each change, along with the new stack of names, from
+    /// the root down.
+    changes: Vec<(f32, Vec<String>)>,
+}
+
+impl StackHistory {
+    #[allow(clippy::float_cmp)]
+    fn record(&mut self, time: f32, stack: &[StackFrame]) {
+        let names: Vec<_> = stack.iter().map(|f| f.name.clone()).collect();
+        match self.changes.last_mut() {
+            Some((_, last)) if *last == names => {}
+            // A nested `Runner` records its own stack first, so let the
+            // outermost one have the last word for each frame.
+            Some((t, last)) if *t == time => *last = names,
+            _ => self.changes.push((time, names)),
+        }
+    }
+
+    /// Returns true if a behavior named `name` was anywhere on the stack,
+    /// `elapsed` seconds after the first recorded frame.
+    pub fn was_inside(&self, name: &str, elapsed: f32) -> bool {
+        let start = match self.changes.first() {
+            Some(&(start, _)) => start,
+            None => return false,
+        };
+        self.changes
+            .iter()
+            .take_while(|(time, _)| *time <= start + elapsed)
+            .last()
+            .map(|(_, names)| names.iter().any(|n| n == name))
+            .unwrap_or(false)
+    }
 }
 
 pub struct DrawList {
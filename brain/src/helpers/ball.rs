@@ -1,3 +1,4 @@
+use crate::params;
 use chip::Ball;
 use common::{math::fractionality, prelude::*, rl, vector_iter};
 use derive_new::new;
@@ -5,8 +6,6 @@ use nalgebra::{Point3, Vector3};
 use ordered_float::OrderedFloat;
 use std::{iter::Cloned, slice::Iter};
 
-const PREDICT_DURATION: f32 = 7.0;
-
 pub struct BallTrajectory {
     frames: Vec<BallFrame>,
 }
@@ -121,7 +120,7 @@ impl BallPredictor for ChipBallPrediction {
         ball.set_vel(packet.GameBall.Physics.vel());
         ball.set_omega(packet.GameBall.Physics.ang_vel());
 
-        let num_frames = (PREDICT_DURATION / DT).ceil() as usize;
+        let num_frames = (params::BALL_PREDICT_DURATION.get() / DT).ceil() as usize;
         let mut frames = Vec::with_capacity(num_frames);
         let mut t = 0.0;
 
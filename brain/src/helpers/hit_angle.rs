@@ -1,6 +1,9 @@
 use crate::utils::geometry::ExtendF32;
 use common::prelude::*;
-use nalgebra::{Point2, UnitComplex};
+use nalgebra::{Point2, UnitComplex, Vector2};
+use ordered_float::NotNan;
+use simulate::CarDodge;
+use std::f32::consts::PI;
 
 pub fn feasible_hit_angle_toward(
     ball_loc: Point2<f32>,
@@ -54,3 +57,119 @@ pub fn blocking_angle(
         .min(max_angle_diff);
     (naive_angle + adjust).normalize_angle()
 }
+
+/// Choose the dodge angle (relative to the car, as in `Dodge::angle`) which
+/// sends the ball closest to `aim_loc`.
+///
+/// Each candidate is scored with a crude model: the car keeps its post-dodge
+/// velocity until it touches the ball, and the ball is pushed along the contact
+/// normal. `contact_dist` is the distance between the car's and the ball's
+/// centers at the moment of contact.
+pub fn best_dodge_angle(
+    car_loc: Point2<f32>,
+    car_vel: Vector2<f32>,
+    car_rot: UnitComplex<f32>,
+    ball_loc: Point2<f32>,
+    ball_vel: Vector2<f32>,
+    aim_loc: Point2<f32>,
+    contact_dist: f32,
+) -> Option<UnitComplex<f32>> {
+    let ideal = aim_loc - ball_loc;
+    if ideal.norm() < 1.0 {
+        // There's no direction to aim in.
+        return None;
+    }
+
+    let steps = 12;
+    (0..=steps)
+        .map(|i| UnitComplex::new(-PI / 2.0 + PI * i as f32 / steps as f32))
+        .filter_map(|angle| {
+            let dodge = CarDodge::calc_2d(car_vel, car_rot, angle);
+            let hit =
+                predict_hit_direction(car_loc, dodge.end_vel, ball_loc, ball_vel, contact_dist)?;
+            let error = NotNan::new(hit.angle_to(&ideal).abs()).ok()?;
+            Some((angle, error))
+        })
+        .min_by_key(|&(_, error)| error)
+        .map(|(angle, _)| angle)
+}
+
+/// Predict the direction the ball will travel after being touched by a car
+/// moving in a straight line. Returns `None` if the car would miss.
+fn predict_hit_direction(
+    car_loc: Point2<f32>,
+    car_vel: Vector2<f32>,
+    ball_loc: Point2<f32>,
+    ball_vel: Vector2<f32>,
+    contact_dist: f32,
+) -> Option<Vector2<f32>> {
+    let rel_vel = car_vel - ball_vel;
+    if rel_vel.norm() < 1.0 {
+        return None;
+    }
+    let rel_dir = rel_vel.normalize();
+
+    // Find where the car is along its path when it first touches the ball.
+    let car_to_ball = ball_loc - car_loc;
+    let along = car_to_ball.dot(&rel_dir);
+    let perp = car_to_ball - rel_dir * along;
+    if along <= 0.0 || perp.norm() >= contact_dist {
+        return None;
+    }
+    let back_off = (contact_dist.powi(2) - perp.norm_squared()).sqrt();
+    let contact_car_loc = car_loc + rel_dir * (along - back_off);
+
+    let normal = (ball_loc - contact_car_loc).normalize();
+    let push = rel_vel.dot(&normal).max(0.0);
+    Some(ball_vel + normal * push)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helpers::hit_angle::{best_dodge_angle, predict_hit_direction};
+    use common::prelude::*;
+    use nalgebra::{Point2, UnitComplex, Vector2};
+    use simulate::CarDodge;
+
+    #[test]
+    fn best_dodge_angle_with_aim_on_the_ball() {
+        let ball_loc = Point2::new(0.0, 500.0);
+        let angle = best_dodge_angle(
+            Point2::origin(),
+            Vector2::new(0.0, 1000.0),
+            UnitComplex::new(90.0_f32.to_radians()),
+            ball_loc,
+            Vector2::zeros(),
+            ball_loc,
+            150.0,
+        );
+        assert!(angle.is_none());
+    }
+
+    #[test]
+    fn best_dodge_angle_cuts_toward_off_axis_aim() {
+        let car_loc = Point2::origin();
+        let car_vel = Vector2::new(0.0, 1000.0);
+        let car_rot = UnitComplex::new(90.0_f32.to_radians());
+        let ball_loc = Point2::new(0.0, 500.0);
+        let aim_loc = Point2::new(1500.0, 2000.0);
+        let angle = best_dodge_angle(
+            car_loc,
+            car_vel,
+            car_rot,
+            ball_loc,
+            Vector2::zeros(),
+            aim_loc,
+            150.0,
+        )
+        .unwrap();
+
+        // Dodging straight would send the ball 45° off. The chosen angle should
+        // do much better.
+        let dodge = CarDodge::calc_2d(car_vel, car_rot, angle);
+        let hit = predict_hit_direction(car_loc, dodge.end_vel, ball_loc, Vector2::zeros(), 150.0)
+            .unwrap();
+        let error = hit.angle_to(&(aim_loc - ball_loc)).abs();
+        assert!(error < 5.0_f32.to_radians(), "{}", error.to_degrees());
+    }
+}
@@ -63,6 +63,7 @@ where
         ball_vel: sim_ball.vel,
         car_loc: intercept_loc,
         car_speed: sim_car.speed(),
+        car_boost: sim_car.boost(),
         data,
     };
     Some(intercept)
@@ -74,6 +75,7 @@ pub struct NaiveIntercept<D = ()> {
     pub ball_vel: Vector3<f32>,
     pub car_loc: Point3<f32>,
     pub car_speed: f32,
+    pub car_boost: f32,
     pub data: D,
 }
 
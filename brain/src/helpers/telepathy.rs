@@ -3,7 +3,7 @@ use crate::{
     strategy::{Context, Context2},
 };
 use common::prelude::*;
-use nalgebra::{Unit, Vector2};
+use nalgebra::{Point2, Unit, Vector2};
 use std::f32::consts::PI;
 
 pub fn predict_enemy_hit_direction(ctx: &mut Context<'_>) -> Option<Unit<Vector2<f32>>> {
@@ -12,13 +12,29 @@ pub fn predict_enemy_hit_direction(ctx: &mut Context<'_>) -> Option<Unit<Vector2
 }
 
 pub fn predict_enemy_hit_direction_2(ctx: &Context2<'_, '_>) -> Option<Unit<Vector2<f32>>> {
+    let (enemy_loc, _ball_loc, likely_aim) = predict_enemy_aim(ctx)?;
+    Some((likely_aim - enemy_loc).to_axis())
+}
+
+/// The direction the ball will travel after the enemy's next hit. Unlike
+/// `predict_enemy_hit_direction`, this starts from the ball, not the enemy.
+pub fn predict_ball_travel_direction(ctx: &mut Context<'_>) -> Option<Unit<Vector2<f32>>> {
+    let (ctx, _eeg) = ctx.split();
+    predict_ball_travel_direction_2(&ctx)
+}
+
+pub fn predict_ball_travel_direction_2(ctx: &Context2<'_, '_>) -> Option<Unit<Vector2<f32>>> {
+    let (_enemy_loc, ball_loc, likely_aim) = predict_enemy_aim(ctx)?;
+    Some((likely_aim - ball_loc).to_axis())
+}
+
+/// Returns the enemy's location, the ball's location when they reach it, and
+/// where they'll likely aim.
+fn predict_enemy_aim(ctx: &Context2<'_, '_>) -> Option<(Point2<f32>, Point2<f32>, Point2<f32>)> {
     let (enemy, intercept) = ctx.scenario.enemy_intercept()?;
     let enemy_loc = enemy.Physics.loc_2d();
-    let likely_aim = feasible_hit_angle_toward(
-        intercept.ball_loc.to_2d(),
-        enemy_loc,
-        ctx.game.own_goal().center_2d,
-        PI / 4.0,
-    );
-    Some((likely_aim - enemy_loc).to_axis())
+    let ball_loc = intercept.ball_loc.to_2d();
+    let likely_aim =
+        feasible_hit_angle_toward(ball_loc, enemy_loc, ctx.game.own_goal().center_2d, PI / 4.0);
+    Some((enemy_loc, ball_loc, likely_aim))
 }
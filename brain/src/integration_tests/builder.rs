@@ -3,9 +3,9 @@ use crate::{
     integration_tests::{
         playback::{BallRecording, CarRecording},
         running_test::RunningTest,
-        scenario::TestScenario,
     },
     strategy::{Behavior, Runner, Soccar},
+    tune::TestScenario,
 };
 use brain_test_data::OneVOneScenario;
 use collect::{RecordingPlayerInput, RecordingPlayerTick, RecordingRigidBodyState, RecordingTick};
@@ -1,8 +1,8 @@
-pub use self::{builder::TestRunner, scenario::TestScenario};
+pub use self::builder::TestRunner;
+pub use crate::tune::TestScenario;
 
 mod builder;
 mod playback;
 mod running_test;
-mod scenario;
 mod templates;
 mod utils;
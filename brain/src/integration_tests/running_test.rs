@@ -78,6 +78,20 @@ impl RunningTest {
         self.examine_eeg(move |eeg| f(eeg.events.as_ref().unwrap()));
     }
 
+    /// Panics unless a behavior named `name` was running `elapsed` seconds
+    /// into the test.
+    pub fn assert_inside(&self, name: &'static str, elapsed: f32) {
+        self.examine_eeg(move |eeg| {
+            let history = eeg.stack_history.as_ref().unwrap();
+            assert!(
+                history.was_inside(name, elapsed),
+                "not inside {} at t={}s",
+                name,
+                elapsed,
+            );
+        });
+    }
+
     pub fn spawn_thread(
         ball_scenario: BallRecording,
         car_scenario: CarRecording,
@@ -3,7 +3,12 @@
 #![warn(clippy::all)]
 #![allow(clippy::unreadable_literal)]
 
-pub use crate::{brain::Brain, eeg::EEG};
+pub use crate::{
+    brain::Brain,
+    eeg::EEG,
+    routing::snapshot::PlanSnapshot,
+    strategy::{ConfigError, Registry, StrategyConfig},
+};
 
 macro_rules! return_some {
     ($rule:expr) => {
@@ -28,8 +33,11 @@ mod eeg;
 mod helpers;
 #[cfg(test)]
 mod integration_tests;
+pub mod params;
 mod routing;
 mod rules;
 mod sim;
 mod strategy;
+#[cfg(any(test, feature = "tune"))]
+pub mod tune;
 mod utils;
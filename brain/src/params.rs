@@ -0,0 +1,299 @@
+//! Tunable numbers which would otherwise be scattered around as constants.
+//!
+//! Every parameter has a default, and can be overridden at startup:
+//!
+//! - `FORMULA_NONE_PARAMS` names a JSON file with an object mapping parameter
+//!   names to values, e.g. `{"POSSESSION_CONTESTABLE": 0.75}`.
+//! - `FORMULA_NONE_<NAME>` overrides a single parameter, and wins over the
+//!   file.
+//!
+//! `dump` prints the current values in the same format as the file.
+
+use lazy_static::lazy_static;
+use std::{env, error::Error, f32::consts::PI, fmt, fs, io, str::FromStr, sync::RwLock};
+
+const ENV_PREFIX: &str = "FORMULA_NONE_";
+const FILE_VAR: &str = "FORMULA_NONE_PARAMS";
+
+macro_rules! params {
+    ($($(#[doc = $doc:expr])* $name:ident: $ty:ty = $default:expr;)*) => {
+        lazy_static! {
+            $(
+                $(#[doc = $doc])*
+                pub static ref $name: Param<$ty> =
+                    Param::new(stringify!($name), $default, concat!($($doc),*).trim());
+            )*
+        }
+
+        /// Every parameter, in declaration order.
+        pub fn all() -> Vec<&'static dyn Tunable> {
+            vec![$(&*$name),*]
+        }
+    };
+}
+
+params! {
+    /// Seconds of possession below which the ball is up for grabs.
+    POSSESSION_CONTESTABLE: f32 = 0.5;
+    /// Seconds of possession beyond which more makes no difference.
+    POSSESSION_SATURATED: f32 = 5.0;
+    /// How far (uu) the ball may stray from its predicted path before a
+    /// `SameBallTrajectory` gives up.
+    SAME_BALL_TRAJECTORY_ERROR: f32 = 50.0;
+    /// How far (uu) an intercept may move before `InterceptMemory` considers
+    /// it a different intercept.
+    INTERCEPT_MEMORY_LOC_THRESHOLD: f32 = 100.0;
+    /// How many seconds of ball prediction to compute each frame.
+    BALL_PREDICT_DURATION: f32 = 7.0;
+    /// How close (uu) to the goal walls we let planned paths come.
+    GOAL_WALL_MARGIN: f32 = 125.0;
+    /// Frames which take at least this many milliseconds get logged.
+    SLOW_FRAME_MS: u64 = 8;
+    /// The largest angle between an enemy's approach and the ball's path to
+    /// our goal that still counts as a shot, when the ball is close to goal.
+    ENEMY_SHOT_ANGLE_NEAR: f32 = PI / 2.0;
+    /// The same, when the ball is far from goal.
+    ENEMY_SHOT_ANGLE_FAR: f32 = PI / 4.0;
+    /// The enemy must have at least this much possession (in seconds) before
+    /// `Defense` worries about them attacking.
+    ENEMY_ATTACK_POSSESSION: f32 = 2.0;
+    /// The most seconds `Offense` will wait for a better shot.
+    SHOT_MAX_DELAY: f32 = 2.0;
+    /// How many seconds of possession we need before slow-playing the ball.
+    SLOW_PLAY_POSSESSION: f32 = 2.0;
+    /// When the ball is this many times closer to our goal than theirs,
+    /// `Offense` plays goalie.
+    GOALIE_DEFENSIVENESS: f32 = 5.0;
+    /// How far (uu) `ShadowDefense` stays back from the ball, along the
+    /// enemy's likely line of attack.
+    SHADOW_DISTANCE: f32 = 1500.0;
+    /// `ShadowDefense` won't boost below this much boost, so there's some
+    /// left for the save.
+    SHADOW_BOOST_RESERVE: f32 = 30.0;
+    /// With this many seconds left, a one-goal lead is worth protecting and a
+    /// one-goal deficit is worth chasing (scaled up for bigger deficits).
+    LATE_GAME_SECONDS: f32 = 60.0;
+    /// In a tied game with this many seconds left, avoid risky challenges.
+    BUZZER_SECONDS: f32 = 10.0;
+    /// The risk (from -1 to 1) to take in a tied game just before the buzzer.
+    BUZZER_RISK: f32 = -0.75;
+    /// The risk (from -1 to 1) to take in overtime, where the next goal wins.
+    OVERTIME_RISK: f32 = -0.25;
+}
+
+/// One tunable value.
+pub struct Param<T> {
+    name: &'static str,
+    help: &'static str,
+    default: T,
+    value: RwLock<T>,
+}
+
+impl<T: Copy> Param<T> {
+    fn new(name: &'static str, default: T, help: &'static str) -> Self {
+        Self {
+            name,
+            help,
+            default,
+            value: RwLock::new(default),
+        }
+    }
+
+    pub fn get(&self) -> T {
+        *self.value.read().unwrap()
+    }
+
+    pub fn set(&self, value: T) {
+        *self.value.write().unwrap() = value;
+    }
+}
+
+/// A `Param` of any type.
+pub trait Tunable: Sync {
+    fn name(&self) -> &'static str;
+    fn help(&self) -> &'static str;
+    fn value(&self) -> String;
+    fn default_value(&self) -> String;
+    fn set_str(&self, value: &str) -> Result<(), String>;
+    fn reset(&self);
+}
+
+impl<T> Tunable for Param<T>
+where
+    T: Copy + fmt::Display + FromStr + Send + Sync,
+    T::Err: fmt::Display,
+{
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn help(&self) -> &'static str {
+        self.help
+    }
+
+    fn value(&self) -> String {
+        self.get().to_string()
+    }
+
+    fn default_value(&self) -> String {
+        self.default.to_string()
+    }
+
+    fn set_str(&self, value: &str) -> Result<(), String> {
+        let value = value.trim().parse().map_err(|e: T::Err| e.to_string())?;
+        self.set(value);
+        Ok(())
+    }
+
+    fn reset(&self) {
+        self.set(self.default);
+    }
+}
+
+#[derive(Debug)]
+pub enum ParamError {
+    Io(io::Error),
+    Syntax(serde_json::Error),
+    NotAnObject,
+    Unknown {
+        name: String,
+    },
+    Invalid {
+        name: String,
+        value: String,
+        reason: String,
+    },
+}
+
+impl fmt::Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamError::Io(error) => write!(f, "could not read parameters: {}", error),
+            ParamError::Syntax(error) => write!(f, "invalid parameters: {}", error),
+            ParamError::NotAnObject => write!(f, "parameters must be a JSON object"),
+            ParamError::Unknown { name } => write!(f, "unknown parameter {:?}", name),
+            ParamError::Invalid {
+                name,
+                value,
+                reason,
+            } => write!(f, "invalid value {:?} for {}: {}", value, name, reason),
+        }
+    }
+}
+
+impl Error for ParamError {}
+
+/// Apply overrides from the environment, as described in the module docs.
+pub fn load_overrides() -> Result<(), ParamError> {
+    if let Some(path) = env::var_os(FILE_VAR) {
+        let json = fs::read_to_string(path).map_err(ParamError::Io)?;
+        apply_json(&all(), &json)?;
+    }
+    for param in all() {
+        if let Ok(value) = env::var(format!("{}{}", ENV_PREFIX, param.name())) {
+            set(&[param], param.name(), &value)?;
+        }
+    }
+    Ok(())
+}
+
+/// Set a parameter by name.
+pub fn set_by_name(name: &str, value: &str) -> Result<(), ParamError> {
+    set(&all(), name, value)
+}
+
+/// Put every parameter back to its default.
+pub fn reset() {
+    for param in all() {
+        param.reset();
+    }
+}
+
+/// The current value of every parameter, as a JSON object which can be loaded
+/// back with `FORMULA_NONE_PARAMS`.
+pub fn dump() -> String {
+    format_json(&all())
+}
+
+fn set(params: &[&dyn Tunable], name: &str, value: &str) -> Result<(), ParamError> {
+    let param = params
+        .iter()
+        .find(|p| p.name() == name)
+        .ok_or_else(|| ParamError::Unknown {
+            name: name.to_string(),
+        })?;
+    param.set_str(value).map_err(|reason| ParamError::Invalid {
+        name: name.to_string(),
+        value: value.to_string(),
+        reason,
+    })
+}
+
+fn apply_json(params: &[&dyn Tunable], json: &str) -> Result<(), ParamError> {
+    let values: serde_json::Value = serde_json::from_str(json).map_err(ParamError::Syntax)?;
+    let values = values.as_object().ok_or(ParamError::NotAnObject)?;
+    for (name, value) in values {
+        set(params, name, &value.to_string())?;
+    }
+    Ok(())
+}
+
+fn format_json(params: &[&dyn Tunable]) -> String {
+    let lines: Vec<_> = params
+        .iter()
+        .map(|p| format!("    {:?}: {}", p.name(), p.value()))
+        .collect();
+    format!("{{\n{}\n}}\n", lines.join(",\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::params::{apply_json, format_json, Param, ParamError, Tunable};
+
+    #[test]
+    fn apply_and_dump() {
+        let speed = Param::new("SPEED", 1000.0_f32, "");
+        let frames = Param::new("FRAMES", 8_u64, "");
+        let params: &[&dyn Tunable] = &[&speed, &frames];
+
+        apply_json(params, r#"{"SPEED": 1500.5}"#).unwrap();
+        assert_eq!(speed.get(), 1500.5);
+        assert_eq!(frames.get(), 8);
+
+        let dump = format_json(params);
+        assert_eq!(dump, "{\n    \"SPEED\": 1500.5,\n    \"FRAMES\": 8\n}\n");
+
+        // A dump can be loaded back in.
+        speed.reset();
+        apply_json(params, &dump).unwrap();
+        assert_eq!(speed.get(), 1500.5);
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        let frames = Param::new("FRAMES", 8_u64, "");
+        let params: &[&dyn Tunable] = &[&frames];
+
+        match apply_json(params, r#"{"FRAMS": 9}"#) {
+            Err(ParamError::Unknown { name }) => assert_eq!(name, "FRAMS"),
+            _ => panic!("expected an error"),
+        }
+        match apply_json(params, r#"{"FRAMES": 2.5}"#) {
+            Err(ParamError::Invalid { name, .. }) => assert_eq!(name, "FRAMES"),
+            _ => panic!("expected an error"),
+        }
+        match apply_json(params, "[]") {
+            Err(ParamError::NotAnObject) => {}
+            _ => panic!("expected an error"),
+        }
+        assert_eq!(frames.get(), 8);
+    }
+
+    #[test]
+    fn every_param_is_documented() {
+        for param in crate::params::all() {
+            assert_eq!(param.value(), param.default_value(), "{}", param.name());
+            assert!(!param.help().is_empty(), "{}", param.name());
+        }
+    }
+}
@@ -1,13 +1,21 @@
 use crate::{
     eeg::{color, Drawable},
-    routing::models::{
-        PlanningContext, ProvisionalPlanExpansion, ProvisionalPlanExpansionTail, RoutePlan,
-        RoutePlanError, RoutePlanner, SegmentRunAction, SegmentRunner,
+    routing::{
+        models::{
+            PlanningContext, PlanningDump, PlanningSession, ProvisionalPlanExpansion,
+            ProvisionalPlanExpansionTail, RoutePlan, RoutePlanError, RoutePlanner,
+            SegmentRunAction, SegmentRunner,
+        },
+        snapshot::PlanSnapshot,
+        trace::PlanningTrace,
+        tracking::TrackingTolerance,
     },
     rules::SameBallTrajectory,
-    strategy::{Action, Behavior, Context},
+    strategy::{Action, Behavior, Context, Prospect},
 };
+use common::prelude::*;
 use nameof::name_of_type;
+use std::time::{Duration, Instant};
 
 pub struct FollowRoute {
     /// Option dance: This only holds a planner before the first tick.
@@ -15,12 +23,37 @@ pub struct FollowRoute {
     current: Option<Current>,
     never_recover: bool,
     same_ball_trajectory: Option<SameBallTrajectory>,
+    incremental_budget: Option<Duration>,
+    refining: Option<Refining>,
+}
+
+/// A planner which is still improving on the plan being executed.
+struct Refining {
+    planner: Box<dyn RoutePlanner>,
+    session: Box<dyn PlanningSession>,
 }
 
 struct Current {
+    /// The planner which produced `plan`, kept so we can plan again if the car
+    /// strays from it.
+    planner: Box<dyn RoutePlanner>,
     plan: RoutePlan,
     runner: Box<dyn SegmentRunner>,
     provisional_expansion_tail: ProvisionalPlanExpansionTail,
+    /// The game time when we started following `plan`.
+    start_time: f32,
+    /// How the planner arrived at `plan`, in case the plan fails.
+    trace: PlanningTrace,
+}
+
+impl Current {
+    /// How much longer the plan should take to finish, as of now.
+    fn remaining_duration(&self, ctx: &Context<'_>) -> f32 {
+        let expansion =
+            ProvisionalPlanExpansion::new(&*self.plan.segment, &self.provisional_expansion_tail);
+        let elapsed = ctx.packet.GameInfo.TimeSeconds - self.start_time;
+        expansion.duration() - elapsed
+    }
 }
 
 impl FollowRoute {
@@ -34,6 +67,8 @@ impl FollowRoute {
             current: None,
             never_recover: false,
             same_ball_trajectory: None,
+            incremental_budget: None,
+            refining: None,
         }
     }
 
@@ -52,6 +87,18 @@ impl FollowRoute {
         };
         self
     }
+
+    /// If the planner supports it, keep improving on the plan across frames,
+    /// spending at most `budget` per frame. The car follows the planner's
+    /// ordinary one-shot plan until a faster one turns up.
+    ///
+    /// Improved plans start from where the car was when the search began. If
+    /// the car has since moved too far from there, the plan is thrown away and
+    /// the search starts over from the car's current state.
+    pub fn incremental(mut self, budget: Duration) -> Self {
+        self.incremental_budget = Some(budget);
+        self
+    }
 }
 
 impl Behavior for FollowRoute {
@@ -59,16 +106,42 @@ impl Behavior for FollowRoute {
         name_of_type!(FollowRoute)
     }
 
+    fn prospect(&self) -> Prospect {
+        let current = match self.current {
+            Some(ref current) => current,
+            None => return Prospect::default(),
+        };
+        let expansion = ProvisionalPlanExpansion::new(
+            &*current.plan.segment,
+            &current.provisional_expansion_tail,
+        );
+        Prospect {
+            arrival_time: Some(expansion.duration()),
+        }
+    }
+
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
         if let Some(ref mut same_ball_trajectory) = self.same_ball_trajectory {
             return_some!(same_ball_trajectory.execute_old(ctx));
         }
 
-        if self.current.is_none() {
-            let planner = &*self.planner.take().unwrap();
-            if let Err(action) = self.advance(planner, ctx) {
+        if let Some(planner) = self.planner.take() {
+            if let Err(action) = self.advance(&*planner, ctx) {
                 return action;
             }
+            if self.incremental_budget.is_some() {
+                if let Some(session) = planner.incremental() {
+                    ctx.eeg.log(
+                        self.name(),
+                        format!("refining incrementally with {}", planner.name()),
+                    );
+                    self.refining = Some(Refining { planner, session });
+                }
+            }
+        }
+
+        if self.refining.is_some() {
+            self.refine(ctx);
         }
 
         self.draw(ctx);
@@ -94,30 +167,152 @@ impl FollowRoute {
 
         ctx.eeg
             .log(self.name(), format!("planning with {}", planner.name()));
-        let (plan, log) = match PlanningContext::plan(planner, ctx) {
-            Ok((plan, log)) => (plan, log),
-            Err(err) => return Err(self.handle_error(ctx, planner.name(), err.error, err.log)),
+        let (plan, trace) = match PlanningContext::plan(planner, ctx) {
+            Ok((plan, _log, trace)) => (plan, trace),
+            Err(err) => return Err(self.handle_error(ctx, planner.name(), err.error, &err.trace)),
+        };
+        self.install(ctx, planner.clone_box(), plan, trace)
+    }
+
+    /// Continue an incremental planning session, and switch to its plan if it
+    /// found one which arrives sooner than the plan we are following.
+    fn refine(&mut self, ctx: &mut Context<'_>) {
+        let deadline = Instant::now() + self.incremental_budget.unwrap();
+        let refining = self.refining.as_mut().unwrap();
+        let planner_name = refining.planner.name();
+
+        let mut log = Vec::new();
+        let mut trace = PlanningTrace::default();
+        let result = {
+            let (ctx, _eeg) = ctx.split();
+            let context = PlanningContext::from_context(&ctx);
+            let mut dump = PlanningDump {
+                log: &mut log,
+                trace: &mut trace,
+            };
+            refining.session.step(&context, &mut dump, deadline)
+        };
+        let step = match result {
+            Ok(step) => step,
+            Err(error) => {
+                // The current plan is still good, so keep following it.
+                ctx.eeg.log(
+                    self.name(),
+                    format!("error {:?} while refining with {}", error, planner_name),
+                );
+                self.refining = None;
+                return;
+            }
         };
+
+        let planner = refining.planner.clone();
+        if step.finished {
+            ctx.eeg
+                .log(self.name(), format!("{} finished planning", planner_name));
+            self.refining = None;
+        }
+        match step.improved {
+            Some(ref plan) if Self::is_stale(plan, ctx) => {
+                ctx.eeg.log(
+                    self.name(),
+                    format!("discarding stale plan from {}; starting over", planner_name),
+                );
+                self.refining = planner
+                    .incremental()
+                    .map(|session| Refining { planner, session });
+            }
+            Some(plan) => {
+                let tail = match plan.provisional_expand(&ctx.scenario) {
+                    Ok(tail) => tail,
+                    Err(_) => {
+                        ctx.eeg.log(
+                            self.name(),
+                            format!("could not expand improved plan from {}", planner_name),
+                        );
+                        return;
+                    }
+                };
+                let duration = ProvisionalPlanExpansion::new(&*plan.segment, &tail).duration();
+                let remaining = self.current.as_ref().unwrap().remaining_duration(ctx);
+                if duration >= remaining {
+                    ctx.eeg.log(
+                        self.name(),
+                        format!(
+                            "ignoring plan from {} taking {:.2}s; current plan has {:.2}s left",
+                            planner_name, duration, remaining,
+                        ),
+                    );
+                    return;
+                }
+                ctx.eeg.log(
+                    self.name(),
+                    format!("switching to improved plan from {}", planner_name),
+                );
+                self.install_expanded(ctx, planner, plan, tail, trace);
+            }
+            None => {}
+        }
+    }
+
+    /// Returns true if `plan` starts too far from where the car is now to be
+    /// followed.
+    fn is_stale(plan: &RoutePlan, ctx: &Context<'_>) -> bool {
+        let start = plan.segment.start().loc_2d();
+        let gap = (start - ctx.me().Physics.loc_2d()).norm();
+        gap > TrackingTolerance::DEFAULT.cross_track
+    }
+
+    fn install(
+        &mut self,
+        ctx: &mut Context<'_>,
+        planner: Box<dyn RoutePlanner>,
+        plan: RoutePlan,
+        mut trace: PlanningTrace,
+    ) -> Result<(), Action> {
         ctx.eeg.log(
             self.name(),
             format!("next segment is {}", plan.segment.name()),
         );
-        let tail = plan.provisional_expand(&ctx.scenario).map_err(|error| {
-            self.handle_error(
-                ctx,
-                error.planner_name,
-                error.error,
-                log.into_iter().chain(error.log),
-            )
-        })?;
+        let tail = match plan.provisional_expand(&ctx.scenario) {
+            Ok(tail) => tail,
+            Err(error) => {
+                // Show the whole story, from the first planner to the one that failed.
+                trace.events.extend(error.trace.events);
+                return Err(self.handle_error(ctx, error.planner_name, error.error, &trace));
+            }
+        };
+        self.install_expanded(ctx, planner, plan, tail, trace);
+        Ok(())
+    }
+
+    fn install_expanded(
+        &mut self,
+        ctx: &mut Context<'_>,
+        planner: Box<dyn RoutePlanner>,
+        plan: RoutePlan,
+        tail: ProvisionalPlanExpansionTail,
+        trace: PlanningTrace,
+    ) {
+        let violations = plan.validate(&*planner, &tail);
+        for violation in &violations {
+            ctx.eeg
+                .log(self.name(), format!("invalid plan: {:?}", violation));
+        }
+        // A bad plan is a planner bug, but in a match it's better to drive it
+        // anyway than to crash.
+        if cfg!(test) {
+            assert!(violations.is_empty(), "invalid plan: {:?}", violations);
+        }
 
         let runner = plan.segment.run();
         self.current = Some(Current {
+            planner,
             plan,
             runner,
             provisional_expansion_tail: tail,
+            start_time: ctx.packet.GameInfo.TimeSeconds,
+            trace,
         });
-        Ok(())
     }
 
     fn handle_error(
@@ -125,12 +320,9 @@ impl FollowRoute {
         ctx: &mut Context<'_>,
         planner_name: &str,
         error: RoutePlanError,
-        log: impl IntoIterator<Item = String>,
+        trace: &PlanningTrace,
     ) -> Action {
-        // You can print the plan trace if necessary for debugging
-        for line in log.into_iter().take(0) {
-            ctx.eeg.log(self.name(), line);
-        }
+        log_trace(ctx, trace);
 
         ctx.eeg.log(
             self.name(),
@@ -140,18 +332,13 @@ impl FollowRoute {
         match error.recover(ctx) {
             Some(b) => {
                 if self.never_recover {
-                    ctx.eeg
-                        .log(self.name(), "recoverable, but we are forbidden to do so");
-                    Action::Abort
+                    Action::abort("recoverable, but we are forbidden to do so")
                 } else {
                     ctx.eeg.log(self.name(), "recoverable!");
                     Action::RootCall(b)
                 }
             }
-            None => {
-                ctx.eeg.log(self.name(), "non-recoverable");
-                Action::Abort
-            }
+            None => Action::abort("non-recoverable"),
         }
     }
 
@@ -164,20 +351,67 @@ impl FollowRoute {
             SegmentRunAction::Yield(i) => return Action::Yield(i),
             SegmentRunAction::Success => true,
             SegmentRunAction::Failure => false,
+            SegmentRunAction::Deviated(deviation) => {
+                ctx.eeg.log(
+                    self.name(),
+                    format!(
+                        "{} deviated from plan: {:?}",
+                        deviation.segment, deviation.error,
+                    ),
+                );
+                log_snapshot(ctx, self.current.as_ref().unwrap());
+                return self.replan_tail(ctx);
+            }
         };
 
         if !success {
-            ctx.eeg.log(self.name(), "segment failure; aborting");
-            return Action::Abort;
+            let current = self.current.as_ref().unwrap();
+            log_trace(ctx, &current.trace);
+            log_snapshot(ctx, current);
+            return Action::abort("segment failure");
         }
 
         let current = self.current.take().unwrap();
+        // Any further refinements would start from before this segment, so they
+        // are no longer useful.
+        self.refining = None;
         let next = some_or_else!(current.plan.next, {
-            return Action::Return;
+            return Action::return_("arrived");
         });
         if let Err(action) = self.advance(&*next, ctx) {
             return action;
         }
         self.go(ctx)
     }
+
+    /// Abandon the current segment and plan the rest of the route from where
+    /// the car is now. This runs the planner which produced the segment again,
+    /// rather than the one which would have followed it, so the route keeps
+    /// its original intent.
+    fn replan_tail(&mut self, ctx: &mut Context<'_>) -> Action {
+        let current = self.current.take().unwrap();
+        self.refining = None;
+        if let Err(action) = self.advance(&*current.planner, ctx) {
+            return action;
+        }
+        self.go(ctx)
+    }
+}
+
+fn log_trace(ctx: &mut Context<'_>, trace: &PlanningTrace) {
+    for line in trace.summary() {
+        ctx.eeg.log(name_of_type!(FollowRoute), line);
+    }
+}
+
+/// Dump the plan to the log, so it can be loaded with `PlanSnapshot::from_json`
+/// and replayed.
+fn log_snapshot(ctx: &mut Context<'_>, current: &Current) {
+    let expansion =
+        ProvisionalPlanExpansion::new(&*current.plan.segment, &current.provisional_expansion_tail);
+    let snapshot = PlanSnapshot::from_expansion(&expansion);
+    ctx.eeg.log(
+        name_of_type!(FollowRoute),
+        format!("plan: {}", snapshot.to_json()),
+    );
 }
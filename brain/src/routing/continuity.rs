@@ -0,0 +1,276 @@
+//! Sanity checks for chains of segments, such as those produced by
+//! `RoutePlan::provisional_expand`.
+
+use crate::routing::models::{CarState, SegmentPlan};
+use common::prelude::*;
+use nalgebra::Point2;
+use std::fmt;
+
+const LOC_TOLERANCE: f32 = 1.0;
+const ROT_TOLERANCE: f32 = 0.05;
+const VEL_TOLERANCE: f32 = 1.0;
+const TARGET_TOLERANCE: f32 = 50.0;
+
+pub struct ContinuityViolation {
+    /// The planner which produced the offending segment.
+    pub planner: &'static str,
+    pub segment: String,
+    pub problem: ContinuityProblem,
+}
+
+#[derive(Debug)]
+pub enum ContinuityProblem {
+    /// The segment starts this far from where the previous one ended.
+    LocationGap(f32),
+    /// The segment starts facing this many radians away from where the
+    /// previous one ended.
+    RotationGap(f32),
+    /// The segment starts with a velocity this far from the previous one's end
+    /// velocity.
+    VelocityGap(f32),
+    NegativeDuration(f32),
+    NegativeBoost(f32),
+    /// The last segment ends this far from the target.
+    MissedTarget(f32),
+}
+
+impl fmt::Debug for ContinuityViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}: {:?}", self.planner, self.segment, self.problem)
+    }
+}
+
+/// Check that each segment picks up where the previous one left off, that
+/// nothing is physically nonsensical, and optionally that the chain ends at
+/// `target_loc`.
+///
+/// Everything is compared in 2D, since the ground segments don't track height.
+pub fn check_continuity<'a>(
+    start: &CarState,
+    segments: impl IntoIterator<Item = (&'static str, &'a dyn SegmentPlan)>,
+    target_loc: Option<Point2<f32>>,
+) -> Vec<ContinuityViolation> {
+    let mut violations = Vec::new();
+    let mut prev = start.clone();
+    let mut last = None;
+
+    for (planner, segment) in segments {
+        let mut report = |problem| {
+            violations.push(ContinuityViolation {
+                planner,
+                segment: segment.name().to_string(),
+                problem,
+            });
+        };
+
+        let seg_start = segment.start();
+        let loc_gap = (seg_start.loc_2d() - prev.loc_2d()).norm();
+        if loc_gap > LOC_TOLERANCE {
+            report(ContinuityProblem::LocationGap(loc_gap));
+        }
+        let rot_gap = (prev.rot_2d().inverse() * seg_start.rot_2d()).angle().abs();
+        if rot_gap > ROT_TOLERANCE {
+            report(ContinuityProblem::RotationGap(rot_gap));
+        }
+        let vel_gap = (seg_start.vel_2d() - prev.vel_2d()).norm();
+        if vel_gap > VEL_TOLERANCE {
+            report(ContinuityProblem::VelocityGap(vel_gap));
+        }
+        let duration = segment.duration();
+        if duration < 0.0 {
+            report(ContinuityProblem::NegativeDuration(duration));
+        }
+        let end = segment.end();
+        if end.boost < 0.0 {
+            report(ContinuityProblem::NegativeBoost(end.boost));
+        }
+
+        last = Some((planner, segment.name().to_string()));
+        prev = end;
+    }
+
+    if let (Some(target_loc), Some((planner, segment))) = (target_loc, last) {
+        let miss = (prev.loc_2d() - target_loc).norm();
+        if miss > TARGET_TOLERANCE {
+            violations.push(ContinuityViolation {
+                planner,
+                segment,
+                problem: ContinuityProblem::MissedTarget(miss),
+            });
+        }
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routing::{
+        continuity::check_continuity,
+        models::{CarState2D, PlanningContext, RoutePlanner, SegmentPlan},
+        plan::{GroundDrive, SearchPlanner},
+        segments::{Brake, ForwardDodge, JumpAndDodge, SimpleArc, Straight, StraightMode},
+        test::with_fixed_context,
+    };
+    use common::{physics, prelude::*, rl};
+    use nalgebra::{Point2, UnitComplex, Vector2};
+    use simulate::CarForwardDodge;
+    use std::{f32::consts::PI, time::Duration};
+
+    /// A tiny deterministic PRNG (xorshift) so the tests are reproducible.
+    struct Rng(u32);
+
+    impl Rng {
+        fn next(&mut self) -> f32 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 17;
+            self.0 ^= self.0 << 5;
+            self.0 as f32 / u32::max_value() as f32
+        }
+
+        fn range(&mut self, min: f32, max: f32) -> f32 {
+            min + (max - min) * self.next()
+        }
+    }
+
+    fn random_start(rng: &mut Rng) -> CarState2D {
+        let rot = UnitComplex::new(rng.range(-PI, PI));
+        let speed = rng.range(0.0, rl::CAR_MAX_SPEED);
+        CarState2D {
+            loc: Point2::new(
+                rng.range(-rl::FIELD_MAX_X, rl::FIELD_MAX_X),
+                rng.range(-rl::FIELD_MAX_Y, rl::FIELD_MAX_Y),
+            ),
+            rot,
+            vel: physics::car_forward_axis_2d(rot).into_inner() * speed,
+            boost: rng.range(0.0, 100.0),
+        }
+    }
+
+    /// Append a few segments the way a provisional expansion would, each one
+    /// starting from the previous one's end.
+    fn extend(chain: &mut Vec<Box<dyn SegmentPlan>>) {
+        let end = chain.last().unwrap().end();
+        let speed = end.vel_2d().norm();
+        if speed >= 500.0 {
+            chain.push(Box::new(Brake::new(end.to_2d_assume(), speed / 2.0)));
+        }
+        let end = chain.last().unwrap().end();
+        chain.push(Box::new(ForwardDodge::new(
+            end.clone(),
+            CarForwardDodge::calc_1d(end.vel_2d().norm()),
+        )));
+        let end = chain.last().unwrap().end();
+        chain.push(Box::new(JumpAndDodge::new(end, UnitComplex::new(PI / 4.0))));
+    }
+
+    fn check(start: &CarState2D, chain: &[Box<dyn SegmentPlan>], target: Option<Point2<f32>>) {
+        let violations =
+            check_continuity(&start.to_3d(), chain.iter().map(|s| ("test", &**s)), target);
+        assert!(violations.is_empty(), "{:?}", violations);
+    }
+
+    #[test]
+    fn straight_chains_are_continuous() {
+        let mut rng = Rng(12345);
+        for _ in 0..200 {
+            let start = random_start(&mut rng);
+            let target = start.loc + start.forward_axis().into_inner() * rng.range(100.0, 3000.0);
+            let straight = Straight::new(start.clone(), target, 0.0, StraightMode::Asap, true);
+            let mut chain: Vec<Box<dyn SegmentPlan>> = vec![Box::new(straight)];
+            check(&start, &chain, Some(target));
+
+            extend(&mut chain);
+            check(&start, &chain, None);
+        }
+    }
+
+    #[test]
+    fn arc_chains_are_continuous() {
+        let mut rng = Rng(54321);
+        let mut tested = 0;
+        for _ in 0..200 {
+            let start = random_start(&mut rng);
+            let radius = rng.range(300.0, 2000.0);
+            let side = if rng.next() < 0.5 { -1.0 } else { 1.0 };
+            let normal = Vector2::new(-start.vel.y, start.vel.x).normalize() * side;
+            let center = start.loc + normal * radius;
+            let target = center + UnitComplex::new(rng.range(-PI, PI)) * (start.loc - center);
+            let arc =
+                match SimpleArc::new(center, radius, start.loc, start.vel, start.boost, target) {
+                    Ok(arc) => arc,
+                    // Too slow to plan an arc.
+                    Err(_) => continue,
+                };
+            let mut chain: Vec<Box<dyn SegmentPlan>> = vec![Box::new(arc)];
+            check(&start, &chain, Some(target));
+
+            extend(&mut chain);
+            check(&start, &chain, None);
+            tested += 1;
+        }
+        assert!(tested >= 100);
+    }
+
+    #[test]
+    fn planner_output_is_continuous() {
+        let mut rng = Rng(2468);
+        with_fixed_context(|fixed| {
+            let mut tested = 0;
+            for _ in 0..50 {
+                let ctx = PlanningContext {
+                    game: fixed.game,
+                    start: random_start(&mut rng).to_3d(),
+                    ball_prediction: fixed.ball_prediction,
+                };
+                let target = Point2::new(
+                    rng.range(-rl::FIELD_MAX_X, rl::FIELD_MAX_X) * 0.8,
+                    rng.range(-rl::FIELD_MAX_Y, rl::FIELD_MAX_Y) * 0.8,
+                );
+                let planners: Vec<Box<dyn RoutePlanner>> = vec![
+                    Box::new(GroundDrive::new(target)),
+                    Box::new(SearchPlanner::new(target).time_budget(Duration::from_millis(50))),
+                ];
+                for planner in planners {
+                    // Not every start can reach every target; only the plans which
+                    // are produced need to be valid.
+                    let (plan, _log, _trace) = match PlanningContext::plan_2(&*planner, &ctx) {
+                        Ok(x) => x,
+                        Err(_) => continue,
+                    };
+                    let tail = match plan.provisional_expand_2(ctx.game, ctx.ball_prediction) {
+                        Ok(tail) => tail,
+                        Err(_) => continue,
+                    };
+                    let violations = plan.validate(&*planner, &tail);
+                    assert!(violations.is_empty(), "{:?}", violations);
+                    tested += 1;
+                }
+            }
+            assert!(tested >= 25);
+        });
+    }
+
+    #[test]
+    fn detects_gaps() {
+        let mut rng = Rng(999);
+        let start = random_start(&mut rng);
+        let elsewhere = CarState2D {
+            loc: start.loc + Vector2::new(500.0, 0.0),
+            ..start.clone()
+        };
+        let chain: Vec<Box<dyn SegmentPlan>> = vec![Box::new(Straight::new(
+            elsewhere.clone(),
+            elsewhere.loc + Vector2::new(0.0, 100.0),
+            0.0,
+            StraightMode::Asap,
+            true,
+        ))];
+        let violations = check_continuity(
+            &start.to_3d(),
+            chain.iter().map(|s| ("test", &**s)),
+            Some(start.loc),
+        );
+        assert_eq!(violations.len(), 2);
+    }
+}
@@ -1,9 +1,14 @@
 pub use self::segments::StraightMode;
 
 pub mod behavior;
+pub mod continuity;
 pub mod models;
+pub mod parallel;
 pub mod plan;
 pub mod recover;
 mod segments;
+pub mod snapshot;
 #[cfg(test)]
-mod test;
+pub mod test;
+pub mod trace;
+pub mod tracking;
@@ -1,14 +1,21 @@
 use crate::{
     helpers::ball::BallTrajectory,
+    routing::{
+        continuity::{check_continuity, ContinuityViolation},
+        snapshot::SegmentSnapshot,
+        trace::PlanningTrace,
+        tracking::Deviation,
+    },
     strategy::{Context, Context2, Game, Scenario},
     utils::geometry::flattener::Flattener,
 };
 use common::{physics, prelude::*, rl, PrettyPrint};
 use derive_new::new;
 use nalgebra::{Point2, Point3, Unit, UnitComplex, UnitQuaternion, Vector2, Vector3};
-use std::{fmt, iter};
+use serde_derive::{Deserialize, Serialize};
+use std::{fmt, iter, time::Instant};
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct CarState {
     pub loc: Point3<f32>,
     pub rot: UnitQuaternion<f32>,
@@ -79,7 +86,7 @@ impl<'a> From<&'a common::halfway_house::PlayerInfo> for CarState {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct CarState2D {
     pub loc: Point2<f32>,
     pub rot: UnitComplex<f32>,
@@ -116,6 +123,39 @@ pub trait RoutePlanner: RoutePlannerCloneBox + Send {
         ctx: &PlanningContext<'_, '_>,
         dump: &mut PlanningDump<'_>,
     ) -> Result<RoutePlan, RoutePlanError>;
+
+    /// Start planning in a way that can be spread across several frames.
+    /// Planners which can only plan all at once return `None`.
+    fn incremental(&self) -> Option<Box<dyn PlanningSession>> {
+        None
+    }
+
+    /// Where the fully expanded route is guaranteed to end, for planners which
+    /// can promise that. This is only used to validate plans.
+    fn target_loc(&self) -> Option<Point2<f32>> {
+        None
+    }
+}
+
+/// An in-progress plan which can be refined a little bit at a time.
+pub trait PlanningSession: Send {
+    /// Keep planning until `deadline`. The first call decides the starting
+    /// state; in later calls only `ctx.game` and `ctx.ball_prediction` are
+    /// used.
+    fn step(
+        &mut self,
+        ctx: &PlanningContext<'_, '_>,
+        dump: &mut PlanningDump<'_>,
+        deadline: Instant,
+    ) -> Result<PlanningStep, RoutePlanError>;
+}
+
+pub struct PlanningStep {
+    /// A better plan than any returned so far, if one was found during this
+    /// step.
+    pub improved: Option<RoutePlan>,
+    /// If true, planning is complete and the session should be dropped.
+    pub finished: bool,
 }
 
 pub trait RoutePlannerCloneBox {
@@ -155,7 +195,7 @@ impl<'a: 's, 's> PlanningContext<'a, 's> {
     pub fn plan(
         planner: &dyn RoutePlanner,
         ctx: &mut Context<'_>,
-    ) -> Result<(RoutePlan, Vec<String>), ProvisionalExpandError<'a>> {
+    ) -> Result<(RoutePlan, Vec<String>, PlanningTrace), ProvisionalExpandError<'a>> {
         let (ctx, _eeg) = ctx.split();
         Self::plan2(planner, &ctx)
     }
@@ -163,7 +203,7 @@ impl<'a: 's, 's> PlanningContext<'a, 's> {
     pub fn plan2(
         planner: &dyn RoutePlanner,
         ctx: &Context2<'_, '_>,
-    ) -> Result<(RoutePlan, Vec<String>), ProvisionalExpandError<'a>> {
+    ) -> Result<(RoutePlan, Vec<String>, PlanningTrace), ProvisionalExpandError<'a>> {
         let context = PlanningContext::from_context(ctx);
         Self::plan_2(planner, &context)
     }
@@ -171,22 +211,31 @@ impl<'a: 's, 's> PlanningContext<'a, 's> {
     pub fn plan_2(
         planner: &dyn RoutePlanner,
         context: &PlanningContext<'_, '_>,
-    ) -> Result<(RoutePlan, Vec<String>), ProvisionalExpandError<'a>> {
+    ) -> Result<(RoutePlan, Vec<String>, PlanningTrace), ProvisionalExpandError<'a>> {
         let mut log = Vec::new();
-        let mut dump = PlanningDump { log: &mut log };
+        let mut trace = PlanningTrace::default();
+        let mut dump = PlanningDump {
+            log: &mut log,
+            trace: &mut trace,
+        };
         match planner.plan(context, &mut dump) {
-            Ok(plan) => Ok((plan, log)),
-            Err(error) => Err(ProvisionalExpandError {
-                planner_name: planner.name(),
-                error,
-                log,
-            }),
+            Ok(plan) => Ok((plan, log, trace)),
+            Err(error) => {
+                dump.log_error(planner, &context.start, &error);
+                Err(ProvisionalExpandError {
+                    planner_name: planner.name(),
+                    error,
+                    log,
+                    trace,
+                })
+            }
         }
     }
 }
 
 pub struct PlanningDump<'a> {
     pub log: &'a mut Vec<String>,
+    pub trace: &'a mut PlanningTrace,
 }
 
 impl<'a> PlanningDump<'a> {
@@ -208,12 +257,24 @@ impl<'a> PlanningDump<'a> {
     }
 
     pub fn log_start(&mut self, planner: &dyn RoutePlanner, state: &CarState) {
+        self.trace.start(planner.name(), state);
         self.log_pretty(planner, "start loc", state.loc);
         self.log_pretty(planner, "start rot", state.rot);
         self.log_pretty(planner, "start vel", state.vel);
     }
 
+    pub fn log_error(
+        &mut self,
+        planner: &dyn RoutePlanner,
+        state: &CarState,
+        error: &RoutePlanError,
+    ) {
+        self.trace.error(planner.name(), state, error);
+        self.log(planner, format!("error {:?}", error));
+    }
+
     pub fn log_plan(&mut self, planner: &dyn RoutePlanner, plan: &RoutePlan) {
+        self.trace.segment(planner.name(), &*plan.segment);
         let name = plan.segment.name();
         let end = plan.segment.end();
         let duration = plan.segment.duration();
@@ -237,7 +298,8 @@ impl<'a> PlanningDump<'a> {
 }
 
 pub struct ProvisionalPlanExpansionTail {
-    items: Vec<Box<dyn SegmentPlan>>,
+    /// Each segment, along with the name of the planner which produced it.
+    items: Vec<(&'static str, Box<dyn SegmentPlan>)>,
 }
 
 #[derive(new)]
@@ -248,7 +310,7 @@ pub struct ProvisionalPlanExpansion<'a> {
 
 impl<'a> ProvisionalPlanExpansion<'a> {
     pub fn iter(&'a self) -> impl Iterator<Item = &'a (dyn SegmentPlan + 'a)> {
-        iter::once(self.head).chain(self.tail.items.iter().map(|s| &**s))
+        iter::once(self.head).chain(self.tail.items.iter().map(|(_, s)| &**s))
     }
 
     pub fn duration(&self) -> f32 {
@@ -313,14 +375,19 @@ impl RoutePlan {
                 ball_prediction,
             };
             let mut log = Vec::new();
-            let mut dump = PlanningDump { log: &mut log };
-            match Self::expand_round(&**planner, &context, &mut dump, |s| tail.push(s)) {
+            let mut trace = PlanningTrace::default();
+            let mut dump = PlanningDump {
+                log: &mut log,
+                trace: &mut trace,
+            };
+            match Self::expand_round(&**planner, &context, &mut dump, |p, s| tail.push((p, s))) {
                 Ok(()) => {}
                 Err((planner_name, error)) => {
                     return Err(ProvisionalExpandError {
                         planner_name,
                         error,
                         log,
+                        trace,
                     });
                 }
             }
@@ -328,19 +395,37 @@ impl RoutePlan {
         Ok(ProvisionalPlanExpansionTail { items: tail })
     }
 
+    /// Check that the expanded plan is continuous, and that it ends where
+    /// `planner` (the planner which produced this plan) promised.
+    pub fn validate(
+        &self,
+        planner: &dyn RoutePlanner,
+        tail: &ProvisionalPlanExpansionTail,
+    ) -> Vec<ContinuityViolation> {
+        let segments = iter::once((planner.name(), &*self.segment))
+            .chain(tail.items.iter().map(|(planner, s)| (*planner, &**s)));
+        check_continuity(&self.segment.start(), segments, planner.target_loc())
+    }
+
     fn expand_round(
         planner: &dyn RoutePlanner,
         ctx: &PlanningContext<'_, '_>,
         dump: &mut PlanningDump<'_>,
-        mut sink: impl FnMut(Box<dyn SegmentPlan>),
+        mut sink: impl FnMut(&'static str, Box<dyn SegmentPlan>),
     ) -> Result<(), (&'static str, RoutePlanError)> {
         dump.log.push(format!("-{}----------", planner.name()));
-        let plan = planner.plan(ctx, dump).map_err(|e| (planner.name(), e))?;
+        let plan = match planner.plan(ctx, dump) {
+            Ok(plan) => plan,
+            Err(error) => {
+                dump.log_error(planner, &ctx.start, &error);
+                return Err((planner.name(), error));
+            }
+        };
         dump.log_plan(planner, &plan);
 
         let state = plan.segment.end();
         let duration = plan.segment.duration();
-        sink(plan.segment);
+        sink(planner.name(), plan.segment);
 
         match plan.next {
             Some(planner) => {
@@ -360,6 +445,7 @@ pub struct ProvisionalExpandError<'a> {
     pub planner_name: &'a str,
     pub error: RoutePlanError,
     pub log: Vec<String>,
+    pub trace: PlanningTrace,
 }
 
 pub trait SegmentPlan: SegmentPlanCloneBox + Send {
@@ -369,6 +455,7 @@ pub trait SegmentPlan: SegmentPlanCloneBox + Send {
     fn duration(&self) -> f32;
     fn run(&self) -> Box<dyn SegmentRunner>;
     fn draw(&self, ctx: &mut Context<'_>);
+    fn snapshot(&self) -> SegmentSnapshot;
 }
 
 pub trait SegmentPlanCloneBox {
@@ -400,17 +487,25 @@ pub enum SegmentRunAction {
     Yield(common::halfway_house::PlayerInput),
     Success,
     Failure,
+    /// The car strayed too far from the plan. The rest of the route should be
+    /// replanned.
+    Deviated(Deviation),
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        routing::models::{CarState, PlanningContext, PlanningDump, RoutePlanner},
+        routing::{
+            models::{
+                CarState, PlanningContext, PlanningDump, ProvisionalPlanExpansion, RoutePlanner,
+            },
+            trace::{expansion_svg, trace_svg, PlanningTrace},
+        },
         strategy::Scenario,
     };
     use common::prelude::*;
     use nalgebra::{Point3, UnitComplex, Vector3};
-    use std::{f32::consts::PI, mem};
+    use std::{f32::consts::PI, fs, mem};
 
     #[test]
     #[ignore(note = "Use this as needed to debug a plan.")]
@@ -431,12 +526,19 @@ mod tests {
             ball_prediction: &ball_prediction,
         };
         let mut log = Vec::new();
-        let mut dump = PlanningDump { log: &mut log };
-        planner
-            .plan(&ctx, &mut dump)
-            .unwrap()
-            .provisional_expand(&scenario)
-            .ok()
-            .unwrap();
+        let mut trace = PlanningTrace::default();
+        let mut dump = PlanningDump {
+            log: &mut log,
+            trace: &mut trace,
+        };
+        // Open this in a browser and hover over things to see what happened.
+        let svg = match planner.plan(&ctx, &mut dump) {
+            Ok(plan) => {
+                let tail = plan.provisional_expand(&scenario).ok().unwrap();
+                expansion_svg(&ProvisionalPlanExpansion::new(&*plan.segment, &tail))
+            }
+            Err(_) => trace_svg(&trace),
+        };
+        fs::write("debug_plan.svg", svg).unwrap();
     }
 }
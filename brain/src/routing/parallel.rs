@@ -0,0 +1,320 @@
+//! Evaluate several route planners at once, so more options fit in the frame
+//! budget.
+
+use crate::routing::{
+    models::{
+        PlanningContext, PlanningDump, ProvisionalExpandError, ProvisionalPlanExpansion, RoutePlan,
+        RoutePlanError, RoutePlanner,
+    },
+    trace::PlanningTrace,
+};
+use lazy_static::lazy_static;
+use ordered_float::OrderedFloat;
+use scoped_threadpool::Pool;
+use std::{iter, sync::Mutex, vec};
+
+const DEFAULT_WORKERS: usize = 3;
+
+type Queue = Mutex<iter::Enumerate<vec::IntoIter<Box<dyn RoutePlanner>>>>;
+
+lazy_static! {
+    static ref SHARED: Mutex<PlannerPool> = Mutex::new(PlannerPool::default());
+}
+
+/// A small pool of worker threads which plan against a shared, immutable
+/// `PlanningContext`. The threads are spawned once and live as long as the
+/// pool.
+pub struct PlannerPool {
+    /// `None` if there is only one worker, in which case planning happens on
+    /// the calling thread.
+    threads: Option<Pool>,
+}
+
+/// The outcome of one planner.
+pub struct Candidate {
+    /// The planner's position in the list passed to `PlannerPool::evaluate`.
+    pub index: usize,
+    pub planner: &'static str,
+    pub result: Result<RankedPlan, RoutePlanError>,
+    pub log: Vec<String>,
+    pub trace: PlanningTrace,
+}
+
+pub struct RankedPlan {
+    pub plan: RoutePlan,
+    /// How long until the fully expanded plan completes.
+    pub arrival_time: f32,
+}
+
+impl Default for PlannerPool {
+    fn default() -> Self {
+        Self::new(DEFAULT_WORKERS)
+    }
+}
+
+impl PlannerPool {
+    pub fn new(workers: usize) -> Self {
+        assert!(workers >= 1);
+        let threads = if workers > 1 {
+            Some(Pool::new(workers as u32))
+        } else {
+            None
+        };
+        Self { threads }
+    }
+
+    /// Like `evaluate`, but using a pool shared by the whole process. If that
+    /// pool is already busy (e.g. one of its planners is itself evaluating
+    /// planners), plan on this thread instead.
+    pub fn evaluate_shared(
+        ctx: &PlanningContext<'_, '_>,
+        planners: Vec<Box<dyn RoutePlanner>>,
+    ) -> Vec<Candidate> {
+        match SHARED.try_lock() {
+            Ok(mut pool) => pool.evaluate(ctx, planners),
+            Err(_) => Self::new(1).evaluate(ctx, planners),
+        }
+    }
+
+    /// Plan (and provisionally expand) each of `planners`, and return every
+    /// outcome. Successful plans come first, fastest arrival first; failures
+    /// follow in their original order.
+    pub fn evaluate(
+        &mut self,
+        ctx: &PlanningContext<'_, '_>,
+        planners: Vec<Box<dyn RoutePlanner>>,
+    ) -> Vec<Candidate> {
+        let mut candidates = match self.threads {
+            Some(ref mut threads) if planners.len() > 1 => {
+                let workers = (threads.thread_count() as usize).min(planners.len());
+                let queue = Mutex::new(planners.into_iter().enumerate());
+                let results = Mutex::new(Vec::new());
+                threads.scoped(|scope| {
+                    for _ in 0..workers {
+                        scope.execute(|| {
+                            let candidates = work(ctx, &queue);
+                            results.lock().unwrap().extend(candidates);
+                        });
+                    }
+                });
+                results.into_inner().unwrap()
+            }
+            // Not worth the overhead of handing off to other threads.
+            _ => planners
+                .into_iter()
+                .enumerate()
+                .map(|(index, planner)| evaluate_one(ctx, index, &*planner))
+                .collect(),
+        };
+
+        candidates.sort_by_key(|c| match c.result {
+            Ok(ref ranked) => (false, OrderedFloat(ranked.arrival_time), c.index),
+            Err(_) => (true, OrderedFloat(0.0), c.index),
+        });
+        candidates
+    }
+}
+
+/// Pull planners off the shared queue until it's empty.
+fn work(ctx: &PlanningContext<'_, '_>, queue: &Queue) -> Vec<Candidate> {
+    let mut results = Vec::new();
+    loop {
+        let job = queue.lock().unwrap().next();
+        match job {
+            Some((index, planner)) => results.push(evaluate_one(ctx, index, &*planner)),
+            None => return results,
+        }
+    }
+}
+
+fn evaluate_one(
+    ctx: &PlanningContext<'_, '_>,
+    index: usize,
+    planner: &dyn RoutePlanner,
+) -> Candidate {
+    let mut log = Vec::new();
+    let mut trace = PlanningTrace::default();
+    let result = plan_and_expand(ctx, planner, &mut log, &mut trace);
+    Candidate {
+        index,
+        planner: planner.name(),
+        result,
+        log,
+        trace,
+    }
+}
+
+fn plan_and_expand(
+    ctx: &PlanningContext<'_, '_>,
+    planner: &dyn RoutePlanner,
+    log: &mut Vec<String>,
+    trace: &mut PlanningTrace,
+) -> Result<RankedPlan, RoutePlanError> {
+    let mut dump = PlanningDump {
+        log: &mut *log,
+        trace: &mut *trace,
+    };
+    let plan = match planner.plan(ctx, &mut dump) {
+        Ok(plan) => plan,
+        Err(error) => {
+            dump.log_error(planner, &ctx.start, &error);
+            return Err(error);
+        }
+    };
+
+    let tail = match plan.provisional_expand_2(ctx.game, ctx.ball_prediction) {
+        Ok(tail) => tail,
+        Err(ProvisionalExpandError {
+            error,
+            log: expand_log,
+            trace: expand_trace,
+            ..
+        }) => {
+            log.extend(expand_log);
+            trace.events.extend(expand_trace.events);
+            return Err(error);
+        }
+    };
+    let arrival_time = ProvisionalPlanExpansion::new(&*plan.segment, &tail).duration();
+    Ok(RankedPlan { plan, arrival_time })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routing::{
+        models::{
+            CarState2D, PlanningContext, PlanningDump, RoutePlan, RoutePlanError, RoutePlanner,
+            SegmentPlan,
+        },
+        parallel::PlannerPool,
+        plan::FastestPlanner,
+        segments::{Straight, StraightMode},
+        test::{with_fixed_context, CookedPlanner},
+        trace::PlanningTrace,
+    };
+
+    #[derive(Clone)]
+    struct Broken;
+
+    impl RoutePlanner for Broken {
+        fn name(&self) -> &'static str {
+            stringify!(Broken)
+        }
+
+        fn plan(
+            &self,
+            _ctx: &PlanningContext<'_, '_>,
+            _dump: &mut PlanningDump<'_>,
+        ) -> Result<RoutePlan, RoutePlanError> {
+            Err(RoutePlanError::OtherError("broken"))
+        }
+    }
+
+    fn straight(start: &CarState2D, dist: f32) -> Box<dyn RoutePlanner> {
+        let end_loc = start.loc + start.forward_axis().into_inner() * dist;
+        Box::new(CookedPlanner::new(Straight::new(
+            start.clone(),
+            end_loc,
+            0.0,
+            StraightMode::Asap,
+            true,
+        )))
+    }
+
+    fn planners(ctx: &PlanningContext<'_, '_>) -> Vec<Box<dyn RoutePlanner>> {
+        let start = ctx.start.to_2d_assume();
+        vec![
+            straight(&start, 3000.0),
+            Box::new(Broken),
+            straight(&start, 1000.0),
+            straight(&start, 2000.0),
+        ]
+    }
+
+    #[test]
+    fn ranks_by_arrival_time() {
+        with_fixed_context(|ctx| {
+            let candidates = PlannerPool::new(3).evaluate(ctx, planners(ctx));
+            let order: Vec<_> = candidates.iter().map(|c| c.index).collect();
+            assert_eq!(order, vec![2, 3, 0, 1]);
+            assert!(candidates[3].result.is_err());
+            let times: Vec<_> = candidates[..3]
+                .iter()
+                .map(|c| c.result.as_ref().ok().unwrap().arrival_time)
+                .collect();
+            assert!(times[0] < times[1] && times[1] < times[2]);
+        });
+    }
+
+    /// A route with a second leg, which needs expanding.
+    #[derive(Clone)]
+    struct TwoLegs {
+        first: Straight,
+        second: Straight,
+    }
+
+    impl RoutePlanner for TwoLegs {
+        fn name(&self) -> &'static str {
+            stringify!(TwoLegs)
+        }
+
+        fn plan(
+            &self,
+            _ctx: &PlanningContext<'_, '_>,
+            _dump: &mut PlanningDump<'_>,
+        ) -> Result<RoutePlan, RoutePlanError> {
+            Ok(RoutePlan {
+                segment: Box::new(self.first.clone()),
+                next: Some(Box::new(CookedPlanner::new(self.second.clone()))),
+            })
+        }
+    }
+
+    #[test]
+    fn fastest_planner_keeps_the_rest_of_the_route() {
+        with_fixed_context(|ctx| {
+            let start = ctx.start.to_2d_assume();
+            let forward = start.forward_axis().into_inner();
+            let first = Straight::new(
+                start.clone(),
+                start.loc + forward * 1000.0,
+                0.0,
+                StraightMode::Asap,
+                true,
+            );
+            let middle = first.end().to_2d_assume();
+            let second = Straight::new(
+                middle.clone(),
+                middle.loc + forward * 1000.0,
+                0.0,
+                StraightMode::Asap,
+                true,
+            );
+            let first_duration = first.duration();
+            let planner = FastestPlanner::new(vec![Box::new(TwoLegs { first, second })]);
+            let mut log = Vec::new();
+            let mut trace = PlanningTrace::default();
+            let mut dump = PlanningDump {
+                log: &mut log,
+                trace: &mut trace,
+            };
+            let plan = planner.plan(ctx, &mut dump).ok().unwrap();
+            // The second leg must still be planned from wherever the first one
+            // actually ends, not baked in ahead of time.
+            assert!((plan.segment.duration() - first_duration).abs() < 1e-3);
+            assert_eq!(plan.next.unwrap().name(), stringify!(CookedPlanner));
+        });
+    }
+
+    #[test]
+    fn same_results_on_one_thread() {
+        with_fixed_context(|ctx| {
+            let parallel = PlannerPool::new(4).evaluate(ctx, planners(ctx));
+            let serial = PlannerPool::new(1).evaluate(ctx, planners(ctx));
+            assert_eq!(
+                parallel.iter().map(|c| c.index).collect::<Vec<_>>(),
+                serial.iter().map(|c| c.index).collect::<Vec<_>>(),
+            );
+        });
+    }
+}
@@ -2,7 +2,7 @@ use crate::{
     behavior::strike::GroundedHit,
     helpers::{
         ball::{BallFrame, BallTrajectory},
-        intercept::{naive_ground_intercept_2, naive_intercept_penalty},
+        intercept::{naive_ground_intercept_2, naive_intercept_penalty, NaiveIntercept},
     },
     routing::{
         models::{
@@ -19,11 +19,16 @@ use crate::{
 use common::{prelude::*, Time};
 use derive_new::new;
 use nameof::name_of_type;
+use ordered_float::NotNan;
 
 #[derive(Clone, new)]
 pub struct GroundIntercept {
     #[new(value = "GroundInterceptAllowDodging::OnlyIfSlow")]
     allow_dodging: GroundInterceptAllowDodging,
+    #[new(value = "false")]
+    avoid_opponents: bool,
+    #[new(value = "0.0")]
+    boost_reserve: f32,
 }
 
 #[derive(Copy, Clone)]
@@ -42,6 +47,42 @@ impl GroundIntercept {
         };
         self
     }
+
+    /// Detour around opponents who are predicted to be in the way.
+    pub fn avoid_opponents(mut self, avoid_opponents: bool) -> Self {
+        self.avoid_opponents = avoid_opponents;
+        self
+    }
+
+    /// Find the fastest intercept which still arrives with at least this much
+    /// boost.
+    pub fn boost_reserve(mut self, boost_reserve: f32) -> Self {
+        self.boost_reserve = boost_reserve;
+        self
+    }
+}
+
+/// One point on the curve of arrival time vs. boost spent.
+#[derive(Copy, Clone, Debug)]
+pub struct BoostTradeoff {
+    /// The minimum boost to arrive with.
+    pub boost_reserve: f32,
+    pub boost_spent: f32,
+    /// Seconds from now until the car reaches the ball.
+    pub arrival_time: f32,
+}
+
+/// Among the points in `curve` that arrive no more than `max_delay` seconds
+/// after the fastest one, pick the one which spends the least boost.
+pub fn cheapest_tradeoff(curve: &[BoostTradeoff], max_delay: f32) -> Option<&BoostTradeoff> {
+    let fastest = curve
+        .iter()
+        .map(|p| p.arrival_time)
+        .min_by_key(|&t| NotNan::new(t).unwrap())?;
+    curve
+        .iter()
+        .filter(|p| p.arrival_time <= fastest + max_delay)
+        .min_by_key(|p| NotNan::new(p.boost_spent).unwrap())
 }
 
 impl RoutePlanner for GroundIntercept {
@@ -63,8 +104,9 @@ impl RoutePlanner for GroundIntercept {
         );
 
         // Naive first pass to get a rough location.
-        let guess = Self::calc_intercept(&ctx.start, ctx.ball_prediction)
-            .ok_or_else(|| RoutePlanError::UnknownIntercept)?;
+        let guess =
+            Self::calc_intercept_with_reserve(&ctx.start, ctx.ball_prediction, self.boost_reserve)
+                .ok_or_else(|| RoutePlanError::UnknownIntercept)?;
 
         guard!(ctx.start, IsSkidding, RoutePlanError::MustNotBeSkidding {
             recover_target_loc: guess.loc.to_2d(),
@@ -86,6 +128,7 @@ impl RoutePlanner for GroundIntercept {
             };
         let turn = TurnPlanner::new(guess.loc.to_2d(), None)
             .reverse_angle_hint(reverse_angle_hint.to_axis())
+            .avoid_opponents(self.avoid_opponents)
             .plan(ctx, dump)?;
 
         let mut straight_time = guess.t - turn.segment.duration();
@@ -110,7 +153,8 @@ impl RoutePlanner for GroundIntercept {
         let straight = GroundStraightPlanner::new(guess.loc.to_2d(), StraightMode::Fake)
             .target_time(straight_time)
             .end_chop(0.5)
-            .allow_dodging(allow_dodging);
+            .allow_dodging(allow_dodging)
+            .boost_reserve(self.boost_reserve);
 
         Ok(ChainedPlanner::join_planner(turn, Some(Box::new(straight))))
     }
@@ -121,11 +165,90 @@ impl GroundIntercept {
         start: &CarState,
         ball_prediction: &'ball BallTrajectory,
     ) -> Option<&'ball BallFrame> {
-        let intercept = naive_ground_intercept_2(start, ball_prediction, |ball| {
-            ball.loc.z < GroundedHit::MAX_BALL_Z
-        })?;
+        Self::calc_intercept_with_reserve(start, ball_prediction, 0.0)
+    }
+
+    pub fn calc_intercept_with_reserve<'ball>(
+        start: &CarState,
+        ball_prediction: &'ball BallTrajectory,
+        boost_reserve: f32,
+    ) -> Option<&'ball BallFrame> {
+        let (intercept, _) = Self::naive_intercept(start, ball_prediction, boost_reserve)?;
         let intercept = ball_prediction.at_time(intercept.time).unwrap();
         let penalty = naive_intercept_penalty(start, intercept);
         Some(ball_prediction.at_time_or_last(intercept.t + penalty))
     }
+
+    /// Sample the fastest intercept at a range of boost reserves, from spending
+    /// everything down to spending nothing. The result is ordered from most to
+    /// least boost spent.
+    pub fn boost_tradeoff(
+        start: &CarState,
+        ball_prediction: &BallTrajectory,
+    ) -> Vec<BoostTradeoff> {
+        const STEP: f32 = 10.0;
+
+        let mut result = Vec::new();
+        let mut boost_reserve = 0.0;
+        loop {
+            let boost_reserve_clamped = boost_reserve.min(start.boost);
+            if let Some((intercept, end_boost)) =
+                Self::naive_intercept(start, ball_prediction, boost_reserve_clamped)
+            {
+                let ball = ball_prediction.at_time(intercept.time).unwrap();
+                let penalty = naive_intercept_penalty(start, ball);
+                result.push(BoostTradeoff {
+                    boost_reserve: boost_reserve_clamped,
+                    boost_spent: start.boost - end_boost,
+                    arrival_time: intercept.time - ball_prediction.start().t + penalty,
+                });
+            }
+            if boost_reserve >= start.boost {
+                break;
+            }
+            boost_reserve += STEP;
+        }
+        result
+    }
+
+    /// Returns the intercept along with the boost remaining on arrival.
+    fn naive_intercept(
+        start: &CarState,
+        ball_prediction: &BallTrajectory,
+        boost_reserve: f32,
+    ) -> Option<(NaiveIntercept, f32)> {
+        // Hide the reserve from the simulation so it never gets spent.
+        let reserved = boost_reserve.max(0.0).min(start.boost);
+        let budget = CarState {
+            boost: start.boost - reserved,
+            ..start.clone()
+        };
+        let intercept = naive_ground_intercept_2(&budget, ball_prediction, |ball| {
+            ball.loc.z < GroundedHit::MAX_BALL_Z
+        })?;
+        let end_boost = intercept.car_boost + reserved;
+        Some((intercept, end_boost))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routing::plan::ground_intercept::{cheapest_tradeoff, BoostTradeoff};
+
+    fn point(boost_spent: f32, arrival_time: f32) -> BoostTradeoff {
+        BoostTradeoff {
+            boost_reserve: 100.0 - boost_spent,
+            boost_spent,
+            arrival_time,
+        }
+    }
+
+    #[test]
+    fn cheapest_within_delay() {
+        let curve = [point(60.0, 1.0), point(30.0, 1.1), point(0.0, 1.5)];
+        assert_eq!(cheapest_tradeoff(&curve, 0.0).unwrap().boost_spent, 60.0);
+        assert_eq!(cheapest_tradeoff(&curve, 0.2).unwrap().boost_spent, 30.0);
+        assert_eq!(cheapest_tradeoff(&curve, 1.0).unwrap().boost_spent, 0.0);
+        assert!(cheapest_tradeoff(&[], 1.0).is_none());
+    }
 }
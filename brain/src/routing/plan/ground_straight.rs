@@ -4,7 +4,7 @@ use crate::routing::{
         RoutePlanner, SegmentPlan,
     },
     recover::{IsSkidding, NotFacingTarget2D, NotOnFlatGround},
-    segments::{Brake, Chain, ForwardDodge, Straight, StraightMode},
+    segments::{Brake, Chain, ForwardDodge, SpeedFlip, Straight, StraightMode},
 };
 use arrayvec::ArrayVec;
 use common::prelude::*;
@@ -12,7 +12,7 @@ use derive_new::new;
 use nalgebra::Point2;
 use nameof::name_of_type;
 use ordered_float::NotNan;
-use simulate::{Car1D, CarForwardDodge, CarForwardDodge1D};
+use simulate::{Car1D, CarForwardDodge, CarForwardDodge1D, CarSpeedFlip, CarSpeedFlip1D};
 
 /// Drive straight. Requires the car to already be facing the target (i.e., it
 /// won't steer left or right).
@@ -27,6 +27,9 @@ pub struct GroundStraightPlanner {
     mode: StraightMode,
     allow_dodging: bool,
     allow_boost: bool,
+    /// Never boost below this amount.
+    boost_reserve: f32,
+    allow_speed_flip: bool,
     always_prefer_dodge: bool,
 }
 
@@ -39,6 +42,8 @@ impl GroundStraightPlanner {
             mode,
             allow_dodging: true,
             allow_boost: true,
+            boost_reserve: 0.0,
+            allow_speed_flip: false,
             always_prefer_dodge: true,
         }
     }
@@ -64,6 +69,19 @@ impl GroundStraightPlanner {
         self
     }
 
+    /// Arrive with at least this much boost.
+    pub fn boost_reserve(mut self, boost_reserve: f32) -> Self {
+        self.boost_reserve = boost_reserve;
+        self
+    }
+
+    /// Consider speed flips in addition to forward dodges. These only happen
+    /// if boosting is allowed, since the flip relies on boosting throughout.
+    pub fn allow_speed_flip(mut self, allow_speed_flip: bool) -> Self {
+        self.allow_speed_flip = allow_speed_flip;
+        self
+    }
+
     pub fn always_prefer_dodge(mut self, always_prefer_dodge: bool) -> Self {
         self.always_prefer_dodge = always_prefer_dodge;
         self
@@ -99,20 +117,49 @@ impl RoutePlanner for GroundStraightPlanner {
             self.end_chop,
             self.mode,
             self.allow_boost,
+            self.boost_reserve,
         );
         let straight = straight.plan(ctx, dump);
 
         let dodge = if self.allow_dodging {
-            let planner =
-                StraightWithDodge::new(self.target_loc, self.target_time, self.end_chop, self.mode);
+            let planner = StraightWithDodge::new(
+                self.target_loc,
+                self.target_time,
+                self.end_chop,
+                self.mode,
+                self.boost_reserve,
+                false,
+            );
             Some(planner.plan(ctx, dump))
         } else {
             None
         };
 
-        // If we're prioritizing dodges and we have a dodge, return early.
+        // The speed flip behavior boosts the whole way through, so it can't honor a
+        // reserve.
+        let speed_flip = if self.allow_speed_flip && self.allow_boost && self.boost_reserve <= 0.0 {
+            let planner = StraightWithDodge::new(
+                self.target_loc,
+                self.target_time,
+                self.end_chop,
+                self.mode,
+                self.boost_reserve,
+                true,
+            );
+            Some(planner.plan(ctx, dump))
+        } else {
+            None
+        };
+
+        // If we're prioritizing dodges and we have a dodge, return the fastest one
+        // early.
         if self.always_prefer_dodge {
-            if let Some(Ok(plan)) = dodge {
+            let dodges = dodge
+                .iter()
+                .chain(speed_flip.iter())
+                .filter_map(|d| d.as_ref().ok())
+                .cloned();
+            if let Some(plan) = dodges.min_by_key(|p| NotNan::new(p.segment.duration()).unwrap()) {
                 return Ok(plan);
             }
         }
@@ -123,6 +170,9 @@ impl RoutePlanner for GroundStraightPlanner {
         if let Some(dodge) = dodge {
             plans.push(dodge);
         }
+        if let Some(speed_flip) = speed_flip {
+            plans.push(speed_flip);
+        }
         Ok(fastest(at_least_one_ok(plans)?))
     }
 }
@@ -160,6 +210,7 @@ struct StraightSimple {
     end_chop: f32,
     mode: StraightMode,
     allow_boost: bool,
+    boost_reserve: f32,
 }
 
 impl RoutePlanner for StraightSimple {
@@ -201,7 +252,7 @@ impl RoutePlanner for StraightSimple {
             });
         }
 
-        let segment = Straight::new(
+        let segment = Straight::with_boost_reserve(
             CarState2D {
                 loc: ctx.start.loc.to_2d(),
                 rot: ctx.start.rot.to_2d(),
@@ -212,6 +263,7 @@ impl RoutePlanner for StraightSimple {
             self.end_chop,
             self.mode,
             self.allow_boost,
+            self.boost_reserve,
         );
         Ok(RoutePlan {
             segment: Box::new(segment),
@@ -245,6 +297,9 @@ struct StraightWithDodge {
     /// shoot, position itself, etc.
     end_chop: f32,
     mode: StraightMode,
+    boost_reserve: f32,
+    /// Use a speed flip instead of a forward dodge.
+    speed_flip: bool,
 }
 
 impl RoutePlanner for StraightWithDodge {
@@ -276,6 +331,8 @@ impl RoutePlanner for StraightWithDodge {
             self.target_loc,
             self.target_time,
             self.end_chop,
+            self.boost_reserve,
+            self.speed_flip,
         )
         .collect();
         let dodge = dodges
@@ -283,7 +340,7 @@ impl RoutePlanner for StraightWithDodge {
             .min_by_key(|d| NotNan::new(d.score).unwrap())
             .ok_or(RoutePlanError::MovingTooFast)?;
 
-        let before = Straight::new(
+        let before = Straight::with_boost_reserve(
             CarState2D {
                 loc: ctx.start.loc.to_2d(),
                 rot: ctx.start.rot.to_2d(),
@@ -295,15 +352,23 @@ impl RoutePlanner for StraightWithDodge {
             0.0,
             StraightMode::Asap,
             true,
+            self.boost_reserve,
         );
 
-        let dodge = ForwardDodge::new(before.end(), dodge.dodge);
+        let dodge: Box<dyn SegmentPlan> = match dodge.dodge {
+            StraightDodgeKind::Forward(dodge) => Box::new(ForwardDodge::new(before.end(), dodge)),
+            StraightDodgeKind::SpeedFlip(flip) => Box::new(SpeedFlip::new(before.end(), flip)),
+        };
 
         let mut after = GroundStraightPlanner::new(self.target_loc, self.mode);
         after.target_time = self.target_time;
         after.end_chop = self.end_chop;
+        after.boost_reserve = self.boost_reserve;
+        // A speed flip leaves us near top speed, where another dodge would only cost
+        // us control of the car.
+        after.allow_dodging = !self.speed_flip;
 
-        let segment = Chain::new(vec![Box::new(before), Box::new(dodge)]);
+        let segment = Chain::new(vec![Box::new(before), dodge]);
         Ok(RoutePlan {
             segment: Box::new(segment),
             next: Some(Box::new(after)),
@@ -318,6 +383,8 @@ struct StraightDodgeCalculator {
     target_loc: Point2<f32>,
     target_time: Option<f32>,
     end_chop: f32,
+    boost_reserve: f32,
+    speed_flip: bool,
 }
 
 impl StraightDodgeCalculator {
@@ -347,18 +414,26 @@ impl StraightDodgeCalculator {
     }
 
     fn evaluate(&self, approach_time: f32) -> Option<StraightDodge> {
+        // Hide the reserve from the simulation so it never gets spent.
+        let reserved = self.boost_reserve.max(0.0).min(self.start.boost);
         let mut approach = Car1D::new()
             .with_speed(self.start.vel.to_2d().norm())
-            .with_boost(self.start.boost);
+            .with_boost(self.start.boost - reserved);
         approach.advance(approach_time, 1.0, true);
 
-        let dodge = CarForwardDodge::calc_1d(approach.speed());
+        let dodge = if self.speed_flip {
+            let flip = CarSpeedFlip::calc_1d(approach.speed(), approach.boost())?;
+            StraightDodgeKind::SpeedFlip(flip)
+        } else {
+            StraightDodgeKind::Forward(CarForwardDodge::calc_1d(approach.speed()))
+        };
+        let dodge_end_boost = dodge.end_boost(approach.boost());
 
         // `end_chop` is "dead time" that the caller requested we leave available for
         // the subsequent maneuver. Coasting on landing is the most conservative case.
         let mut landing = Car1D::new()
-            .with_speed(dodge.end_speed)
-            .with_boost(approach.boost());
+            .with_speed(dodge.end_speed())
+            .with_boost(dodge_end_boost);
         landing.advance(self.end_chop, 0.0, false);
 
         // Now we know where this dodge would take us. Let's check if it meets the
@@ -374,7 +449,7 @@ impl StraightDodgeCalculator {
 
         // Check that we don't land past the target.
         let target_traveled = (self.target_loc - self.start.loc.to_2d()).norm();
-        let total_dist = approach.distance() + dodge.end_dist + landing.distance();
+        let total_dist = approach.distance() + dodge.end_dist() + landing.distance();
         if total_dist >= target_traveled {
             return None;
         }
@@ -383,8 +458,8 @@ impl StraightDodgeCalculator {
         // target time, dodging made us go too fast.
         if let Some(target_time) = self.target_time {
             let mut coast = Car1D::new()
-                .with_speed(dodge.end_speed)
-                .with_boost(approach.boost());
+                .with_speed(dodge.end_speed())
+                .with_boost(dodge_end_boost);
             coast.advance(target_time - total_time, 0.0, false);
             if total_dist + coast.distance() > target_traveled {
                 return None;
@@ -395,8 +470,8 @@ impl StraightDodgeCalculator {
         // The equal term I choose is the minimum time needed to reach the target.
         // Do not boost here so that we don't outperform multiple dodges.
         let mut blitz = Car1D::new()
-            .with_speed(dodge.end_speed)
-            .with_boost(approach.boost());
+            .with_speed(dodge.end_speed())
+            .with_boost(dodge_end_boost);
         blitz.advance_by_distance(target_traveled - total_dist, 1.0, false);
         let score = total_time + blitz.time();
 
@@ -410,10 +485,46 @@ impl StraightDodgeCalculator {
 
 struct StraightDodge {
     approach_distance: f32,
-    dodge: CarForwardDodge1D,
+    dodge: StraightDodgeKind,
     score: f32,
 }
 
+enum StraightDodgeKind {
+    Forward(CarForwardDodge1D),
+    SpeedFlip(CarSpeedFlip1D),
+}
+
+impl StraightDodgeKind {
+    fn duration(&self) -> f32 {
+        match self {
+            StraightDodgeKind::Forward(dodge) => dodge.duration(),
+            StraightDodgeKind::SpeedFlip(flip) => flip.duration(),
+        }
+    }
+
+    fn end_dist(&self) -> f32 {
+        match self {
+            StraightDodgeKind::Forward(dodge) => dodge.end_dist,
+            StraightDodgeKind::SpeedFlip(flip) => flip.end_dist,
+        }
+    }
+
+    fn end_speed(&self) -> f32 {
+        match self {
+            StraightDodgeKind::Forward(dodge) => dodge.end_speed,
+            StraightDodgeKind::SpeedFlip(flip) => flip.end_speed,
+        }
+    }
+
+    /// The boost remaining after the dodge, given the boost at the start.
+    fn end_boost(&self, start_boost: f32) -> f32 {
+        match self {
+            StraightDodgeKind::Forward(_) => start_boost,
+            StraightDodgeKind::SpeedFlip(flip) => flip.end_boost,
+        }
+    }
+}
+
 #[cfg(test)]
 mod integration_tests {
     use crate::{
@@ -27,6 +27,7 @@ pub struct TurnPlanner {
     target_face: Point2<f32>,
     next: Option<Box<dyn RoutePlanner>>,
     reverse_angle_hint: Option<Unit<Vector2<f32>>>,
+    avoid_opponents: bool,
 }
 
 impl TurnPlanner {
@@ -35,6 +36,7 @@ impl TurnPlanner {
             target_face,
             next,
             reverse_angle_hint: None,
+            avoid_opponents: false,
         }
     }
 
@@ -42,6 +44,13 @@ impl TurnPlanner {
         self.reverse_angle_hint = Some(reverse_angle_hint);
         self
     }
+
+    /// If an opponent is predicted to be in the way after the turn, detour
+    /// around them.
+    pub fn avoid_opponents(mut self, avoid_opponents: bool) -> Self {
+        self.avoid_opponents = avoid_opponents;
+        self
+    }
 }
 
 impl RoutePlanner for TurnPlanner {
@@ -61,15 +70,25 @@ impl RoutePlanner for TurnPlanner {
             PathingUnawareTurnPlanner::new(self.target_face, self.reverse_angle_hint);
         let turn = pathing_unaware_planner.plan(ctx, dump)?;
         dump.log_plan(self, &turn);
-        let plan =
-            match pathing::avoid_plowing_into_goal_wall(&turn.segment.end(), self.target_face) {
-                None => turn,
-                Some(divert) => {
-                    dump.log(self, "diverting due to avoid_plowing_into_goal_wall");
-                    ChainedPlanner::new(divert, Some(Box::new(pathing_unaware_planner)))
-                        .plan(ctx, dump)?
+        let turn_end = turn.segment.end();
+        let divert = pathing::avoid_plowing_into_goal_wall(&turn_end, self.target_face)
+            .map(|divert| (divert, "avoid_plowing_into_goal_wall"))
+            .or_else(|| {
+                if !self.avoid_opponents {
+                    return None;
                 }
-            };
+                let time_offset = turn.segment.duration();
+                pathing::avoid_opponents(ctx.game, &turn_end, self.target_face, time_offset)
+                    .map(|divert| (divert, "avoid_opponents"))
+            });
+        let plan = match divert {
+            None => turn,
+            Some((divert, reason)) => {
+                dump.log(self, format!("diverting due to {}", reason));
+                ChainedPlanner::new(divert, Some(Box::new(pathing_unaware_planner)))
+                    .plan(ctx, dump)?
+            }
+        };
         Ok(ChainedPlanner::join_planner(plan, self.next.clone()))
     }
 }
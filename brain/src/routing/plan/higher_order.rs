@@ -1,5 +1,6 @@
-use crate::routing::models::{
-    PlanningContext, PlanningDump, RoutePlan, RoutePlanError, RoutePlanner,
+use crate::routing::{
+    models::{PlanningContext, PlanningDump, RoutePlan, RoutePlanError, RoutePlanner},
+    parallel::PlannerPool,
 };
 use derive_new::new;
 use nameof::name_of_type;
@@ -76,3 +77,60 @@ impl ChainedPlanner {
         result
     }
 }
+
+/// Evaluate every candidate concurrently and pick whichever arrives first.
+#[derive(Clone)]
+pub struct FastestPlanner {
+    candidates: Vec<Box<dyn RoutePlanner>>,
+}
+
+impl FastestPlanner {
+    pub fn new(candidates: Vec<Box<dyn RoutePlanner>>) -> Self {
+        assert!(!candidates.is_empty());
+        Self { candidates }
+    }
+}
+
+impl RoutePlanner for FastestPlanner {
+    fn name(&self) -> &'static str {
+        name_of_type!(FastestPlanner)
+    }
+
+    fn plan(
+        &self,
+        ctx: &PlanningContext<'_, '_>,
+        dump: &mut PlanningDump<'_>,
+    ) -> Result<RoutePlan, RoutePlanError> {
+        dump.log_start(self, &ctx.start);
+        let candidates = PlannerPool::evaluate_shared(ctx, self.candidates.clone());
+
+        let mut best = None;
+        let mut first_error = None;
+        for candidate in candidates {
+            dump.log.extend(candidate.log);
+            dump.trace.events.extend(candidate.trace.events);
+            match candidate.result {
+                Ok(ranked) => {
+                    dump.log(
+                        self,
+                        format!(
+                            "{} arrives in {:.2}",
+                            candidate.planner, ranked.arrival_time
+                        ),
+                    );
+                    if best.is_none() {
+                        // Keep `next` rather than the pool's expansion, so later legs
+                        // are planned from where the car really ends up.
+                        best = Some(ranked.plan);
+                    }
+                }
+                Err(error) => {
+                    if first_error.is_none() {
+                        first_error = Some(error);
+                    }
+                }
+            }
+        }
+        best.ok_or_else(|| first_error.unwrap())
+    }
+}
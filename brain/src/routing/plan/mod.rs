@@ -1,7 +1,14 @@
 pub use self::{
-    boost::GetDollar, ground_drive::GroundDrive, ground_intercept::GroundIntercept,
-    ground_straight::GroundStraightPlanner, ground_turn::TurnPlanner, higher_order::ChainedPlanner,
-    pathing::avoid_goal_wall_waypoint, wall_intercept::WallIntercept,
+    boost::GetDollar,
+    ground_drive::GroundDrive,
+    ground_intercept::{cheapest_tradeoff, BoostTradeoff, GroundIntercept},
+    ground_straight::GroundStraightPlanner,
+    ground_turn::TurnPlanner,
+    higher_order::{ChainedPlanner, FastestPlanner},
+    pathing::{avoid_goal_wall_waypoint, avoid_opponents_waypoint},
+    search::{SearchPlanner, SearchPrimitive},
+    wall_descent::WallDescent,
+    wall_intercept::WallIntercept,
 };
 
 macro_rules! guard {
@@ -21,6 +28,8 @@ mod ground_straight;
 mod ground_turn;
 mod higher_order;
 mod pathing;
+mod search;
+mod wall_descent;
 mod wall_intercept;
 mod wall_straight;
 mod wall_turn;
@@ -1,13 +1,24 @@
-use crate::routing::{
-    models::{CarState, RoutePlanner},
-    plan::{
-        ground_straight::GroundStraightPlanner, ground_turn::PathingUnawareTurnPlanner,
-        higher_order::ChainedPlanner,
+use crate::{
+    params,
+    routing::{
+        models::{CarState, RoutePlanner, SegmentPlan},
+        plan::{
+            ground_straight::GroundStraightPlanner, ground_turn::PathingUnawareTurnPlanner,
+            higher_order::ChainedPlanner,
+        },
+        segments::StraightMode,
     },
-    segments::StraightMode,
+    strategy::Game,
 };
 use common::{physics, prelude::*, rl};
-use nalgebra::Point2;
+use nalgebra::{Point2, Vector2};
+use simulate::Car1D;
+
+/// Passing an opponent closer than this risks getting bumped or demoed.
+pub const OPPONENT_AVOID_RADIUS: f32 = 300.0;
+/// Opponent predictions further out than this are too unreliable to act on.
+const OPPONENT_HORIZON: f32 = 2.0;
+const OPPONENT_SAMPLE_STEP: f32 = 0.1;
 
 /// Calculate whether driving straight to `target_loc` would intersect the goal
 /// wall. If so, return the route we should follow to get outside the goal.
@@ -34,7 +45,7 @@ pub fn avoid_plowing_into_goal_wall(
 /// embarrassing ourselves.
 #[allow(clippy::float_cmp)]
 pub fn avoid_goal_wall_waypoint(start: &CarState, target_loc: Point2<f32>) -> Option<Point2<f32>> {
-    let margin = 125.0;
+    let margin = params::GOAL_WALL_MARGIN.get();
 
     // Only proceed if we're crossing over the goalline.
     let brink = rl::FIELD_MAX_Y * start.loc.y.signum();
@@ -63,3 +74,157 @@ pub fn avoid_goal_wall_waypoint(start: &CarState, target_loc: Point2<f32>) -> Op
         None
     }
 }
+
+/// Predict where an opponent will be after `t` seconds, assuming they keep
+/// driving in the direction they're moving, as fast as they can.
+pub fn predict_opponent_loc(opponent: &CarState, t: f32) -> Point2<f32> {
+    let vel = opponent.vel_2d();
+    let speed = vel.norm();
+    if speed < 1.0 {
+        return opponent.loc_2d();
+    }
+    let mut sim = Car1D::new().with_speed(speed).with_boost(opponent.boost);
+    sim.advance(t, 1.0, true);
+    opponent.loc_2d() + vel / speed * sim.distance()
+}
+
+/// Calculate whether driving straight to `target_loc` would pass too close to
+/// an opponent. If so, return the route we should follow to go around them.
+/// `time_offset` is how far in the future `start` is.
+pub fn avoid_opponents(
+    game: &Game<'_>,
+    start: &CarState,
+    target_loc: Point2<f32>,
+    time_offset: f32,
+) -> Option<Box<dyn RoutePlanner>> {
+    let waypoint = avoid_opponents_waypoint(game, start, target_loc, time_offset)?;
+    Some(Box::new(ChainedPlanner::chain(vec![
+        Box::new(PathingUnawareTurnPlanner::new(waypoint, None)),
+        Box::new(GroundStraightPlanner::new(waypoint, StraightMode::Asap)),
+    ])))
+}
+
+/// Calculate whether driving straight to `target_loc` would pass too close to
+/// an opponent. If so, return a waypoint beside them to drive to first.
+pub fn avoid_opponents_waypoint(
+    game: &Game<'_>,
+    start: &CarState,
+    target_loc: Point2<f32>,
+    time_offset: f32,
+) -> Option<Point2<f32>> {
+    let start_loc = start.loc_2d();
+    let to_target = target_loc - start_loc;
+    let dist = to_target.norm();
+    if dist < 1.0 {
+        return None;
+    }
+    let dir = to_target / dist;
+
+    let mut sim = Car1D::new()
+        .with_speed(start.vel_2d().norm())
+        .with_boost(start.boost);
+    sim.advance_by_distance(dist, 1.0, true);
+    let duration = sim.time();
+
+    let drive = |t| {
+        let mut sim = Car1D::new()
+            .with_speed(start.vel_2d().norm())
+            .with_boost(start.boost);
+        sim.advance(t, 1.0, true);
+        start_loc + dir * sim.distance().min(dist)
+    };
+    let (our_loc, their_loc) = find_opponent_conflict(game, time_offset, duration, drive)?;
+
+    // If the opponent is going to be at the target, going around them won't
+    // help.
+    if (our_loc - target_loc).norm() < OPPONENT_AVOID_RADIUS {
+        return None;
+    }
+
+    // Pass on whichever side of the opponent is further from them, preferring
+    // the middle of the field if they're dead ahead.
+    let normal = Vector2::new(-dir.y, dir.x);
+    let side = normal.dot(&(their_loc - start_loc));
+    let side = if side.abs() >= 1.0 {
+        -side.signum()
+    } else {
+        -normal.x.signum() * their_loc.x.signum()
+    };
+    let waypoint = their_loc + normal * side * OPPONENT_AVOID_RADIUS * 2.0;
+    if !game.is_inside_field(waypoint) {
+        return None;
+    }
+    Some(waypoint)
+}
+
+/// Returns `true` if the segment passes too close to an opponent. The segment
+/// is approximated as a straight line from start to end. `time_offset` is how
+/// far in the future the segment starts.
+pub fn segment_conflicts_with_opponents(
+    game: &Game<'_>,
+    segment: &dyn SegmentPlan,
+    time_offset: f32,
+) -> bool {
+    let start_loc = segment.start().loc_2d();
+    let end_loc = segment.end().loc_2d();
+    let duration = segment.duration();
+    let path = |t: f32| {
+        let frac = if duration > 0.0 { t / duration } else { 1.0 };
+        start_loc + (end_loc - start_loc) * frac
+    };
+    find_opponent_conflict(game, time_offset, duration, path).is_some()
+}
+
+/// Step along `path` (a function from time to location) looking for a moment
+/// where an opponent is within `OPPONENT_AVOID_RADIUS`. Returns our location
+/// and theirs at that moment.
+fn find_opponent_conflict(
+    game: &Game<'_>,
+    time_offset: f32,
+    duration: f32,
+    path: impl Fn(f32) -> Point2<f32>,
+) -> Option<(Point2<f32>, Point2<f32>)> {
+    let opponents: Vec<CarState> = game
+        .cars(game.enemy_team)
+        .filter(|car| !car.Demolished)
+        .map(CarState::from)
+        .collect();
+    let mut t = 0.0;
+    while t <= duration && time_offset + t <= OPPONENT_HORIZON {
+        let our_loc = path(t);
+        for opponent in &opponents {
+            let their_loc = predict_opponent_loc(opponent, time_offset + t);
+            if (their_loc - our_loc).norm() < OPPONENT_AVOID_RADIUS {
+                return Some((our_loc, their_loc));
+            }
+        }
+        t += OPPONENT_SAMPLE_STEP;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routing::{models::CarState, plan::pathing::predict_opponent_loc};
+    use nalgebra::{Point3, UnitQuaternion, Vector3};
+
+    #[test]
+    fn opponent_prediction() {
+        let opponent = CarState {
+            loc: Point3::new(0.0, 0.0, 17.0),
+            rot: UnitQuaternion::identity(),
+            vel: Vector3::new(0.0, 1000.0, 0.0),
+            boost: 0.0,
+        };
+        let loc = predict_opponent_loc(&opponent, 1.0);
+        assert!(loc.x.abs() < 1e-3);
+        // They should cover at least the distance they would at constant speed.
+        assert!(loc.y >= 1000.0);
+
+        let parked = CarState {
+            vel: Vector3::zeros(),
+            ..opponent
+        };
+        assert!((predict_opponent_loc(&parked, 1.0).y).abs() < 1e-3);
+    }
+}
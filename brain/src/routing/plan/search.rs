@@ -0,0 +1,534 @@
+use crate::routing::{
+    models::{
+        CarState, CarState2D, PlanningContext, PlanningDump, PlanningSession, PlanningStep,
+        RoutePlan, RoutePlanError, RoutePlanner, SegmentPlan,
+    },
+    plan::{
+        ground_powerslide::GroundSimplePowerslideTurn, ground_turn::SimpleTurnPlanner, pathing,
+    },
+    recover::{IsSkidding, NotOnFlatGround},
+    segments::{Brake, Chain, ForwardDodge, JumpAndDodge, SimpleArc, Straight, StraightMode},
+    trace::PlanningTrace,
+};
+use common::{prelude::*, rl};
+use nalgebra::{Point2, Vector2};
+use nameof::name_of_type;
+use ordered_float::NotNan;
+use simulate::CarForwardDodge;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashSet},
+    f32::consts::PI,
+    time::{Duration, Instant},
+};
+
+/// A node counts as reaching the target if it ends within this distance.
+const GOAL_RADIUS: f32 = 25.0;
+/// Only drive straight at the target if the car is already facing it this
+/// precisely.
+const STRAIGHT_ANGLE_TOLERANCE: f32 = PI / 36.0;
+const SLOWEST_TURNING_SPEED: f32 = 900.0;
+
+/// The building blocks `SearchPlanner` is allowed to chain together.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SearchPrimitive {
+    Straight,
+    Turn,
+    SimpleArc,
+    PowerslideTurn,
+    ForwardDodge,
+    JumpAndDodge,
+    Brake,
+}
+
+impl SearchPrimitive {
+    pub const ALL: &'static [SearchPrimitive] = &[
+        SearchPrimitive::Straight,
+        SearchPrimitive::Turn,
+        SearchPrimitive::SimpleArc,
+        SearchPrimitive::PowerslideTurn,
+        SearchPrimitive::ForwardDodge,
+        SearchPrimitive::JumpAndDodge,
+        SearchPrimitive::Brake,
+    ];
+}
+
+/// Drive to a point on the ground by searching over sequences of segments,
+/// instead of following a fixed recipe like the other planners.
+///
+/// This is a best-first search where the cost of a node is the time elapsed so
+/// far plus an optimistic estimate of the time remaining. The search stops when
+/// no remaining node can beat the best route found, or when the time budget
+/// runs out, in which case the best route found so far is returned.
+///
+/// The search can also be run incrementally (see `RoutePlanner::incremental`),
+/// in which case `time_budget` is ignored and the caller decides how much time
+/// to spend each frame.
+#[derive(Clone)]
+pub struct SearchPlanner {
+    target_loc: Point2<f32>,
+    primitives: Vec<SearchPrimitive>,
+    max_segments: usize,
+    time_budget: Duration,
+    avoid_opponents: bool,
+}
+
+impl SearchPlanner {
+    pub fn new(target_loc: Point2<f32>) -> Self {
+        Self {
+            target_loc,
+            primitives: SearchPrimitive::ALL.to_vec(),
+            max_segments: 4,
+            time_budget: Duration::from_millis(2),
+            avoid_opponents: false,
+        }
+    }
+
+    pub fn primitives(mut self, primitives: &[SearchPrimitive]) -> Self {
+        self.primitives = primitives.to_vec();
+        self
+    }
+
+    pub fn max_segments(mut self, max_segments: usize) -> Self {
+        self.max_segments = max_segments;
+        self
+    }
+
+    pub fn time_budget(mut self, time_budget: Duration) -> Self {
+        self.time_budget = time_budget;
+        self
+    }
+
+    /// Reject segments which pass too close to where an opponent is predicted
+    /// to be.
+    pub fn avoid_opponents(mut self, avoid_opponents: bool) -> Self {
+        self.avoid_opponents = avoid_opponents;
+        self
+    }
+}
+
+struct Node {
+    segments: Vec<Box<dyn SegmentPlan>>,
+    end: CarState,
+    elapsed: f32,
+}
+
+impl RoutePlanner for SearchPlanner {
+    fn name(&self) -> &'static str {
+        name_of_type!(SearchPlanner)
+    }
+
+    fn plan(
+        &self,
+        ctx: &PlanningContext<'_, '_>,
+        dump: &mut PlanningDump<'_>,
+    ) -> Result<RoutePlan, RoutePlanError> {
+        let deadline = Instant::now() + self.time_budget;
+        let mut session = SearchSession::new(self.clone());
+        let mut plan = None;
+        loop {
+            let step = session.step(ctx, dump, deadline)?;
+            if step.improved.is_some() {
+                plan = step.improved;
+            }
+            if step.finished || Instant::now() >= deadline {
+                break;
+            }
+        }
+        plan.ok_or(RoutePlanError::OtherError("search found no route"))
+    }
+
+    fn incremental(&self) -> Option<Box<dyn PlanningSession>> {
+        Some(Box::new(SearchSession::new(self.clone())))
+    }
+
+    fn target_loc(&self) -> Option<Point2<f32>> {
+        Some(self.target_loc)
+    }
+}
+
+/// The state of a `SearchPlanner` search, which can be paused at any point and
+/// resumed on a later frame.
+pub struct SearchSession {
+    planner: SearchPlanner,
+    search: Option<Search>,
+}
+
+struct Search {
+    nodes: Vec<Node>,
+    open: BinaryHeap<(Reverse<NotNan<f32>>, usize)>,
+    visited: HashSet<(i32, i32, i32, i32)>,
+    best: Option<usize>,
+    /// The best node that has already been handed out as a plan.
+    reported: Option<usize>,
+    expanded: usize,
+}
+
+impl SearchSession {
+    fn new(planner: SearchPlanner) -> Self {
+        Self {
+            planner,
+            search: None,
+        }
+    }
+
+    fn begin(
+        &self,
+        ctx: &PlanningContext<'_, '_>,
+        dump: &mut PlanningDump<'_>,
+    ) -> Result<Search, RoutePlanError> {
+        let planner = &self.planner;
+        dump.log_start(planner, &ctx.start);
+        dump.log_pretty(planner, "target_loc", planner.target_loc);
+
+        guard!(
+            ctx.start,
+            NotOnFlatGround,
+            RoutePlanError::MustBeOnFlatGround,
+        );
+        guard!(ctx.start, IsSkidding, RoutePlanError::MustNotBeSkidding {
+            recover_target_loc: planner.target_loc,
+        });
+
+        let root = Node {
+            segments: Vec::new(),
+            end: ctx.start.clone(),
+            elapsed: 0.0,
+        };
+        let mut open = BinaryHeap::new();
+        open.push((Reverse(planner.estimate(&root)), 0));
+        Ok(Search {
+            nodes: vec![root],
+            open,
+            visited: HashSet::new(),
+            best: None,
+            reported: None,
+            expanded: 0,
+        })
+    }
+}
+
+impl PlanningSession for SearchSession {
+    fn step(
+        &mut self,
+        ctx: &PlanningContext<'_, '_>,
+        dump: &mut PlanningDump<'_>,
+        deadline: Instant,
+    ) -> Result<PlanningStep, RoutePlanError> {
+        if self.search.is_none() {
+            self.search = Some(self.begin(ctx, dump)?);
+        }
+        let planner = &self.planner;
+        let search = self.search.as_mut().unwrap();
+
+        let finished = search.run(planner, ctx, deadline);
+        if finished {
+            dump.log(planner, format!("expanded {} nodes", search.expanded));
+        } else {
+            dump.log(planner, "ran out of time");
+        }
+
+        let improved = if search.best != search.reported {
+            search.reported = search.best;
+            let best = &search.nodes[search.best.unwrap()];
+            dump.log(
+                planner,
+                format!(
+                    "best route: {} segments, {:.2}s",
+                    best.segments.len(),
+                    best.elapsed,
+                ),
+            );
+            Some(RoutePlan {
+                segment: Box::new(Chain::new(best.segments.clone())),
+                next: None,
+            })
+        } else {
+            None
+        };
+
+        if finished && search.best.is_none() {
+            return Err(RoutePlanError::OtherError("search found no route"));
+        }
+        Ok(PlanningStep { improved, finished })
+    }
+}
+
+impl Search {
+    /// Expand nodes until the search is complete or the deadline passes.
+    /// Returns true if the search is complete.
+    fn run(
+        &mut self,
+        planner: &SearchPlanner,
+        ctx: &PlanningContext<'_, '_>,
+        deadline: Instant,
+    ) -> bool {
+        while let Some((Reverse(estimate), index)) = self.open.pop() {
+            if let Some(best) = self.best {
+                if estimate.into_inner() >= self.nodes[best].elapsed {
+                    self.open.clear();
+                    return true;
+                }
+            }
+            if self.nodes[index].segments.len() >= planner.max_segments {
+                continue;
+            }
+            if !self.visited.insert(bucket(&self.nodes[index].end)) {
+                continue;
+            }
+            self.expanded += 1;
+
+            for &primitive in &planner.primitives {
+                let segment = match planner.expand(ctx, &self.nodes[index].end, primitive) {
+                    Some(s) => s,
+                    None => continue,
+                };
+                if planner.avoid_opponents
+                    && pathing::segment_conflicts_with_opponents(
+                        ctx.game,
+                        &*segment,
+                        self.nodes[index].elapsed,
+                    )
+                {
+                    continue;
+                }
+                let node = self.nodes[index].child(segment);
+                let is_goal = (node.end.loc_2d() - planner.target_loc).norm() < GOAL_RADIUS;
+                let estimate = planner.estimate(&node);
+                self.nodes.push(node);
+                let child = self.nodes.len() - 1;
+                if is_goal {
+                    let improved = match self.best {
+                        Some(b) => self.nodes[child].elapsed < self.nodes[b].elapsed,
+                        None => true,
+                    };
+                    if improved {
+                        self.best = Some(child);
+                    }
+                } else {
+                    self.open.push((Reverse(estimate), child));
+                }
+            }
+
+            if Instant::now() >= deadline {
+                return self.open.is_empty();
+            }
+        }
+        true
+    }
+}
+
+impl SearchPlanner {
+    /// The elapsed time plus a lower bound on the time needed to reach the
+    /// target.
+    fn estimate(&self, node: &Node) -> NotNan<f32> {
+        let dist = (self.target_loc - node.end.loc_2d()).norm();
+        NotNan::new(node.elapsed + dist / rl::CAR_MAX_SPEED).unwrap()
+    }
+
+    /// Try to append one segment of the given kind starting from `start`.
+    /// Returns `None` if the primitive doesn't make sense here.
+    fn expand(
+        &self,
+        ctx: &PlanningContext<'_, '_>,
+        start: &CarState,
+        primitive: SearchPrimitive,
+    ) -> Option<Box<dyn SegmentPlan>> {
+        let start_2d = start.to_2d_assume();
+        let to_target = self.target_loc - start_2d.loc;
+        let facing_error = start.forward_axis_2d().angle_to(&to_target.to_axis()).abs();
+
+        let segment: Box<dyn SegmentPlan> = match primitive {
+            SearchPrimitive::Straight => {
+                if facing_error >= STRAIGHT_ANGLE_TOLERANCE {
+                    return None;
+                }
+                Box::new(Straight::new(
+                    start_2d,
+                    self.target_loc,
+                    0.0,
+                    StraightMode::Asap,
+                    true,
+                ))
+            }
+            SearchPrimitive::Turn => {
+                sub_plan(ctx, start, &SimpleTurnPlanner::new(self.target_loc, None))?
+            }
+            SearchPrimitive::PowerslideTurn => {
+                if start.vel_2d().norm() < 1000.0 {
+                    return None;
+                }
+                sub_plan(
+                    ctx,
+                    start,
+                    &GroundSimplePowerslideTurn::new(self.target_loc),
+                )?
+            }
+            SearchPrimitive::SimpleArc => self.arc_through_target(&start_2d)?,
+            SearchPrimitive::ForwardDodge => {
+                if facing_error >= STRAIGHT_ANGLE_TOLERANCE {
+                    return None;
+                }
+                let dodge = CarForwardDodge::calc_1d(start.vel_2d().norm());
+                if dodge.end_dist >= to_target.norm() {
+                    return None;
+                }
+                Box::new(ForwardDodge::new(start.clone(), dodge))
+            }
+            SearchPrimitive::JumpAndDodge => {
+                let direction = start.forward_axis_2d().rotation_to(&to_target.to_axis());
+                Box::new(JumpAndDodge::new(start.clone(), direction))
+            }
+            SearchPrimitive::Brake => {
+                let speed = start.vel_2d().norm();
+                if speed < 500.0 {
+                    return None;
+                }
+                Box::new(Brake::new(start_2d, speed / 2.0))
+            }
+        };
+
+        // Segments which don't go anywhere would just bloat the search.
+        if segment.duration() <= 0.0 {
+            return None;
+        }
+        Some(segment)
+    }
+
+    /// An arc which starts tangent to the current velocity and passes through
+    /// the target.
+    fn arc_through_target(&self, start: &CarState2D) -> Option<Box<dyn SegmentPlan>> {
+        let to_target = self.target_loc - start.loc;
+        let vel_dir = start.vel.try_normalize(1e-3)?;
+        let normal = Vector2::new(-vel_dir.y, vel_dir.x);
+        let side = to_target.dot(&normal);
+        if side.abs() < 1.0 {
+            // Dead ahead; a straight is the better choice.
+            return None;
+        }
+
+        // Solve |start + normal * r - target| == |r| for r.
+        let radius = to_target.norm_squared() / (2.0 * side);
+        let min_radius = 1.0 / chip::max_curvature(start.vel.norm().max(SLOWEST_TURNING_SPEED));
+        if radius.abs() < min_radius {
+            return None;
+        }
+        let center = start.loc + normal * radius;
+        let arc = SimpleArc::new(
+            center,
+            radius.abs(),
+            start.loc,
+            start.vel,
+            start.boost,
+            self.target_loc,
+        )
+        .ok()?;
+        Some(Box::new(arc))
+    }
+}
+
+impl Node {
+    fn child(&self, segment: Box<dyn SegmentPlan>) -> Self {
+        let mut segments = self.segments.clone();
+        let end = segment.end();
+        let elapsed = self.elapsed + segment.duration();
+        segments.push(segment);
+        Self {
+            segments,
+            end,
+            elapsed,
+        }
+    }
+}
+
+/// Plan a single segment with an existing planner, starting from an arbitrary
+/// state. The planner's own log is discarded since the search calls it many
+/// times.
+fn sub_plan(
+    ctx: &PlanningContext<'_, '_>,
+    start: &CarState,
+    planner: &dyn RoutePlanner,
+) -> Option<Box<dyn SegmentPlan>> {
+    let ctx = PlanningContext {
+        game: ctx.game,
+        start: start.clone(),
+        ball_prediction: ctx.ball_prediction,
+    };
+    let mut log = Vec::new();
+    let mut trace = PlanningTrace::default();
+    let mut dump = PlanningDump {
+        log: &mut log,
+        trace: &mut trace,
+    };
+    planner.plan(&ctx, &mut dump).ok().map(|plan| plan.segment)
+}
+
+/// A coarse grid cell used to avoid expanding nearly identical states twice.
+fn bucket(state: &CarState) -> (i32, i32, i32, i32) {
+    let loc = state.loc_2d();
+    (
+        (loc.x / 100.0) as i32,
+        (loc.y / 100.0) as i32,
+        (state.rot_2d().angle() / (PI / 16.0)) as i32,
+        (state.vel_2d().norm() / 200.0) as i32,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routing::{
+        models::{PlanningDump, RoutePlanner},
+        plan::search::{SearchPlanner, GOAL_RADIUS},
+        test::with_fixed_context,
+        trace::PlanningTrace,
+    };
+    use nalgebra::Point2;
+    use std::time::Duration;
+
+    #[test]
+    fn turn_around() {
+        // The car starts facing upfield, so it has to turn around.
+        let target_loc = Point2::new(0.0, -4500.0);
+        let planner = SearchPlanner::new(target_loc).time_budget(Duration::from_secs(1));
+        let plan = with_fixed_context(|ctx| {
+            let mut log = Vec::new();
+            let mut trace = PlanningTrace::default();
+            let mut dump = PlanningDump {
+                log: &mut log,
+                trace: &mut trace,
+            };
+            planner.plan(ctx, &mut dump).unwrap()
+        });
+        assert!((plan.segment.end().loc_2d() - target_loc).norm() < GOAL_RADIUS);
+        assert!(plan.next.is_none());
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        integration_tests::{TestRunner, TestScenario},
+        routing::{behavior::FollowRoute, plan::search::SearchPlanner},
+    };
+    use common::prelude::*;
+    use nalgebra::{Point2, Point3, Vector3};
+
+    #[test]
+    fn drive_behind_the_car() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(3000.0, 0.0, 0.0),
+                car_loc: Point3::new(0.0, 0.0, 17.01),
+                car_vel: Vector3::new(0.0, 1000.0, 0.0),
+                ..Default::default()
+            })
+            .behavior(FollowRoute::new(SearchPlanner::new(Point2::new(
+                0.0, -1500.0,
+            ))))
+            .run_for_millis(4000);
+
+        let packet = test.sniff_packet();
+        let loc = packet.GameCars[0].Physics.loc();
+        assert!((loc.to_2d() - Point2::new(0.0, -1500.0)).norm() < 500.0);
+    }
+}
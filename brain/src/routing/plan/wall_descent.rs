@@ -0,0 +1,75 @@
+use crate::routing::{
+    models::{PlanningContext, PlanningDump, RoutePlan, RoutePlanError, RoutePlanner},
+    plan::{
+        ground_drive::GroundDrive, wall_straight::WallStraightPlanner, wall_utils::which_surface,
+    },
+    segments::WallJumpOff,
+};
+use common::{prelude::*, rl};
+use derive_new::new;
+use nalgebra::Point2;
+use nameof::name_of_type;
+use simulate::Car1D;
+
+/// Get from a wall (or the ceiling) down to a location on the floor, either by
+/// driving down through the curved transition or by jumping off, whichever is
+/// faster.
+#[derive(Clone, new)]
+pub struct WallDescent {
+    target_loc: Point2<f32>,
+}
+
+impl RoutePlanner for WallDescent {
+    fn name(&self) -> &'static str {
+        name_of_type!(WallDescent)
+    }
+
+    fn plan(
+        &self,
+        ctx: &PlanningContext<'_, '_>,
+        dump: &mut PlanningDump<'_>,
+    ) -> Result<RoutePlan, RoutePlanError> {
+        dump.log_start(self, &ctx.start);
+        dump.log_pretty(self, "target_loc", self.target_loc);
+
+        let surface = which_surface(ctx.game.pitch(), &ctx.start.loc)
+            .map_err(|_| RoutePlanError::CannotOperateWall)?;
+        if surface.normal.z > 0.999 {
+            dump.log(self, "already on the floor");
+            return GroundDrive::new(self.target_loc).plan(ctx, dump);
+        }
+
+        let drive = WallStraightPlanner::new(self.target_loc.to_3d(rl::OCTANE_NEUTRAL_Z))
+            .plan(ctx, dump)
+            .ok();
+        let jump = WallJumpOff::new(ctx.start.clone(), surface);
+
+        let drive_time = drive.as_ref().map(|p| p.segment.duration());
+        let jump_time = jump.as_ref().map(|j| {
+            // After landing, assume a blitz straight to the target.
+            let end = j.end();
+            let dist = (self.target_loc - end.loc.to_2d()).norm();
+            let mut sim = Car1D::new()
+                .with_speed(end.vel.norm())
+                .with_boost(end.boost);
+            if dist > 0.0 {
+                sim.advance_by_distance(dist, 1.0, true);
+            }
+            j.duration() + sim.time()
+        });
+        dump.log(
+            self,
+            format!("drive = {:?}, jump = {:?}", drive_time, jump_time),
+        );
+
+        match (drive, jump) {
+            (Some(drive), None) => Ok(drive),
+            (Some(drive), Some(_)) if drive_time <= jump_time => Ok(drive),
+            (_, Some(jump)) => Ok(RoutePlan {
+                segment: Box::new(jump),
+                next: Some(Box::new(GroundDrive::new(self.target_loc))),
+            }),
+            (None, None) => Err(RoutePlanError::CannotOperateWall),
+        }
+    }
+}
@@ -7,8 +7,9 @@ use crate::{
             RoutePlanner,
         },
         plan::{
-            higher_order::ChainedPlanner, wall_straight::WallStraightPlanner,
-            wall_turn::WallTurnPlanner,
+            higher_order::ChainedPlanner, wall_descent::WallDescent,
+            wall_straight::WallStraightPlanner, wall_turn::WallTurnPlanner,
+            wall_utils::which_surface,
         },
     },
     strategy::{Context2, Game, Pitch},
@@ -127,12 +128,18 @@ impl WallIntercept {
             Err(reason) => return Err((Skip::No, Some(reason))),
         }
 
-        let turn = WallTurnPlanner::new(ball.loc).maximum_turn_angle(PI * 0.75);
-        let straight = WallStraightPlanner::new(ball.loc);
-        let planner = ChainedPlanner::chain(vec_box![turn, straight]);
+        let planner: Box<dyn RoutePlanner> = if Self::is_descent(ctx, ball.loc) {
+            // We're on a wall and the ball is on the floor. Get down first, then go for
+            // the ball.
+            Box::new(WallDescent::new(ball.loc.to_2d()))
+        } else {
+            let turn = WallTurnPlanner::new(ball.loc).maximum_turn_angle(PI * 0.75);
+            let straight = WallStraightPlanner::new(ball.loc);
+            Box::new(ChainedPlanner::chain(vec_box![turn, straight]))
+        };
 
-        let plan = match PlanningContext::plan_2(&planner, ctx) {
-            Ok((plan, _log)) => plan,
+        let plan = match PlanningContext::plan_2(&*planner, ctx) {
+            Ok((plan, _log, _trace)) => plan,
             Err(_) => return Err((Skip::Yes, None)),
         };
         let tail = match plan.provisional_expand_2(ctx.game, ctx.ball_prediction) {
@@ -148,6 +155,15 @@ impl WallIntercept {
         Ok(plan)
     }
 
+    fn is_descent(ctx: &PlanningContext<'_, '_>, intercept_loc: Point3<f32>) -> bool {
+        let pitch = ctx.game.pitch();
+        let on_wall = match which_surface(pitch, &ctx.start.loc) {
+            Ok(plane) => plane.normal.z < 0.999,
+            Err(()) => false,
+        };
+        on_wall && pitch.closest_plane(&intercept_loc).normal.z > 0.999
+    }
+
     fn eligible_wall<'pitch>(
         &self,
         pitch: &'pitch Pitch,
@@ -1,7 +1,7 @@
 use crate::{
     routing::{
         models::{PlanningContext, PlanningDump, RoutePlan, RoutePlanError, RoutePlanner},
-        plan::wall_utils::which_surface,
+        plan::wall_utils::{surface_path, which_surface},
         segments::WallStraight,
     },
     utils::geometry::flattener::Flattener,
@@ -40,12 +40,22 @@ impl RoutePlanner for WallStraightPlanner {
         let start_to_2d = Flattener::new(start_to_ground);
         let target_to_2d = Flattener::new(start_to_ground * target_to_start);
 
+        let flat_dist = (target_to_2d * self.target_loc - start_to_2d * ctx.start.loc).norm();
+        let surface_path = surface_path(
+            start_surface,
+            ctx.start.loc,
+            target_surface,
+            self.target_loc,
+            flat_dist,
+        );
         let segment = WallStraight::new(
             ctx.start.clone(),
             self.target_loc,
             start_to_2d,
             target_to_2d,
-        );
+            &surface_path,
+        )
+        .map_err(|_| RoutePlanError::CannotOperateWall)?;
         Ok(RoutePlan {
             segment: Box::new(segment),
             next: None,
@@ -1,5 +1,6 @@
 use crate::{strategy::Pitch, utils::geometry::Plane};
 use nalgebra::Point3;
+use simulate::{SurfacePath, SurfaceSample, CORNER_RADIUS};
 
 const SURFACE_DIST_THRESHOLD: f32 = 500.0;
 
@@ -10,3 +11,111 @@ pub fn which_surface<'p>(pitch: &'p Pitch, loc: &Point3<f32>) -> Result<&'p Plan
     }
     Ok(wall)
 }
+
+/// How much shorter it is to drive through the rounded corner between two
+/// surfaces than over the sharp seam that `Plane::unfold` assumes.
+pub fn corner_shortcut(a: &Plane, b: &Plane) -> f32 {
+    let angle = a.normal.angle(&b.normal);
+    CORNER_RADIUS * (2.0 * (angle / 2.0).tan() - angle)
+}
+
+/// Describe the surfaces along a straight (once unfolded) drive from
+/// `start_loc` to `target_loc`, for use with `CarSurfaceDrive`. The rounded
+/// corner between the surfaces cuts the seam short, so the path can be up to
+/// `corner_shortcut` shorter than `flat_dist`.
+pub fn surface_path(
+    start_surface: &Plane,
+    start_loc: Point3<f32>,
+    target_surface: &Plane,
+    target_loc: Point3<f32>,
+    flat_dist: f32,
+) -> SurfacePath {
+    let sample = |plane: &Plane, dz: f32, dist: f32| SurfaceSample {
+        slope: if dist > 0.0 {
+            (dz / dist).max(-1.0).min(1.0)
+        } else {
+            0.0
+        },
+        normal_z: plane.normal.z,
+    };
+
+    if start_surface.normal.dot(&target_surface.normal) > 0.999 {
+        let surface = sample(start_surface, target_loc.z - start_loc.z, flat_dist);
+        return SurfacePath::new().flat(flat_dist, surface);
+    }
+
+    // Split the drive at the seam. After unfolding, the two legs are in proportion
+    // to each endpoint's distance from the other surface.
+    let start_leg = target_surface.distance_to_point(&start_loc).max(0.0);
+    let target_leg = start_surface.distance_to_point(&target_loc).max(0.0);
+    let split = start_leg / (start_leg + target_leg).max(1.0);
+    let start_dist = flat_dist * split;
+    let target_dist = flat_dist - start_dist;
+
+    // If either surface is the floor or ceiling, the seam is at that height.
+    // Otherwise it is a seam between two walls, and the climb is spread evenly.
+    let seam_z = if start_surface.normal.z.abs() > 0.999 {
+        start_loc.z
+    } else if target_surface.normal.z.abs() > 0.999 {
+        target_loc.z
+    } else {
+        start_loc.z + (target_loc.z - start_loc.z) * split
+    };
+    let start_sample = sample(start_surface, seam_z - start_loc.z, start_dist);
+    let target_sample = sample(target_surface, target_loc.z - seam_z, target_dist);
+
+    // The curved corner straddles the seam. It starts and ends a tangent length
+    // away from the seam, and the arc between those points is shorter than the
+    // two legs it replaces. If an endpoint is within the corner, only part of
+    // the arc is driven.
+    let corner_angle = start_surface.normal.angle(&target_surface.normal);
+    let tangent = CORNER_RADIUS * (corner_angle / 2.0).tan();
+    let start_corner = tangent.min(start_dist);
+    let target_corner = tangent.min(target_dist);
+    let arc =
+        CORNER_RADIUS * corner_angle * (start_corner + target_corner) / (2.0 * tangent).max(1.0);
+
+    SurfacePath::new()
+        .flat(start_dist - start_corner, start_sample)
+        .transition(arc, start_sample, target_sample)
+        .flat(target_dist - target_corner, target_sample)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        routing::plan::wall_utils::{corner_shortcut, surface_path},
+        strategy::DFH_STADIUM,
+    };
+    use common::rl;
+    use nalgebra::Point3;
+
+    #[test]
+    fn floor_to_side_wall() {
+        let floor = DFH_STADIUM.ground();
+        let wall = DFH_STADIUM.closest_plane(&Point3::new(rl::FIELD_MAX_X, 0.0, 1000.0));
+        let shortcut = corner_shortcut(floor, wall);
+        assert!(shortcut > 0.0 && shortcut < 200.0);
+
+        let start = Point3::new(rl::FIELD_MAX_X - 2000.0, 0.0, 0.0);
+        let target = Point3::new(rl::FIELD_MAX_X, 0.0, 1000.0);
+        let path = surface_path(floor, start, wall, target, 3000.0);
+        assert!((path.length() - (3000.0 - shortcut)).abs() < 1.0);
+        assert!(path.sample(0.0).normal_z > 0.999);
+        assert!(path.sample(path.length()).slope > 0.999);
+    }
+
+    #[test]
+    fn same_surface_has_no_corner() {
+        let floor = DFH_STADIUM.ground();
+        assert!(corner_shortcut(floor, floor).abs() < 1e-3);
+        let path = surface_path(
+            floor,
+            Point3::origin(),
+            floor,
+            Point3::new(1000.0, 0.0, 0.0),
+            1000.0,
+        );
+        assert!((path.length() - 1000.0).abs() < 1e-3);
+    }
+}
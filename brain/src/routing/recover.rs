@@ -5,13 +5,14 @@ use crate::{
         offense::ResetBehindBall,
     },
     helpers::ball::BallTrajectory,
+    params,
     routing::{
         behavior::FollowRoute,
         models::{CarState, RoutePlanError},
         plan::GroundDrive,
         StraightMode,
     },
-    strategy::{Behavior, Context, Priority, Scenario},
+    strategy::{Behavior, Context, Priority},
 };
 use common::{physics::car_forward_axis, prelude::*};
 use derive_new::new;
@@ -187,7 +188,7 @@ impl Predicate for WeDontWinTheRace {
     }
 
     fn evaluate(&mut self, ctx: &mut Context<'_>) -> bool {
-        ctx.scenario.possession() < Scenario::POSSESSION_CONTESTABLE
+        ctx.scenario.possession() < params::POSSESSION_CONTESTABLE.get()
     }
 }
 
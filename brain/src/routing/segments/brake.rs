@@ -1,14 +1,18 @@
 use crate::{
     behavior::movement::GetToFlatGround,
     eeg::{color, Drawable},
-    routing::models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
+    routing::{
+        models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
+        snapshot::SegmentSnapshot,
+    },
     strategy::Context,
 };
 use common::{prelude::*, rl};
 use derive_new::new;
 use nameof::name_of_type;
+use serde_derive::{Deserialize, Serialize};
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Brake {
     start: CarState2D,
     target_speed: f32,
@@ -76,6 +80,10 @@ impl SegmentPlan for Brake {
             color::RED,
         ));
     }
+
+    fn snapshot(&self) -> SegmentSnapshot {
+        SegmentSnapshot::Brake(self.clone())
+    }
 }
 
 #[derive(new)]
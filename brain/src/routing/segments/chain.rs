@@ -1,5 +1,8 @@
 use crate::{
-    routing::models::{CarState, SegmentPlan, SegmentRunAction, SegmentRunner},
+    routing::{
+        models::{CarState, SegmentPlan, SegmentRunAction, SegmentRunner},
+        snapshot::SegmentSnapshot,
+    },
     strategy::Context,
 };
 use derive_new::new;
@@ -39,6 +42,10 @@ impl SegmentPlan for Chain {
             segment.draw(ctx);
         }
     }
+
+    fn snapshot(&self) -> SegmentSnapshot {
+        SegmentSnapshot::Chain(self.segments.iter().map(|s| s.snapshot()).collect())
+    }
 }
 
 #[derive(new)]
@@ -56,6 +63,7 @@ impl SegmentRunner for Chainer {
             SegmentRunAction::Yield(i) => return SegmentRunAction::Yield(i),
             SegmentRunAction::Success => true,
             SegmentRunAction::Failure => return SegmentRunAction::Failure,
+            SegmentRunAction::Deviated(d) => return SegmentRunAction::Deviated(d),
         };
 
         self.segments.pop_front().unwrap();
@@ -4,15 +4,19 @@ use crate::{
         movement::{Dodge, Yielder},
     },
     eeg::{color, Drawable},
-    routing::models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
+    routing::{
+        models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
+        snapshot::SegmentSnapshot,
+    },
     strategy::{Action, Behavior, Context, Priority},
 };
 use common::prelude::*;
 use derive_new::new;
 use nameof::name_of_type;
+use serde_derive::{Deserialize, Serialize};
 use simulate::CarForwardDodge1D;
 
-#[derive(Clone, new)]
+#[derive(Clone, new, Serialize, Deserialize)]
 pub struct ForwardDodge {
     start: CarState,
     dodge: CarForwardDodge1D,
@@ -57,6 +61,10 @@ impl SegmentPlan for ForwardDodge {
             color::GREEN,
         ));
     }
+
+    fn snapshot(&self) -> SegmentSnapshot {
+        SegmentSnapshot::ForwardDodge(self.clone())
+    }
 }
 
 struct ForwardDodgeRunner {
@@ -105,8 +113,11 @@ impl SegmentRunner for ForwardDodgeRunner {
             Action::Yield(i) => SegmentRunAction::Yield(i),
             Action::TailCall(_) => panic!("TailCall not yet supported in SegmentRunner"),
             Action::RootCall(_) => SegmentRunAction::Failure,
-            Action::Return => SegmentRunAction::Success,
-            Action::Abort => SegmentRunAction::Failure,
+            Action::Return(_) => SegmentRunAction::Success,
+            Action::Abort(reason) => {
+                ctx.eeg.log(self.name(), reason);
+                SegmentRunAction::Failure
+            }
         }
     }
 }
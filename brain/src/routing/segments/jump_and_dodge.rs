@@ -1,20 +1,24 @@
 use crate::{
     behavior::{higher_order::Chain, movement::Yielder},
     eeg::{color, Drawable},
-    routing::models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
+    routing::{
+        models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
+        snapshot::SegmentSnapshot,
+    },
     strategy::{Action, Behavior, Context, Priority},
 };
 use common::prelude::*;
 use derive_new::new;
 use nalgebra::UnitComplex;
 use nameof::name_of_type;
+use serde_derive::{Deserialize, Serialize};
 
 const JUMP_TIME: f32 = 6.0 / 120.0;
 const WAIT_TIME: f32 = 6.0 / 120.0;
 const FLOAT_TIME: f32 = 1.333333;
 const DODGE_IMPULSE: f32 = 500.0; // This is inaccurate (but it's an exact minimum bound).
 
-#[derive(Clone, new)]
+#[derive(Clone, new, Serialize, Deserialize)]
 pub struct JumpAndDodge {
     start: CarState,
     direction: UnitComplex<f32>,
@@ -63,6 +67,10 @@ impl SegmentPlan for JumpAndDodge {
             color::GREEN,
         ));
     }
+
+    fn snapshot(&self) -> SegmentSnapshot {
+        SegmentSnapshot::JumpAndDodge(self.clone())
+    }
 }
 
 struct JumpAndDodgeRunner {
@@ -115,8 +123,11 @@ impl SegmentRunner for JumpAndDodgeRunner {
             Action::Yield(i) => SegmentRunAction::Yield(i),
             Action::TailCall(_) => panic!("TailCall not yet supported in SegmentRunner"),
             Action::RootCall(_) => SegmentRunAction::Failure,
-            Action::Return => SegmentRunAction::Success,
-            Action::Abort => SegmentRunAction::Failure,
+            Action::Return(_) => SegmentRunAction::Success,
+            Action::Abort(reason) => {
+                ctx.eeg.log(self.name(), reason);
+                SegmentRunAction::Failure
+            }
         }
     }
 }
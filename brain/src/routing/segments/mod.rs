@@ -6,8 +6,10 @@ pub use crate::routing::segments::{
     null::NullSegment,
     powerslide_turn::PowerslideTurn,
     simple_arc::SimpleArc,
+    speed_flip::SpeedFlip,
     straight::{Straight, StraightMode},
     turn::Turn,
+    wall_jump_off::WallJumpOff,
     wall_straight::WallStraight,
     wall_turn::WallTurn,
 };
@@ -19,7 +21,9 @@ mod jump_and_dodge;
 mod null;
 mod powerslide_turn;
 mod simple_arc;
+mod speed_flip;
 mod straight;
 mod turn;
+mod wall_jump_off;
 mod wall_straight;
 mod wall_turn;
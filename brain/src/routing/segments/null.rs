@@ -1,11 +1,15 @@
 use crate::{
-    routing::models::{CarState, SegmentPlan, SegmentRunAction, SegmentRunner},
+    routing::{
+        models::{CarState, SegmentPlan, SegmentRunAction, SegmentRunner},
+        snapshot::SegmentSnapshot,
+    },
     strategy::Context,
 };
 use derive_new::new;
 use nameof::name_of_type;
+use serde_derive::{Deserialize, Serialize};
 
-#[derive(Clone, new)]
+#[derive(Clone, new, Serialize, Deserialize)]
 pub struct NullSegment {
     start: CarState,
 }
@@ -32,6 +36,10 @@ impl SegmentPlan for NullSegment {
     }
 
     fn draw(&self, _ctx: &mut Context<'_>) {}
+
+    fn snapshot(&self) -> SegmentSnapshot {
+        SegmentSnapshot::NullSegment(self.clone())
+    }
 }
 
 #[derive(new)]
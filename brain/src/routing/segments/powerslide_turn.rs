@@ -1,14 +1,18 @@
 use crate::{
     behavior::movement::GetToFlatGround,
     eeg::{color, Drawable},
-    routing::models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
+    routing::{
+        models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
+        snapshot::SegmentSnapshot,
+    },
     strategy::Context,
 };
 use nalgebra::Vector2;
 use nameof::name_of_type;
+use serde_derive::{Deserialize, Serialize};
 use simulate::CarPowerslideTurnBlueprint;
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct PowerslideTurn {
     blueprint: CarPowerslideTurnBlueprint,
     boost: f32,
@@ -63,6 +67,10 @@ impl SegmentPlan for PowerslideTurn {
             color::RED,
         ));
     }
+
+    fn snapshot(&self) -> SegmentSnapshot {
+        SegmentSnapshot::PowerslideTurn(self.clone())
+    }
 }
 
 struct PowerslideTurnRunner {
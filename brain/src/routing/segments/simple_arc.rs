@@ -1,15 +1,22 @@
 use crate::{
     behavior::movement::GetToFlatGround,
     eeg::{color, Drawable},
-    routing::models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
+    routing::{
+        models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
+        snapshot::SegmentSnapshot,
+        tracking::{
+            tracking_steer, tracking_throttle, Deviation, TrackingError, TrackingTolerance,
+        },
+    },
     strategy::Context,
 };
 use common::{physics::CAR_LOCAL_FORWARD_AXIS_2D, prelude::*};
 use nalgebra::{Point2, Unit, UnitComplex, Vector2};
 use nameof::name_of_type;
+use serde_derive::{Deserialize, Serialize};
 use std::f32::consts::PI;
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct SimpleArc {
     center: Point2<f32>,
     radius: f32,
@@ -159,21 +166,29 @@ impl SegmentPlan for SimpleArc {
             color::YELLOW,
         ));
     }
+
+    fn snapshot(&self) -> SegmentSnapshot {
+        SegmentSnapshot::SimpleArc(self.clone())
+    }
 }
 
 struct SimpleArcRunner {
     plan: SimpleArc,
+    start_time: Option<f32>,
 }
 
 impl SimpleArcRunner {
     fn new(plan: SimpleArc) -> Self {
-        Self { plan }
+        Self {
+            plan,
+            start_time: None,
+        }
     }
 
-    fn calculate_ahead_loc(&self, loc: Point2<f32>, angle: f32) -> Point2<f32> {
+    /// The direction of travel along the arc at the point nearest `loc`.
+    fn tangent_at(&self, loc: Point2<f32>) -> Unit<Vector2<f32>> {
         let center_to_loc = loc - self.plan.center;
-        let center_to_ahead = self.plan.sweep_by_angle(angle) * center_to_loc;
-        self.plan.center + center_to_ahead.normalize() * self.plan.radius
+        UnitComplex::new(self.plan.sweep_by_angle(PI / 2.0)) * center_to_loc.to_axis()
     }
 }
 
@@ -183,6 +198,9 @@ impl SegmentRunner for SimpleArcRunner {
     }
 
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> SegmentRunAction {
+        let now = ctx.packet.GameInfo.TimeSeconds;
+        let elapsed = now - *self.start_time.get_or_insert(now);
+
         let me = ctx.me();
         let car_loc = me.Physics.loc_2d();
         let car_forward_axis = me.Physics.forward_axis_2d();
@@ -198,17 +216,27 @@ impl SegmentRunner for SimpleArcRunner {
             return SegmentRunAction::Success;
         }
 
-        let target_loc = self.calculate_ahead_loc(car_loc, 15.0_f32.to_radians());
+        // The plan assumes a constant speed all the way around.
+        let planned_time = self.plan.radius * swept.abs() / self.plan.start_vel.norm();
+        let path_loc =
+            self.plan.center + (car_loc - self.plan.center).normalize() * self.plan.radius;
+        let path_dir = self.tangent_at(car_loc);
+        let error = TrackingError::measure(car_loc, path_loc, path_dir, elapsed - planned_time);
+        if TrackingTolerance::DEFAULT.is_exceeded_by(&error) {
+            return SegmentRunAction::Deviated(Deviation {
+                segment: self.name().to_string(),
+                error,
+            });
+        }
 
         ctx.eeg
-            .draw(Drawable::ghost_car_ground(target_loc, me.Physics.rot()));
+            .draw(Drawable::ghost_car_ground(path_loc, me.Physics.rot()));
 
-        let angle = car_forward_axis
-            .into_inner()
-            .angle_to(&(target_loc - car_loc));
+        let heading_error = car_forward_axis.angle_to(&path_dir);
+        let speed = me.Physics.vel_2d().norm();
         SegmentRunAction::Yield(common::halfway_house::PlayerInput {
-            Throttle: 1.0,
-            Steer: angle.max(-1.0).min(1.0),
+            Throttle: tracking_throttle(error.timing),
+            Steer: tracking_steer(heading_error, error.cross_track, speed),
             ..Default::default()
         })
     }
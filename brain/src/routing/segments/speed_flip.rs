@@ -0,0 +1,118 @@
+use crate::{
+    behavior::movement,
+    eeg::{color, Drawable},
+    routing::{
+        models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
+        snapshot::SegmentSnapshot,
+    },
+    strategy::{Action, Behavior, Context},
+};
+use common::prelude::*;
+use derive_new::new;
+use nameof::name_of_type;
+use serde_derive::{Deserialize, Serialize};
+use simulate::CarSpeedFlip1D;
+
+#[derive(Clone, new, Serialize, Deserialize)]
+pub struct SpeedFlip {
+    start: CarState,
+    flip: CarSpeedFlip1D,
+}
+
+impl SegmentPlan for SpeedFlip {
+    fn name(&self) -> &str {
+        name_of_type!(SpeedFlip)
+    }
+
+    fn start(&self) -> CarState {
+        self.start.clone()
+    }
+
+    fn end(&self) -> CarState {
+        assert!((self.start.vel.norm() - self.flip.start_speed).abs() < 1.0);
+        assert!(self.flip.end_speed >= self.flip.start_speed);
+        let forward_axis = self.start.forward_axis_2d().into_inner();
+        CarState2D {
+            loc: self.start.loc.to_2d() + forward_axis * self.flip.end_dist,
+            rot: self.start.rot.to_2d(),
+            vel: forward_axis * self.flip.end_speed,
+            boost: self.flip.end_boost,
+        }
+        .to_3d()
+    }
+
+    fn duration(&self) -> f32 {
+        self.flip.duration()
+    }
+
+    fn run(&self) -> Box<dyn SegmentRunner> {
+        Box::new(SpeedFlipRunner::new())
+    }
+
+    fn draw(&self, ctx: &mut Context<'_>) {
+        ctx.eeg.draw(Drawable::Line(
+            self.start.loc.to_2d(),
+            self.end().loc.to_2d(),
+            color::GREEN,
+        ));
+    }
+
+    fn snapshot(&self) -> SegmentSnapshot {
+        SegmentSnapshot::SpeedFlip(self.clone())
+    }
+}
+
+struct SpeedFlipRunner {
+    behavior: movement::SpeedFlip,
+}
+
+impl SpeedFlipRunner {
+    pub fn new() -> Self {
+        Self {
+            behavior: movement::SpeedFlip::new(),
+        }
+    }
+}
+
+impl SegmentRunner for SpeedFlipRunner {
+    fn name(&self) -> &str {
+        name_of_type!(SpeedFlipRunner)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> SegmentRunAction {
+        match self.behavior.execute_old(ctx) {
+            Action::Yield(i) => SegmentRunAction::Yield(i),
+            Action::TailCall(_) => panic!("TailCall not yet supported in SegmentRunner"),
+            Action::RootCall(_) => SegmentRunAction::Failure,
+            Action::Return(_) => SegmentRunAction::Success,
+            Action::Abort(reason) => {
+                ctx.eeg.log(self.name(), reason);
+                SegmentRunAction::Failure
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::{Point2, UnitComplex, Vector2};
+    use simulate::CarSpeedFlip;
+
+    #[test]
+    fn zero_vel() {
+        let start = CarState2D {
+            loc: Point2::origin(),
+            rot: UnitComplex::identity(),
+            vel: Vector2::zeros(),
+            boost: 100.0,
+        }
+        .to_3d();
+        let flip = CarSpeedFlip::calc_1d(0.0, 100.0).unwrap();
+        let segment = SpeedFlip::new(start, flip);
+        let end = segment.end();
+        assert!(end.loc.x >= 400.0);
+        assert!(end.vel.x >= 1000.0);
+        assert!(end.boost < 100.0);
+    }
+}
@@ -1,15 +1,20 @@
 use crate::{
-    behavior::movement::{simple_steer_towards, GetToFlatGround},
+    behavior::movement::GetToFlatGround,
     eeg::{color, Drawable},
-    routing::models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
+    routing::{
+        models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
+        snapshot::SegmentSnapshot,
+        tracking::{tracking_steer, Deviation, TrackingError, TrackingTolerance},
+    },
     strategy::Context,
 };
 use common::{prelude::*, rl};
 use nalgebra::{Point2, Vector2};
 use nameof::name_of_type;
+use serde_derive::{Deserialize, Serialize};
 use simulate::Car1D;
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Straight {
     start: CarState2D,
     end_loc: Point2<f32>,
@@ -18,6 +23,8 @@ pub struct Straight {
     duration: f32,
     mode: StraightMode,
     allow_boost: bool,
+    /// Never boost below this amount.
+    boost_reserve: f32,
 }
 
 /// This is a workaround for the lack of "arrive-at-time" behavior.
@@ -25,7 +32,7 @@ pub struct Straight {
 /// Most behaviors are able to control approach speed on a straightaway. If a
 /// straight segment is followed by one of those behaviors, it's easier to just
 /// defer to the behavior.
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Serialize, Deserialize)]
 pub enum StraightMode {
     /// Run the segment as fast as possible.
     Asap,
@@ -40,15 +47,28 @@ impl Straight {
         end_chop: f32,
         mode: StraightMode,
         allow_boost: bool,
+    ) -> Self {
+        Self::with_boost_reserve(start, end_loc, end_chop, mode, allow_boost, 0.0)
+    }
+
+    /// Like `new`, but stop boosting once the car is down to `boost_reserve`.
+    pub fn with_boost_reserve(
+        start: CarState2D,
+        end_loc: Point2<f32>,
+        end_chop: f32,
+        mode: StraightMode,
+        allow_boost: bool,
+        boost_reserve: f32,
     ) -> Self {
         let start_to_end_dist = (end_loc - start.loc).norm();
         if start_to_end_dist < 0.1 {
             return Self::zero(start);
         }
 
+        let reserved = boost_reserve.max(0.0).min(start.boost);
         let mut sim = Car1D::new()
             .with_speed(start.vel.norm())
-            .with_boost(start.boost);
+            .with_boost(start.boost - reserved);
         sim.advance_by_distance(start_to_end_dist, 1.0, allow_boost);
 
         // end_chop is the caller requesting we end the segment before reaching the
@@ -57,13 +77,13 @@ impl Straight {
             let duration = (sim.time() - end_chop).max(0.0);
             sim = Car1D::new()
                 .with_speed(start.vel.norm())
-                .with_boost(start.boost);
+                .with_boost(start.boost - reserved);
             sim.advance(duration, 1.0, allow_boost);
         }
 
         let sim_end_loc = sim.distance();
         let sim_end_speed = sim.speed();
-        let sim_end_boost = sim.boost();
+        let sim_end_boost = sim.boost() + reserved;
 
         let end_loc = start.loc + (end_loc - start.loc).normalize() * sim_end_loc;
         if (end_loc - start.loc).norm() < 1.0 {
@@ -79,6 +99,7 @@ impl Straight {
             duration: sim.time(),
             mode,
             allow_boost,
+            boost_reserve: reserved,
         }
     }
 
@@ -91,6 +112,7 @@ impl Straight {
             duration: 0.0,
             mode: StraightMode::Fake,
             allow_boost: true,
+            boost_reserve: 0.0,
         }
     }
 }
@@ -128,15 +150,32 @@ impl SegmentPlan for Straight {
         ctx.eeg
             .draw(Drawable::Line(self.start.loc, self.end_loc, color::YELLOW));
     }
+
+    fn snapshot(&self) -> SegmentSnapshot {
+        SegmentSnapshot::Straight(self.clone())
+    }
 }
 
 struct StraightRunner {
     plan: Straight,
+    start_time: Option<f32>,
 }
 
 impl StraightRunner {
     pub fn new(plan: Straight) -> Self {
-        StraightRunner { plan }
+        StraightRunner {
+            plan,
+            start_time: None,
+        }
+    }
+
+    /// The time the plan expects the car to reach `dist` along the line.
+    fn planned_time(&self, dist: f32) -> f32 {
+        let mut sim = Car1D::new()
+            .with_speed(self.plan.start.vel.norm())
+            .with_boost(self.plan.start.boost - self.plan.boost_reserve);
+        sim.advance_by_distance(dist.max(0.0), 1.0, self.plan.allow_boost);
+        sim.time()
     }
 }
 
@@ -154,10 +193,14 @@ impl SegmentRunner for StraightRunner {
             StraightMode::Asap => {} // continued below :)
         }
 
+        let now = ctx.packet.GameInfo.TimeSeconds;
+        let elapsed = now - *self.start_time.get_or_insert(now);
+
         let me = ctx.me();
         let me_loc = me.Physics.loc_2d();
         let start_to_end = self.plan.end_loc - self.plan.start.loc;
-        let cur_dist = (me_loc - self.plan.start.loc).dot(&start_to_end.normalize());
+        let path_dir = start_to_end.to_axis();
+        let cur_dist = (me_loc - self.plan.start.loc).dot(&path_dir.into_inner());
 
         if cur_dist >= start_to_end.norm() {
             return SegmentRunAction::Success;
@@ -168,20 +211,58 @@ impl SegmentRunner for StraightRunner {
             return SegmentRunAction::Failure;
         }
 
-        // Drive to a point slightly in front of us, so we "hug the line" and get back
-        // on course quicker in case of any inaccuracies.
-        let target_loc = self.plan.start.loc + start_to_end.normalize() * (cur_dist + 500.0);
+        let path_loc = self.plan.start.loc + path_dir.into_inner() * cur_dist;
+        let error = TrackingError::measure(
+            me_loc,
+            path_loc,
+            path_dir,
+            elapsed - self.planned_time(cur_dist),
+        );
+        if TrackingTolerance::DEFAULT.is_exceeded_by(&error) {
+            return SegmentRunAction::Deviated(Deviation {
+                segment: self.name().to_string(),
+                error,
+            });
+        }
 
         ctx.eeg
-            .draw(Drawable::ghost_car_ground(target_loc, me.Physics.rot()));
+            .draw(Drawable::ghost_car_ground(path_loc, me.Physics.rot()));
 
+        let heading_error = me.Physics.forward_axis_2d().angle_to(&path_dir);
+        let speed = me.Physics.vel_2d().norm();
         SegmentRunAction::Yield(common::halfway_house::PlayerInput {
             Throttle: 1.0,
-            Steer: simple_steer_towards(&me.Physics, target_loc),
+            Steer: tracking_steer(heading_error, error.cross_track, speed),
             Boost: self.plan.allow_boost
                 && me.Physics.vel().norm() < rl::CAR_ALMOST_MAX_SPEED
-                && me.Boost > 0,
+                && me.Boost as f32 > self.plan.boost_reserve,
             ..Default::default()
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::routing::{
+        models::{CarState2D, SegmentPlan},
+        segments::{Straight, StraightMode},
+    };
+    use nalgebra::{Point2, UnitComplex, Vector2};
+
+    #[test]
+    fn boost_reserve() {
+        let start = CarState2D {
+            loc: Point2::origin(),
+            rot: UnitComplex::identity(),
+            vel: Vector2::new(500.0, 0.0),
+            boost: 50.0,
+        };
+        let target = Point2::new(4000.0, 0.0);
+        let all_in = Straight::new(start.clone(), target, 0.0, StraightMode::Asap, true);
+        let frugal =
+            Straight::with_boost_reserve(start, target, 0.0, StraightMode::Asap, true, 40.0);
+        assert!(all_in.end().boost < 40.0);
+        assert!(frugal.end().boost >= 40.0);
+        assert!(frugal.duration() > all_in.duration());
+    }
+}
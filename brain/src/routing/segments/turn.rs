@@ -1,15 +1,28 @@
 use crate::{
     behavior::movement::GetToFlatGround,
     eeg::{color, Drawable},
-    routing::models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
+    routing::{
+        models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
+        snapshot::SegmentSnapshot,
+        tracking::{tracking_steer, Deviation, TrackingError, TrackingTolerance},
+    },
     strategy::Context,
 };
 use common::prelude::*;
 use nalgebra::{Point2, UnitComplex, Vector2};
 use nameof::name_of_type;
+use serde_derive::{Deserialize, Serialize};
 use std::f32::consts::PI;
 
-#[derive(Clone)]
+/// The planned radius is only approximate since the car's speed (and therefore
+/// its turning radius) changes during the turn, and the planned duration is a
+/// rough guess, so only give up if things go badly wrong.
+const TRACKING_TOLERANCE: TrackingTolerance = TrackingTolerance {
+    cross_track: 300.0,
+    timing: std::f32::INFINITY,
+};
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Turn {
     start: CarState2D,
     target_loc: Point2<f32>,
@@ -98,6 +111,10 @@ impl SegmentPlan for Turn {
             color::YELLOW,
         ));
     }
+
+    fn snapshot(&self) -> SegmentSnapshot {
+        SegmentSnapshot::Turn(self.clone())
+    }
 }
 
 struct Turner {
@@ -146,9 +163,27 @@ impl SegmentRunner for Turner {
             return SegmentRunAction::Success;
         }
 
+        let center_to_car = me_loc - self.plan.center;
+        let path_loc = self.plan.center + center_to_car.normalize() * self.plan.radius;
+        let path_dir =
+            UnitComplex::new(PI / 2.0 * self.plan.sweep.signum()) * center_to_car.to_axis();
+        let error = TrackingError::measure(me_loc, path_loc, path_dir, 0.0);
+        if TRACKING_TOLERANCE.is_exceeded_by(&error) {
+            return SegmentRunAction::Deviated(Deviation {
+                segment: self.name().to_string(),
+                error,
+            });
+        }
+
+        // Turn at full lock, but ease off if we've drifted inside the circle.
+        let heading_error = me_forward.angle_to(&path_dir);
+        let speed = me.Physics.vel_2d().norm();
+        let steer =
+            self.plan.sweep.signum() + tracking_steer(heading_error, error.cross_track, speed);
+
         SegmentRunAction::Yield(common::halfway_house::PlayerInput {
             Throttle: 1.0,
-            Steer: yaw_diff.signum(),
+            Steer: steer.max(-1.0).min(1.0),
             ..Default::default()
         })
     }
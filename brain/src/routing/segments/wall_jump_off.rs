@@ -0,0 +1,176 @@
+use crate::{
+    behavior::{
+        higher_order::Chain,
+        movement::{Land, Yielder},
+    },
+    eeg::{color, Drawable},
+    routing::{
+        models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
+        snapshot::SegmentSnapshot,
+    },
+    strategy::{Action, Behavior, Context, Priority},
+    utils::geometry::Plane,
+};
+use common::{kinematics::kinematic_time, physics::CAR_LOCAL_FORWARD_AXIS_2D, prelude::*, rl};
+use nalgebra::{Point2, Unit, Vector2};
+use nameof::name_of_type;
+use serde_derive::{Deserialize, Serialize};
+
+const JUMP_TIME: f32 = 6.0 / 120.0;
+
+/// Jump off a wall and fall to the floor.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WallJumpOff {
+    start: CarState,
+    land_loc: Point2<f32>,
+    land_vel: Vector2<f32>,
+    duration: f32,
+}
+
+impl WallJumpOff {
+    /// Returns `None` if the car would never come back down (e.g., it's
+    /// already on the floor).
+    pub fn new(start: CarState, surface: &Plane) -> Option<Self> {
+        // Model the jump as an instantaneous impulse away from the wall. The runner
+        // only taps jump, so there's no sustained jump force to speak of.
+        let vel = start.vel + surface.normal.into_inner() * rl::CAR_JUMP_IMPULSE_SPEED;
+        let fall = rl::OCTANE_NEUTRAL_Z - start.loc.z;
+        if fall >= 0.0 {
+            return None;
+        }
+        let duration = kinematic_time(fall, vel.z, rl::GRAVITY)?;
+        let land_vel = vel.to_2d();
+        Some(Self {
+            land_loc: start.loc.to_2d() + land_vel * duration,
+            land_vel,
+            start,
+            duration,
+        })
+    }
+}
+
+impl SegmentPlan for WallJumpOff {
+    fn name(&self) -> &str {
+        name_of_type!(WallJumpOff)
+    }
+
+    fn start(&self) -> CarState {
+        self.start.clone()
+    }
+
+    fn end(&self) -> CarState {
+        // Assume `Land` manages to point the nose where we're going.
+        let rot = match Unit::try_new(self.land_vel, 1.0) {
+            Some(dir) => CAR_LOCAL_FORWARD_AXIS_2D.rotation_to(&dir),
+            None => self.start.rot.to_2d(),
+        };
+        CarState2D {
+            loc: self.land_loc,
+            rot,
+            vel: self.land_vel,
+            boost: self.start.boost,
+        }
+        .to_3d()
+    }
+
+    fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    fn run(&self) -> Box<dyn SegmentRunner> {
+        Box::new(WallJumpOffRunner::new())
+    }
+
+    fn draw(&self, ctx: &mut Context<'_>) {
+        ctx.eeg.draw(Drawable::Line(
+            self.start.loc.to_2d(),
+            self.land_loc,
+            color::GREEN,
+        ));
+    }
+
+    fn snapshot(&self) -> SegmentSnapshot {
+        SegmentSnapshot::WallJumpOff(self.clone())
+    }
+}
+
+struct WallJumpOffRunner {
+    behavior: Box<dyn Behavior>,
+}
+
+impl WallJumpOffRunner {
+    pub fn new() -> Self {
+        let behavior = Box::new(Chain::new(Priority::Idle, vec![
+            Box::new(Yielder::new(
+                JUMP_TIME,
+                common::halfway_house::PlayerInput {
+                    Jump: true,
+                    ..Default::default()
+                },
+            )),
+            Box::new(Land::new()),
+        ]));
+        Self { behavior }
+    }
+}
+
+impl SegmentRunner for WallJumpOffRunner {
+    fn name(&self) -> &str {
+        name_of_type!(WallJumpOffRunner)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> SegmentRunAction {
+        match self.behavior.execute_old(ctx) {
+            Action::Yield(i) => SegmentRunAction::Yield(i),
+            Action::TailCall(_) => panic!("TailCall not yet supported in SegmentRunner"),
+            Action::RootCall(_) => SegmentRunAction::Failure,
+            Action::Return(_) => SegmentRunAction::Success,
+            Action::Abort(reason) => {
+                ctx.eeg.log(self.name(), reason);
+                SegmentRunAction::Failure
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        routing::{
+            models::{CarState, SegmentPlan},
+            segments::WallJumpOff,
+        },
+        strategy::DFH_STADIUM,
+    };
+    use common::{prelude::*, rl};
+    use nalgebra::{Point3, UnitQuaternion, Vector3};
+
+    #[test]
+    fn lands_inside_the_field() {
+        let loc = Point3::new(rl::FIELD_MAX_X - rl::OCTANE_NEUTRAL_Z, 0.0, 1000.0);
+        let start = CarState {
+            loc,
+            rot: UnitQuaternion::identity(),
+            vel: Vector3::new(0.0, 0.0, -500.0),
+            boost: 50.0,
+        };
+        let wall = DFH_STADIUM.closest_plane(&loc);
+        let jump = WallJumpOff::new(start, wall).unwrap();
+        let end = jump.end();
+        assert!(jump.duration() > 0.0);
+        assert!(end.loc.x < loc.x);
+        assert!((end.loc.z - rl::OCTANE_NEUTRAL_Z).abs() < 1.0);
+        assert!((end.loc.to_2d().y - loc.to_2d().y).abs() < 1.0);
+    }
+
+    #[test]
+    fn already_on_the_floor() {
+        let start = CarState {
+            loc: Point3::new(0.0, 0.0, rl::OCTANE_NEUTRAL_Z),
+            rot: UnitQuaternion::identity(),
+            vel: Vector3::zeros(),
+            boost: 0.0,
+        };
+        assert!(WallJumpOff::new(start, DFH_STADIUM.ground()).is_none());
+    }
+}
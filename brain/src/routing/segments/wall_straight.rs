@@ -1,14 +1,18 @@
 use crate::{
-    routing::models::{CarState, SegmentPlan, SegmentRunAction, SegmentRunner},
+    routing::{
+        models::{CarState, SegmentPlan, SegmentRunAction, SegmentRunner},
+        snapshot::SegmentSnapshot,
+    },
     strategy::Context,
     utils::geometry::flattener::Flattener,
 };
 use common::{prelude::*, rl};
 use nalgebra::{Point2, Point3, Vector2};
 use nameof::name_of_type;
-use simulate::Car1D;
+use serde_derive::{Deserialize, Serialize};
+use simulate::{CarSurfaceDrive, CarSurfaceDriveError, SurfacePath};
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct WallStraight {
     start: CarState,
     target_loc: Point3<f32>,
@@ -25,23 +29,23 @@ impl WallStraight {
         target_loc: Point3<f32>,
         start_to_flat: Flattener,
         target_to_flat: Flattener,
-    ) -> Self {
+        surface_path: &SurfacePath,
+    ) -> Result<Self, CarSurfaceDriveError> {
         let flat_start = start.flatten(&start_to_flat);
         let flat_target_loc = target_to_flat * target_loc;
-        let flat_target_dist = (flat_target_loc - flat_start.loc).norm();
 
-        let mut sim = Car1D::new()
+        let mut sim = CarSurfaceDrive::new()
             .with_speed(flat_start.vel.norm())
             .with_boost(flat_start.boost);
+        sim.advance_path(surface_path, 1.0, true)?;
 
-        // XXX: This ignores the fact that you lose speed when driving around a seam.
-        sim.advance_by_distance(flat_target_dist, 1.0, true);
-
+        // The surface path can be shorter than the unfolded distance (it cuts through
+        // the rounded corners), but either way it ends at the target.
         let flat_dir = (flat_target_loc - flat_start.loc).normalize();
-        let flat_end_loc = flat_start.loc + flat_dir * sim.distance();
+        let flat_end_loc = flat_target_loc;
         let flat_end_vel = flat_dir * sim.speed();
 
-        Self {
+        Ok(Self {
             start,
             target_loc,
             start_to_flat,
@@ -49,7 +53,7 @@ impl WallStraight {
             flat_end_loc,
             flat_end_vel,
             duration: sim.time(),
-        }
+        })
     }
 }
 
@@ -81,6 +85,10 @@ impl SegmentPlan for WallStraight {
     }
 
     fn draw(&self, _ctx: &mut Context<'_>) {}
+
+    fn snapshot(&self) -> SegmentSnapshot {
+        SegmentSnapshot::WallStraight(self.clone())
+    }
 }
 
 struct WallStraightRunner;
@@ -1,14 +1,18 @@
 use crate::{
-    routing::models::{CarState, SegmentPlan, SegmentRunAction, SegmentRunner},
+    routing::{
+        models::{CarState, SegmentPlan, SegmentRunAction, SegmentRunner},
+        snapshot::SegmentSnapshot,
+    },
     strategy::Context,
     utils::geometry::{flattener::Flattener, Plane},
 };
 use common::{prelude::*, rl};
 use nalgebra::{Point2, UnitComplex};
 use nameof::name_of_type;
+use serde_derive::{Deserialize, Serialize};
 use std::f32::consts::PI;
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct WallTurn {
     start: CarState,
     surface: Plane,
@@ -90,6 +94,10 @@ impl SegmentPlan for WallTurn {
     }
 
     fn draw(&self, _ctx: &mut Context<'_>) {}
+
+    fn snapshot(&self) -> SegmentSnapshot {
+        SegmentSnapshot::WallTurn(self.clone())
+    }
 }
 
 struct WallTurnRunner {
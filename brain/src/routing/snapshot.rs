@@ -0,0 +1,97 @@
+//! A serializable form of route plans, for dumping them while debugging,
+//! reloading them from the log afterwards, and for golden tests.
+
+use crate::routing::{
+    models::{ProvisionalPlanExpansion, RoutePlan, SegmentPlan},
+    segments::{
+        Brake, Chain, ForwardDodge, JumpAndDodge, NullSegment, PowerslideTurn, SimpleArc,
+        SpeedFlip, Straight, Turn, WallJumpOff, WallStraight, WallTurn,
+    },
+};
+use serde_derive::{Deserialize, Serialize};
+
+/// The parameters of a single segment, tagged with its type.
+#[derive(Clone, Serialize, Deserialize)]
+pub enum SegmentSnapshot {
+    Brake(Brake),
+    Chain(Vec<SegmentSnapshot>),
+    ForwardDodge(ForwardDodge),
+    JumpAndDodge(JumpAndDodge),
+    NullSegment(NullSegment),
+    PowerslideTurn(PowerslideTurn),
+    SimpleArc(SimpleArc),
+    SpeedFlip(SpeedFlip),
+    Straight(Straight),
+    Turn(Turn),
+    WallJumpOff(WallJumpOff),
+    WallStraight(WallStraight),
+    WallTurn(WallTurn),
+}
+
+impl SegmentSnapshot {
+    /// Rebuild the segment so it can be run or drawn again.
+    pub fn restore(self) -> Box<dyn SegmentPlan> {
+        match self {
+            SegmentSnapshot::Brake(s) => Box::new(s),
+            SegmentSnapshot::Chain(s) => Box::new(Chain::new(
+                s.into_iter().map(SegmentSnapshot::restore).collect(),
+            )),
+            SegmentSnapshot::ForwardDodge(s) => Box::new(s),
+            SegmentSnapshot::JumpAndDodge(s) => Box::new(s),
+            SegmentSnapshot::NullSegment(s) => Box::new(s),
+            SegmentSnapshot::PowerslideTurn(s) => Box::new(s),
+            SegmentSnapshot::SimpleArc(s) => Box::new(s),
+            SegmentSnapshot::SpeedFlip(s) => Box::new(s),
+            SegmentSnapshot::Straight(s) => Box::new(s),
+            SegmentSnapshot::Turn(s) => Box::new(s),
+            SegmentSnapshot::WallJumpOff(s) => Box::new(s),
+            SegmentSnapshot::WallStraight(s) => Box::new(s),
+            SegmentSnapshot::WallTurn(s) => Box::new(s),
+        }
+    }
+}
+
+/// A plan's segments, in order.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PlanSnapshot {
+    pub segments: Vec<SegmentSnapshot>,
+    /// The name of the planner which would continue after the last segment,
+    /// if the plan was not fully expanded. Planners themselves are not
+    /// serialized, so this is informational only.
+    pub next: Option<String>,
+}
+
+impl PlanSnapshot {
+    pub fn from_plan(plan: &RoutePlan) -> Self {
+        Self {
+            segments: vec![plan.segment.snapshot()],
+            next: plan.next.as_ref().map(|p| p.name().to_string()),
+        }
+    }
+
+    pub fn from_expansion(expansion: &ProvisionalPlanExpansion<'_>) -> Self {
+        Self {
+            segments: expansion.iter().map(|s| s.snapshot()).collect(),
+            next: None,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Rebuild all the segments as a single chain, so the plan can be run
+    /// again with `SegmentPlan::run`.
+    pub fn restore(self) -> Box<dyn SegmentPlan> {
+        Box::new(Chain::new(
+            self.segments
+                .into_iter()
+                .map(SegmentSnapshot::restore)
+                .collect(),
+        ))
+    }
+}
@@ -1,13 +1,19 @@
 use crate::{
+    helpers::ball::{BallPredictor, ChipBallPrediction},
     routing::{
         behavior::FollowRoute,
         models::{
-            PlanningContext, PlanningDump, RoutePlan, RoutePlanError, RoutePlanner, SegmentPlan,
+            CarState, PlanningContext, PlanningDump, RoutePlan, RoutePlanError, RoutePlanner,
+            SegmentPlan,
         },
     },
-    strategy::Behavior,
+    strategy::{Behavior, Game},
+};
+use common::halfway_house::{
+    BallInfo, GameInfo, LiveDataPacket, Physics, PlayerInfo, Rotator, TeamInfo, Vector3,
 };
 use derive_new::new;
+use std::f32::consts::PI;
 
 pub fn route_planner_tester(planner: impl RoutePlanner + 'static) -> impl Behavior {
     FollowRoute::new(planner)
@@ -40,3 +46,195 @@ where
         })
     }
 }
+
+/// A blue car at kickoff-ish distance on its own half, facing and driving
+/// upfield, with the ball resting at center.
+pub fn fixed_packet() -> LiveDataPacket {
+    let car = PlayerInfo {
+        Physics: Physics {
+            Location: Vector3 {
+                X: 0.0,
+                Y: -3000.0,
+                Z: 17.01,
+            },
+            Rotation: Rotator {
+                Yaw: PI / 2.0,
+                ..Default::default()
+            },
+            Velocity: Vector3 {
+                X: 0.0,
+                Y: 1000.0,
+                Z: 0.0,
+            },
+            ..Default::default()
+        },
+        OnGround: true,
+        Team: 0,
+        Boost: 50,
+        ..Default::default()
+    };
+    let ball = BallInfo {
+        Physics: Physics {
+            Location: Vector3 {
+                X: 0.0,
+                Y: 0.0,
+                Z: 92.74,
+            },
+            ..Default::default()
+        },
+    };
+    LiveDataPacket {
+        GameCars: vec![car].into_iter().collect(),
+        NumCars: 1,
+        GameBall: ball,
+        GameInfo: GameInfo {
+            RoundActive: true,
+            ..Default::default()
+        },
+        Teams: vec![TeamInfo::default(), TeamInfo {
+            TeamIndex: 1,
+            Score: 0,
+        }]
+        .into_iter()
+        .collect(),
+        NumTeams: 2,
+    }
+}
+
+/// Run `f` with a `PlanningContext` built from `fixed_packet`.
+pub fn with_fixed_context<R>(f: impl FnOnce(&PlanningContext<'_, '_>) -> R) -> R {
+    let packet = fixed_packet();
+    let game = Game::fake_soccar(&packet, 0);
+    let ball_prediction = ChipBallPrediction::new().predict(&packet);
+    f(&PlanningContext {
+        game: &game,
+        start: CarState::from(game.me()),
+        ball_prediction: &ball_prediction,
+    })
+}
+
+mod golden {
+    use crate::routing::{
+        models::{PlanningDump, RoutePlanner},
+        plan::GroundStraightPlanner,
+        snapshot::PlanSnapshot,
+        test::with_fixed_context,
+        trace::PlanningTrace,
+        StraightMode,
+    };
+    use nalgebra::Point2;
+    use serde_json::Value;
+    use std::{env, fs, path::PathBuf};
+
+    /// Plans may drift by this much before a golden test fails. Anything more
+    /// is a real behavior change.
+    const FLOAT_TOLERANCE: f64 = 1e-3;
+
+    fn plan(planner: &dyn RoutePlanner) -> PlanSnapshot {
+        with_fixed_context(|ctx| {
+            let mut log = Vec::new();
+            let mut trace = PlanningTrace::default();
+            let mut dump = PlanningDump {
+                log: &mut log,
+                trace: &mut trace,
+            };
+            match planner.plan(ctx, &mut dump) {
+                Ok(plan) => PlanSnapshot::from_plan(&plan),
+                Err(error) => panic!("{:?}\n{}", error, log.join("\n")),
+            }
+        })
+    }
+
+    fn golden_path(name: &str) -> PathBuf {
+        [env!("CARGO_MANIFEST_DIR"), "src", "routing", "snapshots"]
+            .iter()
+            .collect::<PathBuf>()
+            .join(format!("{}.json", name))
+    }
+
+    /// Compare a plan against the golden file with the given name. Run the
+    /// tests with `UPDATE_GOLDENS=1` to record the files instead, e.g. to
+    /// accept a deliberate change.
+    fn assert_golden(name: &str, snapshot: &PlanSnapshot) {
+        let path = golden_path(name);
+        let actual = snapshot.to_json();
+        if env::var("UPDATE_GOLDENS")
+            .map(|v| v == "1")
+            .unwrap_or(false)
+        {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, &actual).unwrap();
+            return;
+        }
+        let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+            panic!(
+                "missing golden file {}. Run with UPDATE_GOLDENS=1 to record it.",
+                path.display(),
+            )
+        });
+        let actual_value: Value = serde_json::from_str(&actual).unwrap();
+        let expected_value: Value = serde_json::from_str(&expected).unwrap();
+        assert!(
+            approx_eq(&actual_value, &expected_value),
+            "plan for {} changed.\nexpected: {}\nactual: {}",
+            name,
+            expected,
+            actual,
+        );
+    }
+
+    fn approx_eq(a: &Value, b: &Value) -> bool {
+        match (a, b) {
+            (Value::Number(a), Value::Number(b)) => {
+                let (a, b) = (a.as_f64().unwrap(), b.as_f64().unwrap());
+                (a - b).abs() <= FLOAT_TOLERANCE * a.abs().max(b.abs()).max(1.0)
+            }
+            (Value::Array(a), Value::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| approx_eq(a, b))
+            }
+            (Value::Object(a), Value::Object(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .all(|(k, a)| b.get(k).map(|b| approx_eq(a, b)).unwrap_or(false))
+            }
+            (a, b) => a == b,
+        }
+    }
+
+    #[test]
+    fn ground_straight_golden() {
+        let planner = GroundStraightPlanner::new(Point2::new(0.0, 0.0), StraightMode::Asap)
+            .allow_dodging(false);
+        assert_golden("ground_straight", &plan(&planner));
+    }
+
+    #[test]
+    fn ground_straight_with_dodge_golden() {
+        let planner = GroundStraightPlanner::new(Point2::new(0.0, 0.0), StraightMode::Asap)
+            .allow_dodging(true);
+        assert_golden("ground_straight_with_dodge", &plan(&planner));
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let planner = GroundStraightPlanner::new(Point2::new(0.0, 0.0), StraightMode::Asap);
+        let snapshot = plan(&planner);
+        let json = snapshot.to_json();
+        let reloaded = PlanSnapshot::from_json(&json).unwrap();
+        assert_eq!(reloaded.to_json(), json);
+    }
+
+    #[test]
+    fn restore_and_run() {
+        let planner = GroundStraightPlanner::new(Point2::new(0.0, 0.0), StraightMode::Asap);
+        let snapshot = plan(&planner);
+        let original = snapshot.clone().restore();
+        let reloaded = PlanSnapshot::from_json(&snapshot.to_json())
+            .unwrap()
+            .restore();
+        assert_eq!(reloaded.duration(), original.duration());
+        assert_eq!(reloaded.end().loc, original.end().loc);
+        // The restored plan must be runnable, not just inspectable.
+        reloaded.run();
+    }
+}
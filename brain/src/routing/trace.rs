@@ -0,0 +1,271 @@
+//! A structured record of what happened during planning, and a way to look at
+//! it.
+
+use crate::routing::models::{CarState, ProvisionalPlanExpansion, RoutePlanError, SegmentPlan};
+use common::{prelude::*, rl, PrettyPrint};
+use nalgebra::Point2;
+use std::fmt::Write;
+
+/// Everything the planners did while producing (or failing to produce) a
+/// route, in order.
+#[derive(Clone, Default)]
+pub struct PlanningTrace {
+    pub events: Vec<TraceEvent>,
+}
+
+#[derive(Clone)]
+pub enum TraceEvent {
+    /// A planner was invoked.
+    Start {
+        planner: &'static str,
+        state: CarState,
+    },
+    /// A planner produced a segment.
+    Segment {
+        planner: &'static str,
+        segment: String,
+        start: CarState,
+        end: CarState,
+        duration: f32,
+    },
+    /// A planner gave up.
+    Error {
+        planner: &'static str,
+        state: CarState,
+        error: String,
+    },
+}
+
+impl PlanningTrace {
+    pub fn start(&mut self, planner: &'static str, state: &CarState) {
+        self.events.push(TraceEvent::Start {
+            planner,
+            state: state.clone(),
+        });
+    }
+
+    pub fn segment(&mut self, planner: &'static str, segment: &dyn SegmentPlan) {
+        self.events.push(TraceEvent::Segment {
+            planner,
+            segment: segment.name().to_string(),
+            start: segment.start(),
+            end: segment.end(),
+            duration: segment.duration(),
+        });
+    }
+
+    pub fn error(&mut self, planner: &'static str, state: &CarState, error: &RoutePlanError) {
+        self.events.push(TraceEvent::Error {
+            planner,
+            state: state.clone(),
+            error: format!("{:?}", error),
+        });
+    }
+
+    /// A few lines describing how planning went, suitable for the EEG.
+    pub fn summary(&self) -> Vec<String> {
+        let segments = self
+            .events
+            .iter()
+            .filter(|e| match e {
+                TraceEvent::Segment { .. } => true,
+                _ => false,
+            })
+            .count();
+        let mut result = vec![format!(
+            "{} events, {} segments",
+            self.events.len(),
+            segments,
+        )];
+        for event in &self.events {
+            if let TraceEvent::Error {
+                planner,
+                state,
+                error,
+            } = event
+            {
+                result.push(format!("[{}] {} at {}", planner, error, state.loc.pretty()));
+            }
+        }
+        result
+    }
+}
+
+/// Render a successful route as an SVG of the field.
+pub fn expansion_svg(expansion: &ProvisionalPlanExpansion<'_>) -> String {
+    let mut svg = Svg::new();
+    for (i, segment) in expansion.iter().enumerate() {
+        svg.segment(
+            &segment.start(),
+            &segment.end(),
+            COLORS[i % COLORS.len()],
+            &format!("{} ({:.2}s)", segment.name(), segment.duration()),
+        );
+    }
+    svg.finish()
+}
+
+/// Render a planning trace (usually a failed one) as an SVG of the field.
+pub fn trace_svg(trace: &PlanningTrace) -> String {
+    let mut svg = Svg::new();
+    let mut segment_index = 0;
+    for event in &trace.events {
+        match event {
+            TraceEvent::Start { planner, state } => {
+                svg.point(state.loc.to_2d(), "gray", planner);
+            }
+            TraceEvent::Segment {
+                planner,
+                segment,
+                start,
+                end,
+                duration,
+            } => {
+                let color = COLORS[segment_index % COLORS.len()];
+                let title = format!("[{}] {} ({:.2}s)", planner, segment, duration);
+                svg.segment(start, end, color, &title);
+                segment_index += 1;
+            }
+            TraceEvent::Error {
+                planner,
+                state,
+                error,
+            } => {
+                svg.cross(state.loc.to_2d(), &format!("[{}] {}", planner, error));
+            }
+        }
+    }
+    svg.finish()
+}
+
+const COLORS: &[&str] = &["#e6194b", "#3cb44b", "#4363d8", "#f58231", "#911eb4"];
+/// How far the goals stick out past the back wall.
+const GOAL_DEPTH: f32 = 880.0;
+
+struct Svg {
+    body: String,
+}
+
+impl Svg {
+    fn new() -> Self {
+        let mut body = String::new();
+        let (w, h) = (rl::FIELD_MAX_X, rl::FIELD_MAX_Y + GOAL_DEPTH);
+        writeln!(
+            body,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}">"#,
+            -w,
+            -h,
+            w * 2.0,
+            h * 2.0,
+        )
+        .unwrap();
+        // Flip the y axis so orange's goal is at the top.
+        writeln!(body, r#"<g transform="scale(1,-1)" stroke-width="20">"#).unwrap();
+        writeln!(
+            body,
+            r##"<rect x="{}" y="{}" width="{}" height="{}" fill="#eee" stroke="black"/>"##,
+            -rl::FIELD_MAX_X,
+            -rl::FIELD_MAX_Y,
+            rl::FIELD_MAX_X * 2.0,
+            rl::FIELD_MAX_Y * 2.0,
+        )
+        .unwrap();
+        for &y in &[-rl::FIELD_MAX_Y - GOAL_DEPTH, rl::FIELD_MAX_Y] {
+            writeln!(
+                body,
+                r##"<rect x="{}" y="{}" width="{}" height="{}" fill="#ccc" stroke="black"/>"##,
+                -rl::GOALPOST_X,
+                y,
+                rl::GOALPOST_X * 2.0,
+                GOAL_DEPTH,
+            )
+            .unwrap();
+        }
+        Self { body }
+    }
+
+    fn segment(&mut self, start: &CarState, end: &CarState, color: &str, title: &str) {
+        let (a, b) = (start.loc.to_2d(), end.loc.to_2d());
+        writeln!(
+            self.body,
+            r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}"><title>{}</title></line>"#,
+            a.x,
+            a.y,
+            b.x,
+            b.y,
+            color,
+            escape(title),
+        )
+        .unwrap();
+        self.point(a, color, title);
+    }
+
+    fn point(&mut self, loc: Point2<f32>, color: &str, title: &str) {
+        writeln!(
+            self.body,
+            r#"<circle cx="{}" cy="{}" r="40" fill="{}"><title>{}</title></circle>"#,
+            loc.x,
+            loc.y,
+            color,
+            escape(title),
+        )
+        .unwrap();
+    }
+
+    fn cross(&mut self, loc: Point2<f32>, title: &str) {
+        let r = 80.0;
+        writeln!(
+            self.body,
+            r#"<path d="M{} {} L{} {} M{} {} L{} {}" stroke="red" stroke-width="30"><title>{}</title></path>"#,
+            loc.x - r,
+            loc.y - r,
+            loc.x + r,
+            loc.y + r,
+            loc.x - r,
+            loc.y + r,
+            loc.x + r,
+            loc.y - r,
+            escape(title),
+        )
+        .unwrap();
+    }
+
+    fn finish(mut self) -> String {
+        self.body.push_str("</g>\n</svg>\n");
+        self.body
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routing::{
+        models::{CarState, RoutePlanError},
+        trace::{trace_svg, PlanningTrace},
+    };
+    use nalgebra::{Point3, UnitQuaternion, Vector3};
+
+    #[test]
+    fn renders_failed_trace() {
+        let state = CarState {
+            loc: Point3::new(100.0, -200.0, 17.0),
+            rot: UnitQuaternion::identity(),
+            vel: Vector3::zeros(),
+            boost: 0.0,
+        };
+        let mut trace = PlanningTrace::default();
+        trace.start("TestPlanner", &state);
+        trace.error("TestPlanner", &state, &RoutePlanError::OtherError("<oops>"));
+
+        let svg = trace_svg(&trace);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert!(svg.contains("&lt;oops&gt;"));
+        assert_eq!(trace.summary().len(), 2);
+    }
+}
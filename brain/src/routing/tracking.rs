@@ -0,0 +1,113 @@
+//! Helpers for segment runners which follow their planned trajectory
+//! closed-loop instead of replaying the plan blindly.
+
+use nalgebra::{Point2, Unit, Vector2};
+
+/// How strongly to steer back towards the path, relative to speed. Higher
+/// values converge faster but oscillate more.
+const CROSS_TRACK_GAIN: f32 = 2.5;
+/// Below this speed, treat the car as going this speed so the cross-track term
+/// doesn't blow up.
+const CROSS_TRACK_MIN_SPEED: f32 = 300.0;
+/// Radians of steering error which map to full lock.
+const STEER_GAIN: f32 = 3.0;
+/// Throttle reduction per second of being ahead of schedule.
+const THROTTLE_GAIN: f32 = 4.0;
+
+/// How far the car has strayed from a segment's planned trajectory.
+#[derive(Copy, Clone, Debug)]
+pub struct TrackingError {
+    /// The signed distance from the path. Positive means the car is on the
+    /// counterclockwise side of the path's direction of travel.
+    pub cross_track: f32,
+    /// Seconds behind schedule. Negative means ahead of schedule.
+    pub timing: f32,
+}
+
+impl TrackingError {
+    /// Measure against a path which, at the closest point to the car, passes
+    /// through `path_loc` going in direction `path_dir`.
+    pub fn measure(
+        car_loc: Point2<f32>,
+        path_loc: Point2<f32>,
+        path_dir: Unit<Vector2<f32>>,
+        timing: f32,
+    ) -> Self {
+        Self {
+            cross_track: path_dir.perp(&(car_loc - path_loc)),
+            timing,
+        }
+    }
+}
+
+/// The largest `TrackingError` a runner will put up with before giving up on
+/// its plan.
+#[derive(Copy, Clone, Debug)]
+pub struct TrackingTolerance {
+    pub cross_track: f32,
+    pub timing: f32,
+}
+
+impl TrackingTolerance {
+    pub const DEFAULT: Self = Self {
+        cross_track: 150.0,
+        timing: 0.5,
+    };
+
+    pub fn is_exceeded_by(&self, error: &TrackingError) -> bool {
+        error.cross_track.abs() > self.cross_track || error.timing.abs() > self.timing
+    }
+}
+
+/// A segment runner has strayed too far from its plan to continue. The rest of
+/// the route should be replanned from wherever the car is now.
+#[derive(Clone, Debug)]
+pub struct Deviation {
+    pub segment: String,
+    pub error: TrackingError,
+}
+
+/// A Stanley-style lateral controller. `heading_error` is the angle from the
+/// car's forward axis to the path's direction of travel.
+pub fn tracking_steer(heading_error: f32, cross_track: f32, speed: f32) -> f32 {
+    let speed = speed.max(CROSS_TRACK_MIN_SPEED);
+    let correction = -(CROSS_TRACK_GAIN * cross_track / speed).atan();
+    ((heading_error + correction) * STEER_GAIN)
+        .max(-1.0)
+        .min(1.0)
+}
+
+/// Back off the throttle when ahead of schedule, for segments which were
+/// planned at a constant speed.
+pub fn tracking_throttle(timing: f32) -> f32 {
+    (1.0 + THROTTLE_GAIN * timing).max(0.0).min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routing::tracking::{tracking_steer, tracking_throttle, TrackingError};
+    use nalgebra::{Point2, Unit, Vector2};
+
+    #[test]
+    fn cross_track_sign() {
+        let dir = Unit::new_normalize(Vector2::x());
+        let left = TrackingError::measure(Point2::new(0.0, 100.0), Point2::origin(), dir, 0.0);
+        let right = TrackingError::measure(Point2::new(0.0, -100.0), Point2::origin(), dir, 0.0);
+        assert!(left.cross_track > 0.0);
+        assert!(right.cross_track < 0.0);
+    }
+
+    #[test]
+    fn steers_back_towards_path() {
+        assert!(tracking_steer(0.0, 100.0, 1000.0) < 0.0);
+        assert!(tracking_steer(0.0, -100.0, 1000.0) > 0.0);
+        assert!(tracking_steer(0.0, 0.0, 1000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn eases_off_when_ahead() {
+        assert!((tracking_throttle(0.0) - 1.0).abs() < 1e-6);
+        assert!(tracking_throttle(-0.1) < 1.0);
+        assert!((tracking_throttle(-1.0) - 0.0).abs() < 1e-6);
+    }
+}
@@ -1,10 +1,11 @@
-use crate::strategy::{Action, Context};
+use crate::{
+    params,
+    strategy::{Action, Context},
+};
 use common::prelude::*;
 use nalgebra::Point3;
 use nameof::name_of_type;
 
-const ERROR_THRESHOLD: f32 = 50.0;
-
 /// Track the ball's trajectory vs. our prediction, and if they differ by too
 /// much, abort.
 pub struct SameBallTrajectory {
@@ -23,7 +24,7 @@ impl SameBallTrajectory {
 
     pub fn execute_old(&mut self, ctx: &mut Context<'_>) -> Option<Action> {
         if self.eval_vel_changed(ctx) {
-            Some(Action::Abort)
+            Some(Action::abort("ball trajectory changed"))
         } else {
             self.update_snapshot(ctx);
             None
@@ -52,7 +53,7 @@ impl SameBallTrajectory {
         };
 
         let error = (prediction.loc - frame.loc).to_2d().norm();
-        if error >= ERROR_THRESHOLD {
+        if error >= params::SAME_BALL_TRAJECTORY_ERROR.get() {
             ctx.eeg.log(
                 name_of_type!(SameBallTrajectory),
                 format!("perturbance detected with error {:.2}", error),
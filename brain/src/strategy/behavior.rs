@@ -1,4 +1,5 @@
 use crate::strategy::Context;
+use serde_derive::Deserialize;
 
 pub trait Behavior: Send {
     /// A very short string identifying the behavior; usually just the name of
@@ -14,10 +15,41 @@ pub trait Behavior: Send {
         Priority::Idle
     }
 
+    /// What the behavior expects to accomplish, as far as it knows. This is
+    /// usually only filled in after the behavior has executed once (e.g., after
+    /// it has planned a route).
+    fn prospect(&self) -> Prospect {
+        Prospect::default()
+    }
+
+    /// The child currently in control, along with its index among this
+    /// behavior's children. Combinators override this so the `Runner` can see
+    /// the whole stack of nested behaviors.
+    fn active_child(&self) -> Option<(usize, &dyn Behavior)> {
+        None
+    }
+
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action;
 }
 
-#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
+#[derive(Clone, Default)]
+pub struct Prospect {
+    /// Seconds until the behavior reaches its goal.
+    pub arrival_time: Option<f32>,
+}
+
+/// One level of the stack of nested behaviors, as seen by the `Runner`.
+#[derive(Clone, Debug)]
+pub struct StackFrame {
+    pub name: String,
+    pub priority: Priority,
+    /// Seconds since this behavior first appeared at this position.
+    pub age: f32,
+    /// The behavior's index within its parent, or `None` at the root.
+    pub child_index: Option<usize>,
+}
+
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Deserialize)]
 pub enum Priority {
     Idle,
     Defense,
@@ -31,12 +63,22 @@ pub enum Action {
     Yield(common::halfway_house::PlayerInput),
     TailCall(Box<dyn Behavior>),
     RootCall(Box<dyn Behavior>),
-    Return,
-    Abort,
+    /// Finished successfully, for the given reason.
+    Return(String),
+    /// Give up, for the given reason.
+    Abort(String),
 }
 
 impl Action {
     pub fn tail_call(behavior: impl Behavior + 'static) -> Self {
         Action::TailCall(Box::new(behavior))
     }
+
+    pub fn return_(reason: impl Into<String>) -> Self {
+        Action::Return(reason.into())
+    }
+
+    pub fn abort(reason: impl Into<String>) -> Self {
+        Action::Abort(reason.into())
+    }
 }
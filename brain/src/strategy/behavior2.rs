@@ -0,0 +1,308 @@
+use crate::{
+    eeg::{Drawable, EEG},
+    strategy::{context::QUICK_CHAT_INTERVAL, Action, Behavior, Context, Context2, Priority},
+};
+
+/// The second generation of `Behavior`.
+///
+/// Game state comes in through the immutable `Context2`, and every side effect
+/// goes out through a `Sink`. This means a behavior can be dry-run or unit
+/// tested without an `EEG`. Use `Adapter` to run one wherever a `Behavior` is
+/// expected.
+pub trait Behavior2: Send {
+    /// The value produced when the behavior returns successfully.
+    type Output;
+
+    /// A very short string identifying the behavior; usually just the name of
+    /// the object.
+    fn name(&self) -> &str;
+
+    fn priority(&self) -> Priority {
+        Priority::Idle
+    }
+
+    fn execute(&mut self, ctx: &Context2<'_, '_>, sink: &mut dyn Sink) -> Outcome<Self::Output>;
+}
+
+pub enum Outcome<T> {
+    /// Keep going. `confidence` is in `[0, 1]` and says how sure the behavior
+    /// is that it will succeed.
+    Yield {
+        input: common::halfway_house::PlayerInput,
+        confidence: f32,
+    },
+    /// Finished successfully.
+    Return(T),
+    /// Gave up, for the given reason.
+    Abort(String),
+}
+
+impl<T> Outcome<T> {
+    pub fn yield_(input: common::halfway_house::PlayerInput) -> Self {
+        Outcome::Yield {
+            input,
+            confidence: 1.0,
+        }
+    }
+}
+
+/// Everything a `Behavior2` is allowed to do besides return an `Outcome`.
+pub trait Sink {
+    /// In a live game, quick chats within `QUICK_CHAT_INTERVAL` of the last one
+    /// are dropped. Check `Context2::can_quick_chat` first to know whether it
+    /// will go through.
+    fn draw(&mut self, drawable: Drawable);
+    fn log(&mut self, tag: &str, message: String);
+    fn quick_chat(&mut self, selection: rlbot::flat::QuickChatSelection);
+}
+
+/// A `Sink` which records side effects instead of performing them.
+#[cfg(test)]
+#[derive(Default)]
+pub struct Effects {
+    pub draws: Vec<Drawable>,
+    pub logs: Vec<String>,
+    pub quick_chats: Vec<rlbot::flat::QuickChatSelection>,
+}
+
+#[cfg(test)]
+impl Sink for Effects {
+    fn draw(&mut self, drawable: Drawable) {
+        self.draws.push(drawable);
+    }
+
+    fn log(&mut self, tag: &str, message: String) {
+        self.logs.push(format!("[{}] {}", tag, message));
+    }
+
+    fn quick_chat(&mut self, selection: rlbot::flat::QuickChatSelection) {
+        self.quick_chats.push(selection);
+    }
+}
+
+/// Forwards side effects to the `EEG`, the same way old-style behaviors do.
+struct EegSink<'a> {
+    eeg: &'a mut EEG,
+    last_quick_chat: &'a mut f32,
+    now: f32,
+}
+
+impl<'a> Sink for EegSink<'a> {
+    fn draw(&mut self, drawable: Drawable) {
+        self.eeg.draw(drawable);
+    }
+
+    fn log(&mut self, tag: &str, message: String) {
+        self.eeg.log(tag, message);
+    }
+
+    fn quick_chat(&mut self, selection: rlbot::flat::QuickChatSelection) {
+        if self.now - *self.last_quick_chat < QUICK_CHAT_INTERVAL {
+            self.eeg.log("quick_chat", "throttled");
+            return;
+        }
+        self.eeg.quick_chat(selection);
+        *self.last_quick_chat = self.now;
+    }
+}
+
+/// Run a `Behavior2` as a `Behavior`, so old and new behaviors can be mixed
+/// freely. The return value is discarded.
+pub struct Adapter<B: Behavior2> {
+    behavior: B,
+}
+
+impl<B: Behavior2> Adapter<B> {
+    pub fn new(behavior: B) -> Self {
+        Self { behavior }
+    }
+}
+
+impl<B: Behavior2> Behavior for Adapter<B> {
+    fn name(&self) -> &str {
+        self.behavior.name()
+    }
+
+    fn priority(&self) -> Priority {
+        self.behavior.priority()
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        let ctx2 = Context2 {
+            packet: ctx.packet,
+            game: ctx.game,
+            scenario: ctx.scenario,
+            last_quick_chat: *ctx.last_quick_chat,
+        };
+        let mut sink = EegSink {
+            eeg: &mut *ctx.eeg,
+            last_quick_chat: &mut *ctx.last_quick_chat,
+            now: ctx.packet.GameInfo.TimeSeconds,
+        };
+        match self.behavior.execute(&ctx2, &mut sink) {
+            Outcome::Yield { input, .. } => Action::Yield(input),
+            Outcome::Return(_) => Action::return_("finished"),
+            Outcome::Abort(reason) => Action::Abort(reason),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        behavior::taunt::SaltWhileDemolished,
+        eeg::{color, Drawable, EEG},
+        helpers::ball::ChipBallPrediction,
+        routing::test::fixed_packet,
+        strategy::{
+            behavior2::{Adapter, Behavior2, Effects, Outcome, Sink},
+            Context, Context2, Game, Runner, Scenario,
+        },
+    };
+    use common::halfway_house::{LiveDataPacket, PlayerInput};
+
+    /// Drive forward for a few frames, then report how fast we were going.
+    struct Accelerate {
+        frames: u32,
+    }
+
+    impl Behavior2 for Accelerate {
+        type Output = f32;
+
+        fn name(&self) -> &str {
+            stringify!(Accelerate)
+        }
+
+        fn execute(&mut self, ctx: &Context2<'_, '_>, sink: &mut dyn Sink) -> Outcome<f32> {
+            if self.frames == 0 {
+                return Outcome::Return(ctx.me().Physics.Velocity.Y);
+            }
+            self.frames -= 1;
+            sink.draw(Drawable::print("vroom", color::GREEN));
+            Outcome::yield_(common::halfway_house::PlayerInput {
+                Throttle: 1.0,
+                ..Default::default()
+            })
+        }
+    }
+
+    #[test]
+    fn runs_without_an_eeg() {
+        let packet = fixed_packet();
+        let game = Game::fake_soccar(&packet, 0);
+        let ball_predictor = ChipBallPrediction::new();
+        let scenario = Scenario::new(&game, &ball_predictor, &packet);
+        let ctx = Context2 {
+            packet: &packet,
+            game: &game,
+            scenario: &scenario,
+            last_quick_chat: 0.0,
+        };
+
+        let mut behavior = Accelerate { frames: 2 };
+        let mut effects = Effects::default();
+        for _ in 0..2 {
+            match behavior.execute(&ctx, &mut effects) {
+                Outcome::Yield { input, confidence } => {
+                    assert!(input.Throttle > 0.0);
+                    assert!(confidence > 0.0);
+                }
+                _ => panic!("expected a yield"),
+            }
+        }
+        match behavior.execute(&ctx, &mut effects) {
+            Outcome::Return(speed) => assert!(speed > 0.0),
+            _ => panic!("expected a return"),
+        }
+        assert_eq!(effects.draws.len(), 2);
+    }
+
+    /// Chats on every tick, whether or not it's allowed to.
+    struct Chatterbox;
+
+    impl Behavior2 for Chatterbox {
+        type Output = ();
+
+        fn name(&self) -> &str {
+            stringify!(Chatterbox)
+        }
+
+        fn execute(&mut self, _ctx: &Context2<'_, '_>, sink: &mut dyn Sink) -> Outcome<()> {
+            sink.quick_chat(rlbot::flat::QuickChatSelection::Reactions_Okay);
+            Outcome::yield_(Default::default())
+        }
+    }
+
+    /// Run `runner` for one frame of `packet`.
+    fn tick(
+        runner: &mut Runner,
+        packet: &LiveDataPacket,
+        eeg: &mut EEG,
+        last_quick_chat: &mut f32,
+    ) -> PlayerInput {
+        let game = Game::fake_soccar(packet, 0);
+        let ball_predictor = ChipBallPrediction::new();
+        let scenario = Scenario::new(&game, &ball_predictor, packet);
+        eeg.begin(packet);
+        let mut ctx = Context::new(&game, packet, &scenario, eeg, last_quick_chat);
+        let input = runner.execute_old(&mut ctx);
+        eeg.show(packet);
+        input
+    }
+
+    #[test]
+    fn adapter_runs_under_runner() {
+        let mut runner = Runner::with_current(Adapter::new(Accelerate { frames: 1 }));
+        let packet = fixed_packet();
+        let mut eeg = EEG::new();
+        let mut last_quick_chat = 0.0;
+
+        let input = tick(&mut runner, &packet, &mut eeg, &mut last_quick_chat);
+        assert_eq!(input.Throttle, 1.0);
+
+        // `Accelerate` returns, and the `NullStrategy` takes over.
+        let input = tick(&mut runner, &packet, &mut eeg, &mut last_quick_chat);
+        assert_eq!(input.Throttle, 0.0);
+    }
+
+    #[test]
+    fn quick_chats_are_throttled() {
+        let mut runner = Runner::with_current(Adapter::new(Chatterbox));
+        let mut packet = fixed_packet();
+        let mut eeg = EEG::new();
+        let mut last_quick_chat = 0.0;
+
+        for &(time, expect_chat) in &[(10.0, true), (10.5, false), (10.8, true)] {
+            packet.GameInfo.TimeSeconds = time;
+            tick(&mut runner, &packet, &mut eeg, &mut last_quick_chat);
+            assert_eq!(eeg.quick_chat.is_some(), expect_chat);
+        }
+        assert_eq!(last_quick_chat, 10.8);
+    }
+
+    #[test]
+    fn salt_respects_the_last_quick_chat() {
+        let mut packet = fixed_packet();
+        packet.GameCars[0].Demolished = true;
+        let game = Game::fake_soccar(&packet, 0);
+        let ball_predictor = ChipBallPrediction::new();
+        let scenario = Scenario::new(&game, &ball_predictor, &packet);
+        let now = packet.GameInfo.TimeSeconds;
+
+        let mut behavior = SaltWhileDemolished::new();
+        let mut effects = Effects::default();
+        for &(last_quick_chat, expect_chats) in &[(now - 0.1, 0), (now - 1.0, 1)] {
+            let ctx = Context2 {
+                packet: &packet,
+                game: &game,
+                scenario: &scenario,
+                last_quick_chat,
+            };
+            match behavior.execute(&ctx, &mut effects) {
+                Outcome::Yield { .. } => {}
+                _ => panic!("expected a yield"),
+            }
+            assert_eq!(effects.quick_chats.len(), expect_chats);
+        }
+    }
+}
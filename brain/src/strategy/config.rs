@@ -0,0 +1,594 @@
+//! Strategies described in a file instead of in code.
+//!
+//! A strategy file is JSON. `baseline` is the behavior tree to run when
+//! nothing else is running, and each of `interrupts` replaces the running
+//! behavior when its predicate holds and the running behavior has a lower
+//! priority. For example:
+//!
+//! ```json
+//! {
+//!     "baseline": {"try_choose": {"priority": "Idle", "choices": [
+//!         {"behavior": "Offense"},
+//!         {"behavior": "Defense"}
+//!     ]}},
+//!     "interrupts": [
+//!         {
+//!             "when": {"is": "IsKickoff"},
+//!             "priority": "Force",
+//!             "behavior": {"behavior": "PreKickoff"}
+//!         }
+//!     ]
+//! }
+//! ```
+//!
+//! Names are resolved against a `Registry` when the file is loaded, so a typo
+//! fails at startup rather than mid-match.
+//!
+//! Why JSON rather than TOML or RON? Plan snapshots and tuned params are
+//! already JSON, so this keeps to one format and one parser, and TOML's tables
+//! get awkward for trees this deep. Only `StrategyConfig::from_json` knows about the format, so switching
+//! later means swapping out that one parser.
+
+use crate::{
+    behavior::{
+        defense::{Defense, HitToOwnCorner, PanicDefense, PushToOwnCorner, Retreat},
+        higher_order::{Chain, Predicate, TryChoose, While},
+        movement::{GetToFlatGround, Land},
+        offense::{Offense, Shoot, TepidHit},
+        strike::{FiftyFifty, WallHit},
+        taunt::{PodiumBlastoff, PodiumSpew, PodiumStare, SaltWhileDemolished, TurtleSpin},
+        PreKickoff,
+    },
+    routing::{
+        behavior::FollowRoute,
+        plan::GetDollar,
+        recover::{IsSkidding, MatchIsEnded, NotOnFlatGround, RoundIsNotActive, WeDontWinTheRace},
+    },
+    strategy::{
+        soccar::{ScoringVerySoon, UnstoppableScore},
+        strategy::Strategy,
+        Adapter, Behavior, Context, Priority,
+    },
+};
+use nameof::name_of_type;
+use serde_derive::Deserialize;
+use std::{collections::HashMap, error::Error, fmt, fs, io, path::Path};
+
+pub type BehaviorConstructor = fn(&mut Context<'_>) -> Box<dyn Behavior>;
+pub type PredicateConstructor = fn() -> Box<dyn Predicate>;
+
+/// The names a strategy file may use.
+pub struct Registry {
+    behaviors: HashMap<&'static str, BehaviorConstructor>,
+    predicates: HashMap<&'static str, PredicateConstructor>,
+}
+
+impl Registry {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            behaviors: HashMap::new(),
+            predicates: HashMap::new(),
+        }
+    }
+
+    /// Every behavior and predicate which can be built without arguments.
+    pub fn standard() -> Self {
+        let mut registry = Self::new();
+        registry
+            .behavior(name_of_type!(Offense), |_| Box::new(Offense::new()))
+            .behavior(name_of_type!(Defense), |_| Box::new(Defense::new()))
+            .behavior(name_of_type!(Retreat), |_| Box::new(Retreat::new()))
+            .behavior(name_of_type!(PanicDefense), |_| {
+                Box::new(PanicDefense::new())
+            })
+            .behavior(name_of_type!(HitToOwnCorner), |_| {
+                Box::new(HitToOwnCorner::new())
+            })
+            .behavior(name_of_type!(PushToOwnCorner), |_| {
+                Box::new(PushToOwnCorner::new())
+            })
+            .behavior(name_of_type!(FiftyFifty), |_| Box::new(FiftyFifty::new()))
+            .behavior(name_of_type!(Shoot), |_| Box::new(Shoot::new()))
+            .behavior(name_of_type!(TepidHit), |_| Box::new(TepidHit::new()))
+            .behavior(name_of_type!(WallHit), |_| Box::new(WallHit::new()))
+            .behavior(name_of_type!(PreKickoff), |_| Box::new(PreKickoff::new()))
+            .behavior(name_of_type!(Land), |_| Box::new(Land::new()))
+            .behavior(name_of_type!(GetToFlatGround), |_| {
+                Box::new(GetToFlatGround::new())
+            })
+            .behavior(name_of_type!(GetDollar), |ctx| {
+                let (ctx, eeg) = ctx.split();
+                Box::new(FollowRoute::new(GetDollar::smart(&ctx, eeg)))
+            })
+            .behavior(name_of_type!(TurtleSpin), |_| Box::new(TurtleSpin::new()))
+            .behavior(name_of_type!(PodiumStare), |_| Box::new(PodiumStare::new()))
+            .behavior(name_of_type!(PodiumSpew), |_| Box::new(PodiumSpew::new()))
+            .behavior(name_of_type!(PodiumBlastoff), |_| {
+                Box::new(PodiumBlastoff::new())
+            })
+            .behavior(name_of_type!(SaltWhileDemolished), |_| {
+                Box::new(Adapter::new(SaltWhileDemolished::new()))
+            });
+        registry
+            .predicate(name_of_type!(IsKickoff), || Box::new(IsKickoff))
+            .predicate(name_of_type!(IsAirborne), || Box::new(IsAirborne))
+            .predicate(name_of_type!(NotOnFlatGround), || Box::new(NotOnFlatGround))
+            .predicate(name_of_type!(IsSkidding), || Box::new(IsSkidding))
+            .predicate(name_of_type!(WeDontWinTheRace), || {
+                Box::new(WeDontWinTheRace)
+            })
+            .predicate(name_of_type!(RoundIsNotActive), || {
+                Box::new(RoundIsNotActive)
+            })
+            .predicate(name_of_type!(MatchIsEnded), || Box::new(MatchIsEnded))
+            .predicate(name_of_type!(UnstoppableScore), || {
+                Box::new(UnstoppableScore)
+            })
+            .predicate(name_of_type!(ScoringVerySoon), || Box::new(ScoringVerySoon));
+        registry
+    }
+
+    pub fn behavior(&mut self, name: &'static str, constructor: BehaviorConstructor) -> &mut Self {
+        self.behaviors.insert(name, constructor);
+        self
+    }
+
+    pub fn predicate(
+        &mut self,
+        name: &'static str,
+        constructor: PredicateConstructor,
+    ) -> &mut Self {
+        self.predicates.insert(name, constructor);
+        self
+    }
+}
+
+/// A strategy file which has been parsed and checked against a `Registry`.
+#[derive(Clone)]
+pub struct StrategyConfig {
+    baseline: Node,
+    interrupts: Vec<Interrupt>,
+}
+
+#[derive(Clone)]
+struct Interrupt {
+    when: PredicateNode,
+    priority: Priority,
+    behavior: Node,
+}
+
+#[derive(Clone)]
+enum Node {
+    Behavior(BehaviorConstructor),
+    Chain(Priority, Vec<Node>),
+    TryChoose(Priority, Vec<Node>),
+    While(PredicateNode, Box<Node>),
+}
+
+#[derive(Clone)]
+enum PredicateNode {
+    Is(PredicateConstructor),
+    Not(Box<PredicateNode>),
+}
+
+impl StrategyConfig {
+    pub fn load(path: impl AsRef<Path>, registry: &Registry) -> Result<Self, ConfigError> {
+        let json = fs::read_to_string(path).map_err(ConfigError::Io)?;
+        Self::from_json(&json, registry)
+    }
+
+    pub fn from_json(json: &str, registry: &Registry) -> Result<Self, ConfigError> {
+        let file: StrategyFile = serde_json::from_str(json).map_err(ConfigError::Syntax)?;
+        let baseline = resolve(registry, &file.baseline, "baseline".to_string())?;
+        let interrupts = file
+            .interrupts
+            .iter()
+            .enumerate()
+            .map(|(i, interrupt)| {
+                let path = format!("interrupts[{}]", i);
+                Ok(Interrupt {
+                    when: resolve_predicate(registry, &interrupt.when, format!("{}.when", path))?,
+                    priority: interrupt.priority,
+                    behavior: resolve(registry, &interrupt.behavior, format!("{}.behavior", path))?,
+                })
+            })
+            .collect::<Result<_, ConfigError>>()?;
+        Ok(Self {
+            baseline,
+            interrupts,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    Syntax(serde_json::Error),
+    UnknownBehavior {
+        path: String,
+        name: String,
+        known: Vec<&'static str>,
+    },
+    UnknownPredicate {
+        path: String,
+        name: String,
+        known: Vec<&'static str>,
+    },
+    NoChildren {
+        path: String,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(error) => write!(f, "could not read strategy: {}", error),
+            ConfigError::Syntax(error) => write!(f, "invalid strategy: {}", error),
+            ConfigError::UnknownBehavior { path, name, known } => write!(
+                f,
+                "{}: unknown behavior {:?} (expected one of {})",
+                path,
+                name,
+                known.join(", "),
+            ),
+            ConfigError::UnknownPredicate { path, name, known } => write!(
+                f,
+                "{}: unknown predicate {:?} (expected one of {})",
+                path,
+                name,
+                known.join(", "),
+            ),
+            ConfigError::NoChildren { path } => write!(f, "{}: must have at least one child", path),
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+/// The on-disk format.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StrategyFile {
+    baseline: NodeFile,
+    #[serde(default)]
+    interrupts: Vec<InterruptFile>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct InterruptFile {
+    when: PredicateFile,
+    priority: Priority,
+    behavior: NodeFile,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+enum NodeFile {
+    Behavior(String),
+    Chain {
+        priority: Priority,
+        children: Vec<NodeFile>,
+    },
+    TryChoose {
+        priority: Priority,
+        choices: Vec<NodeFile>,
+    },
+    While {
+        predicate: PredicateFile,
+        child: Box<NodeFile>,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+enum PredicateFile {
+    Is(String),
+    Not(Box<PredicateFile>),
+}
+
+fn resolve(registry: &Registry, node: &NodeFile, path: String) -> Result<Node, ConfigError> {
+    match node {
+        NodeFile::Behavior(name) => match registry.behaviors.get(name.as_str()) {
+            Some(&constructor) => Ok(Node::Behavior(constructor)),
+            None => Err(ConfigError::UnknownBehavior {
+                name: name.clone(),
+                known: sorted_keys(&registry.behaviors),
+                path,
+            }),
+        },
+        NodeFile::Chain { priority, children } => Ok(Node::Chain(
+            *priority,
+            resolve_children(registry, children, format!("{}.chain", path))?,
+        )),
+        NodeFile::TryChoose { priority, choices } => Ok(Node::TryChoose(
+            *priority,
+            resolve_children(registry, choices, format!("{}.try_choose", path))?,
+        )),
+        NodeFile::While { predicate, child } => Ok(Node::While(
+            resolve_predicate(registry, predicate, format!("{}.while.predicate", path))?,
+            Box::new(resolve(registry, child, format!("{}.while.child", path))?),
+        )),
+    }
+}
+
+fn resolve_children(
+    registry: &Registry,
+    children: &[NodeFile],
+    path: String,
+) -> Result<Vec<Node>, ConfigError> {
+    if children.is_empty() {
+        return Err(ConfigError::NoChildren { path });
+    }
+    children
+        .iter()
+        .enumerate()
+        .map(|(i, child)| resolve(registry, child, format!("{}[{}]", path, i)))
+        .collect()
+}
+
+fn resolve_predicate(
+    registry: &Registry,
+    predicate: &PredicateFile,
+    path: String,
+) -> Result<PredicateNode, ConfigError> {
+    match predicate {
+        PredicateFile::Is(name) => match registry.predicates.get(name.as_str()) {
+            Some(&constructor) => Ok(PredicateNode::Is(constructor)),
+            None => Err(ConfigError::UnknownPredicate {
+                name: name.clone(),
+                known: sorted_keys(&registry.predicates),
+                path,
+            }),
+        },
+        PredicateFile::Not(inner) => Ok(PredicateNode::Not(Box::new(resolve_predicate(
+            registry,
+            inner,
+            format!("{}.not", path),
+        )?))),
+    }
+}
+
+fn sorted_keys<V>(map: &HashMap<&'static str, V>) -> Vec<&'static str> {
+    let mut keys: Vec<_> = map.keys().cloned().collect();
+    keys.sort();
+    keys
+}
+
+impl Node {
+    fn build(&self, ctx: &mut Context<'_>) -> Box<dyn Behavior> {
+        match self {
+            Node::Behavior(constructor) => constructor(ctx),
+            Node::Chain(priority, children) => Box::new(Chain::new(
+                *priority,
+                children.iter().map(|c| c.build(ctx)).collect(),
+            )),
+            Node::TryChoose(priority, choices) => Box::new(TryChoose::new(
+                *priority,
+                choices.iter().map(|c| c.build(ctx)).collect(),
+            )),
+            Node::While(predicate, child) => {
+                Box::new(While::boxed(predicate.build(), child.build(ctx)))
+            }
+        }
+    }
+}
+
+impl PredicateNode {
+    fn build(&self) -> Box<dyn Predicate> {
+        match self {
+            PredicateNode::Is(constructor) => constructor(),
+            PredicateNode::Not(inner) => Box::new(Not(inner.build())),
+        }
+    }
+}
+
+/// A `Strategy` built from a `StrategyConfig`.
+pub struct ConfiguredStrategy {
+    baseline: Node,
+    interrupts: Vec<(Box<dyn Predicate>, Interrupt)>,
+}
+
+impl ConfiguredStrategy {
+    pub fn new(config: StrategyConfig) -> Self {
+        Self {
+            baseline: config.baseline,
+            interrupts: config
+                .interrupts
+                .into_iter()
+                .map(|i| (i.when.build(), i))
+                .collect(),
+        }
+    }
+}
+
+impl Strategy for ConfiguredStrategy {
+    fn baseline(&mut self, ctx: &mut Context<'_>) -> Box<dyn Behavior> {
+        self.baseline.build(ctx)
+    }
+
+    fn interrupt(
+        &mut self,
+        ctx: &mut Context<'_>,
+        current: &dyn Behavior,
+    ) -> Option<Box<dyn Behavior>> {
+        for (when, interrupt) in &mut self.interrupts {
+            if current.priority() < interrupt.priority && when.evaluate(ctx) {
+                ctx.eeg.log(
+                    name_of_type!(ConfiguredStrategy),
+                    format!("interrupting because {}", when.name()),
+                );
+                let behavior = interrupt.behavior.build(ctx);
+                return Some(Box::new(Chain::new(interrupt.priority, vec![behavior])));
+            }
+        }
+        None
+    }
+}
+
+struct Not(Box<dyn Predicate>);
+
+impl Predicate for Not {
+    fn name(&self) -> &str {
+        name_of_type!(Not)
+    }
+
+    fn evaluate(&mut self, ctx: &mut Context<'_>) -> bool {
+        !self.0.evaluate(ctx)
+    }
+}
+
+struct IsKickoff;
+
+impl Predicate for IsKickoff {
+    fn name(&self) -> &str {
+        name_of_type!(IsKickoff)
+    }
+
+    fn evaluate(&mut self, ctx: &mut Context<'_>) -> bool {
+        PreKickoff::is_kickoff(&ctx.packet.GameBall)
+    }
+}
+
+struct IsAirborne;
+
+impl Predicate for IsAirborne {
+    fn name(&self) -> &str {
+        name_of_type!(IsAirborne)
+    }
+
+    fn evaluate(&mut self, ctx: &mut Context<'_>) -> bool {
+        !ctx.me().OnGround
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        behavior::higher_order::NullBehavior,
+        eeg::EEG,
+        helpers::ball::ChipBallPrediction,
+        routing::test::fixed_packet,
+        strategy::{
+            config::{ConfigError, ConfiguredStrategy, IsAirborne, Registry, StrategyConfig},
+            strategy::Strategy,
+            Action, Context, Game, Priority, Scenario,
+        },
+    };
+
+    #[test]
+    fn loads_the_example() {
+        let json = include_str!("../../strategies/soccar.json");
+        StrategyConfig::from_json(json, &Registry::standard()).unwrap();
+    }
+
+    #[test]
+    fn unknown_behavior() {
+        let json = r#"{
+            "baseline": {"try_choose": {"priority": "Idle", "choices": [
+                {"behavior": "Offense"},
+                {"behavior": "Ofense"}
+            ]}}
+        }"#;
+        let error = StrategyConfig::from_json(json, &Registry::standard())
+            .err()
+            .unwrap();
+        match error {
+            ConfigError::UnknownBehavior {
+                ref path, ref name, ..
+            } => {
+                assert_eq!(path, "baseline.try_choose[1]");
+                assert_eq!(name, "Ofense");
+            }
+            _ => panic!("wrong error: {}", error),
+        }
+        assert!(error.to_string().contains("expected one of"));
+    }
+
+    #[test]
+    fn unknown_predicate() {
+        let json = r#"{
+            "baseline": {"behavior": "Offense"},
+            "interrupts": [{
+                "when": {"not": {"is": "Whatever"}},
+                "priority": "Save",
+                "behavior": {"behavior": "Defense"}
+            }]
+        }"#;
+        let error = StrategyConfig::from_json(json, &Registry::standard())
+            .err()
+            .unwrap();
+        match error {
+            ConfigError::UnknownPredicate { ref path, .. } => {
+                assert_eq!(path, "interrupts[0].when.not");
+            }
+            _ => panic!("wrong error: {}", error),
+        }
+    }
+
+    #[test]
+    fn empty_chain() {
+        let json = r#"{"baseline": {"chain": {"priority": "Idle", "children": []}}}"#;
+        match StrategyConfig::from_json(json, &Registry::standard()) {
+            Err(ConfigError::NoChildren { path }) => assert_eq!(path, "baseline.chain"),
+            _ => panic!("expected an error"),
+        }
+    }
+
+    #[test]
+    fn bad_priority() {
+        let json = r#"{"baseline": {"chain": {"priority": "Urgent", "children": []}}}"#;
+        match StrategyConfig::from_json(json, &Registry::standard()) {
+            Err(ConfigError::Syntax(_)) => {}
+            _ => panic!("expected an error"),
+        }
+    }
+
+    #[test]
+    fn builds_and_runs_behaviors() {
+        let json = r#"{
+            "baseline": {"while": {
+                "predicate": {"not": {"is": "IsAirborne"}},
+                "child": {"chain": {"priority": "Idle", "children": [
+                    {"behavior": "Null"}
+                ]}}
+            }},
+            "interrupts": [{
+                "when": {"not": {"is": "IsAirborne"}},
+                "priority": "Save",
+                "behavior": {"behavior": "Null"}
+            }]
+        }"#;
+        let mut registry = Registry::new();
+        registry
+            .behavior("Null", |_| Box::new(NullBehavior::new()))
+            .predicate("IsAirborne", || Box::new(IsAirborne));
+        let config = StrategyConfig::from_json(json, &registry).unwrap();
+        let mut strategy = ConfiguredStrategy::new(config);
+
+        let packet = fixed_packet();
+        let game = Game::fake_soccar(&packet, 0);
+        let ball_predictor = ChipBallPrediction::new();
+        let scenario = Scenario::new(&game, &ball_predictor, &packet);
+        let mut eeg = EEG::new();
+        let mut last_quick_chat = 0.0;
+        eeg.begin(&packet);
+        let mut ctx = Context::new(&game, &packet, &scenario, &mut eeg, &mut last_quick_chat);
+
+        let mut baseline = strategy.baseline(&mut ctx);
+        assert_eq!(baseline.name(), "While");
+        assert_eq!(baseline.priority(), Priority::Idle);
+        // We're on the ground, so the `While` keeps running its child.
+        match baseline.execute_old(&mut ctx) {
+            Action::Yield(_) => {}
+            _ => panic!("expected a yield"),
+        }
+        assert_eq!(baseline.active_child().unwrap().1.name(), "Chain");
+
+        // The interrupt outranks the baseline, but not itself.
+        let interrupt = strategy.interrupt(&mut ctx, &*baseline).unwrap();
+        assert_eq!(interrupt.priority(), Priority::Save);
+        assert!(strategy.interrupt(&mut ctx, &*interrupt).is_none());
+    }
+}
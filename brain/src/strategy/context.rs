@@ -4,6 +4,10 @@ use crate::{
 };
 use common::prelude::*;
 
+/// Quick chats closer together than this are dropped, so taunts can't spam the
+/// chat.
+pub const QUICK_CHAT_INTERVAL: f32 = 0.75;
+
 pub struct Context<'a> {
     pub packet: &'a common::halfway_house::LiveDataPacket,
     pub game: &'a Game<'a>,
@@ -49,6 +53,7 @@ impl<'a> Context<'a> {
             packet: self.packet,
             game: self.game,
             scenario: &self.scenario,
+            last_quick_chat: *self.last_quick_chat,
         };
         (ctx, self.eeg)
     }
@@ -85,6 +90,8 @@ pub struct Context2<'c, 's> {
     pub packet: &'c common::halfway_house::LiveDataPacket,
     pub game: &'c Game<'c>,
     pub scenario: &'s Scenario<'c>,
+    /// The game time of our most recent quick chat.
+    pub last_quick_chat: f32,
 }
 
 impl<'c, 's> Context2<'c, 's> {
@@ -92,4 +99,9 @@ impl<'c, 's> Context2<'c, 's> {
     pub fn me(&self) -> &common::halfway_house::PlayerInfo {
         self.game.me()
     }
+
+    /// Returns true if a quick chat sent now would not be throttled.
+    pub fn can_quick_chat(&self) -> bool {
+        self.packet.GameInfo.TimeSeconds - self.last_quick_chat >= QUICK_CHAT_INTERVAL
+    }
 }
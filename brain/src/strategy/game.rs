@@ -43,6 +43,26 @@ impl<'a> Game<'a> {
         }
     }
 
+    /// A standard soccar game with no boost pickups, for tests and offline
+    /// simulations which don't have a real `FieldInfo` to hand.
+    #[cfg(any(test, feature = "tune"))]
+    pub fn fake_soccar(
+        packet: &'a common::halfway_house::LiveDataPacket,
+        player_index: usize,
+    ) -> Self {
+        let team = Team::from_ffi(packet.GameCars[player_index].Team);
+        Self {
+            packet,
+            mode: rlbot::GameMode::Soccer,
+            pitch: &*DFH_STADIUM,
+            player_index,
+            team,
+            enemy_team: team.opposing(),
+            boost_dollars: Box::new([]),
+            me_vehicle: &OCTANE,
+        }
+    }
+
     pub fn pitch(&self) -> &Pitch {
         self.pitch
     }
@@ -155,7 +175,7 @@ impl Team {
         }
     }
 
-    fn opposing(self) -> Self {
+    pub fn opposing(self) -> Self {
         match self {
             Team::Blue => Team::Orange,
             Team::Orange => Team::Blue,
@@ -0,0 +1,145 @@
+use crate::{params, strategy::Team};
+use common::halfway_house::LiveDataPacket;
+
+/// Where the match stands, as far as how much risk to take.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Phase {
+    /// Plenty of time left, or no clock at all.
+    Normal,
+    /// Ahead late in the game. Play it safe and run out the clock.
+    Protecting,
+    /// Behind late in the game. Commit to attacks.
+    Chasing,
+    /// Tied with seconds left. Overtime (and a fresh kickoff) is coming, so
+    /// there's nothing to gain from a risky challenge.
+    Buzzer,
+    /// Next goal wins.
+    Overtime,
+}
+
+/// The clock and the score, and what they mean for how we should play.
+#[derive(Copy, Clone, Debug)]
+pub struct MatchState {
+    pub phase: Phase,
+    /// Our score minus theirs.
+    pub lead: i32,
+    /// Seconds left in regulation, or `None` if the clock doesn't matter
+    /// (unlimited time, or overtime). When the clock hits zero with the ball
+    /// still in play, this stays at zero, the latest it can possibly be.
+    pub seconds_remaining: Option<f32>,
+    /// How much risk to take, from -1 (none at all) through 0 (normal play) to
+    /// 1 (all in). Decisions scale their thresholds by this.
+    pub risk: f32,
+}
+
+impl MatchState {
+    pub fn new(packet: &LiveDataPacket, team: Team) -> Self {
+        let score = |team: Team| {
+            packet
+                .Teams
+                .iter()
+                .find(|t| t.TeamIndex == i32::from(team.to_ffi()))
+                .map(|t| t.Score)
+                .unwrap_or(0)
+        };
+        let lead = score(team) - score(team.opposing());
+
+        let seconds_remaining = if packet.GameInfo.UnlimitedTime || packet.GameInfo.OverTime {
+            None
+        } else {
+            Some(packet.GameInfo.GameTimeRemaining.max(0.0))
+        };
+
+        let (phase, risk) = if packet.GameInfo.OverTime {
+            (Phase::Overtime, params::OVERTIME_RISK.get())
+        } else if let Some(seconds) = seconds_remaining {
+            classify(lead, seconds)
+        } else {
+            (Phase::Normal, 0.0)
+        };
+
+        Self {
+            phase,
+            lead,
+            seconds_remaining,
+            risk: risk.max(-1.0).min(1.0),
+        }
+    }
+
+    /// Scale a threshold which should loosen as we take more risk, e.g. how
+    /// much possession the enemy needs before we worry about them. At full
+    /// risk it doubles; at no risk it drops to zero.
+    pub fn loosen(&self, threshold: f32) -> f32 {
+        threshold * (1.0 + self.risk)
+    }
+}
+
+fn classify(lead: i32, seconds: f32) -> (Phase, f32) {
+    let late = params::LATE_GAME_SECONDS.get();
+    if lead > 0 && seconds < late {
+        // The closer to the end, the more there is to lose.
+        (Phase::Protecting, -(1.0 - seconds / late))
+    } else if lead < 0 && seconds < late * (-lead) as f32 {
+        // The more goals we need, the earlier we need to start pushing.
+        let window = late * (-lead) as f32;
+        (Phase::Chasing, 1.0 - seconds / window)
+    } else if lead == 0 && seconds < params::BUZZER_SECONDS.get() {
+        (Phase::Buzzer, params::BUZZER_RISK.get())
+    } else {
+        (Phase::Normal, 0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        routing::test::fixed_packet,
+        strategy::{
+            match_state::{MatchState, Phase},
+            Team,
+        },
+    };
+
+    fn state(us: i32, them: i32, seconds: f32) -> MatchState {
+        let mut packet = fixed_packet();
+        packet.Teams[0].Score = us;
+        packet.Teams[1].Score = them;
+        packet.GameInfo.GameTimeRemaining = seconds;
+        MatchState::new(&packet, Team::Blue)
+    }
+
+    #[test]
+    fn phases() {
+        assert_eq!(state(0, 0, 200.0).phase, Phase::Normal);
+        assert_eq!(state(2, 1, 200.0).phase, Phase::Normal);
+        assert_eq!(state(2, 1, 30.0).phase, Phase::Protecting);
+        assert_eq!(state(1, 2, 30.0).phase, Phase::Chasing);
+        assert_eq!(state(0, 2, 90.0).phase, Phase::Chasing);
+        assert_eq!(state(1, 1, 5.0).phase, Phase::Buzzer);
+        // Time is up, but the ball hasn't touched the ground yet.
+        assert_eq!(state(1, 1, 0.0).phase, Phase::Buzzer);
+        assert_eq!(state(2, 1, 0.0).phase, Phase::Protecting);
+        assert_eq!(state(2, 1, 0.0).risk, -1.0);
+
+        let mut packet = fixed_packet();
+        packet.GameInfo.UnlimitedTime = true;
+        packet.GameInfo.GameTimeRemaining = 0.0;
+        assert_eq!(MatchState::new(&packet, Team::Blue).phase, Phase::Normal);
+
+        let mut packet = fixed_packet();
+        packet.GameInfo.OverTime = true;
+        assert_eq!(MatchState::new(&packet, Team::Blue).phase, Phase::Overtime);
+    }
+
+    #[test]
+    fn risk_grows_with_urgency() {
+        assert_eq!(state(0, 0, 200.0).risk, 0.0);
+        assert!(state(2, 1, 10.0).risk < state(2, 1, 50.0).risk);
+        assert!(state(2, 1, 50.0).risk < 0.0);
+        assert!(state(1, 2, 10.0).risk > state(1, 2, 50.0).risk);
+        assert!(state(1, 2, 50.0).risk > 0.0);
+
+        let protecting = state(2, 1, 10.0);
+        assert!(protecting.loosen(2.0) < 2.0);
+    }
+}
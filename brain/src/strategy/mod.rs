@@ -1,21 +1,30 @@
+#[cfg(test)]
+pub use crate::strategy::behavior2::Effects;
 pub use crate::strategy::{
-    behavior::{Action, Behavior, Priority},
+    behavior::{Action, Behavior, Priority, Prospect, StackFrame},
+    behavior2::{Adapter, Behavior2, Outcome, Sink},
+    config::{ConfigError, ConfiguredStrategy, Registry, StrategyConfig},
     context::{Context, Context2},
     dropshot::Dropshot,
     game::{
         infer_game_mode, BoostPickup, Game, Goal, Team, Vehicle, SOCCAR_GOAL_BLUE,
         SOCCAR_GOAL_ORANGE,
     },
-    pitch::Pitch,
+    match_state::{MatchState, Phase},
+    pitch::{Pitch, DFH_STADIUM},
     runner::Runner,
     scenario::Scenario,
     soccar::Soccar,
+    utility::{UtilityCandidate, UtilitySelector},
 };
 
 mod behavior;
+mod behavior2;
+mod config;
 mod context;
 mod dropshot;
 mod game;
+mod match_state;
 #[cfg(test)]
 pub mod null;
 mod pitch;
@@ -24,3 +33,4 @@ mod scenario;
 mod soccar;
 #[allow(clippy::module_inception)]
 mod strategy;
+mod utility;
@@ -1,12 +1,18 @@
 use crate::{
     eeg::{color, Drawable},
-    strategy::{strategy::Strategy, Action, Behavior, Context},
+    strategy::{strategy::Strategy, Action, Behavior, Context, StackFrame},
 };
+use itertools::Itertools;
 use nameof::name_of_type;
 
 pub struct Runner {
     strategy: Box<dyn Strategy>,
     current: Option<Box<dyn Behavior>>,
+    /// The stack of nested behaviors as of the end of the last frame.
+    stack: Vec<StackFrame>,
+    /// For each level of `stack`, the address of the behavior at that level,
+    /// its child index, and the time it first appeared there.
+    stack_ids: Vec<(usize, Option<usize>, f32)>,
 }
 
 impl Runner {
@@ -14,6 +20,8 @@ impl Runner {
         Self {
             strategy: Box::new(strategy),
             current: None,
+            stack: Vec::new(),
+            stack_ids: Vec::new(),
         }
     }
 
@@ -22,12 +30,20 @@ impl Runner {
         Self {
             strategy: Box::new(crate::strategy::null::NullStrategy::new()),
             current: Some(Box::new(current)),
+            stack: Vec::new(),
+            stack_ids: Vec::new(),
         }
     }
 
     pub fn execute_old(&mut self, ctx: &mut Context<'_>) -> common::halfway_house::PlayerInput {
         self.exec(0, ctx)
     }
+
+    /// The stack of nested behaviors as of the end of the last frame, from the
+    /// root down.
+    pub fn stack(&self) -> &[StackFrame] {
+        &self.stack
+    }
 }
 
 impl Behavior for Runner {
@@ -35,6 +51,11 @@ impl Behavior for Runner {
         name_of_type!(Runner)
     }
 
+    fn active_child(&self) -> Option<(usize, &dyn Behavior)> {
+        let current = self.current.as_ref()?;
+        Some((0, &**current))
+    }
+
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
         Action::Yield(self.exec(0, ctx))
     }
@@ -52,7 +73,10 @@ impl Runner {
             .draw(Drawable::print(behavior.blurb(), color::YELLOW));
 
         match behavior.execute_old(ctx) {
-            Action::Yield(i) => i,
+            Action::Yield(i) => {
+                self.update_stack(ctx);
+                i
+            }
             Action::TailCall(b) => {
                 ctx.eeg.log(self.name(), format!("> {}", b.name()));
                 self.current = Some(b);
@@ -63,10 +87,18 @@ impl Runner {
                 self.current = Some(b);
                 self.exec(depth + 1, ctx)
             }
-            Action::Return | Action::Abort => {
+            Action::Return(reason) => {
+                ctx.eeg.log(
+                    self.name(),
+                    format!("< {} returned: {}", self.describe_path(), reason),
+                );
+                self.current = None;
+                self.exec(depth + 1, ctx)
+            }
+            Action::Abort(reason) => {
                 ctx.eeg.log(
                     self.name(),
-                    format!("< {}", self.current.as_ref().unwrap().name()),
+                    format!("< {} aborted: {}", self.describe_path(), reason),
                 );
                 self.current = None;
                 self.exec(depth + 1, ctx)
@@ -96,4 +128,135 @@ impl Runner {
 
         &mut **self.current.as_mut().unwrap()
     }
+
+    /// The names of the current behavior and its active descendants, as they
+    /// stand right now (which may differ from `stack` mid-frame).
+    fn describe_path(&self) -> String {
+        walk(&**self.current.as_ref().unwrap())
+            .into_iter()
+            .map(|(_, b)| b.name())
+            .join(" > ")
+    }
+
+    /// Rebuild `stack` from the current behavior. A level keeps its age as
+    /// long as it, and every level above it, holds the same behavior as in the
+    /// previous frame.
+    fn update_stack(&mut self, ctx: &mut Context<'_>) {
+        let now = ctx.packet.GameInfo.TimeSeconds;
+        let mut stack = Vec::new();
+        let mut stack_ids = Vec::new();
+        let mut same_so_far = true;
+        for (level, (child_index, behavior)) in walk(&**self.current.as_ref().unwrap())
+            .into_iter()
+            .enumerate()
+        {
+            let address = behavior as *const dyn Behavior as *const () as usize;
+            let since = match self.stack_ids.get(level) {
+                // A freed behavior's address can be reused by its replacement, so
+                // check the name too.
+                Some(&(a, i, since))
+                    if same_so_far
+                        && a == address
+                        && i == child_index
+                        && self.stack[level].name == behavior.name() =>
+                {
+                    since
+                }
+                _ => {
+                    same_so_far = false;
+                    now
+                }
+            };
+            stack.push(StackFrame {
+                name: behavior.name().to_string(),
+                priority: behavior.priority(),
+                age: now - since,
+                child_index,
+            });
+            stack_ids.push((address, child_index, since));
+        }
+        self.stack = stack;
+        self.stack_ids = stack_ids;
+        ctx.eeg.track_stack(&self.stack);
+    }
+}
+
+/// Return `root` and then each active child in turn, along with its index
+/// within its parent.
+fn walk(root: &dyn Behavior) -> Vec<(Option<usize>, &dyn Behavior)> {
+    let mut result = vec![(None, root)];
+    let mut behavior = root;
+    while let Some((index, child)) = behavior.active_child() {
+        result.push((Some(index), child));
+        behavior = child;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        behavior::{higher_order::Chain, movement::Yielder},
+        eeg::EEG,
+        helpers::ball::ChipBallPrediction,
+        routing::test::fixed_packet,
+        strategy::{Action, Behavior, Context, Game, Priority, Runner, Scenario},
+    };
+
+    struct Quitter;
+
+    impl Behavior for Quitter {
+        fn name(&self) -> &str {
+            stringify!(Quitter)
+        }
+
+        fn execute_old(&mut self, _ctx: &mut Context<'_>) -> Action {
+            Action::abort("giving up")
+        }
+    }
+
+    /// Run `runner` for one frame at `time`.
+    fn tick(runner: &mut Runner, eeg: &mut EEG, time: f32) {
+        let mut packet = fixed_packet();
+        packet.GameInfo.TimeSeconds = time;
+        let game = Game::fake_soccar(&packet, 0);
+        let ball_predictor = ChipBallPrediction::new();
+        let scenario = Scenario::new(&game, &ball_predictor, &packet);
+        let mut last_quick_chat = 0.0;
+        eeg.begin(&packet);
+        let mut ctx = Context::new(&game, &packet, &scenario, eeg, &mut last_quick_chat);
+        runner.execute_old(&mut ctx);
+        eeg.show(&packet);
+    }
+
+    #[test]
+    fn tracks_the_stack() {
+        let mut runner = Runner::with_current(Chain::new(Priority::Save, vec![
+            Box::new(Yielder::new(1.0, Default::default())),
+            Box::new(Quitter),
+        ]));
+        let mut eeg = EEG::new();
+        eeg.track_events();
+
+        tick(&mut runner, &mut eeg, 10.0);
+        tick(&mut runner, &mut eeg, 10.5);
+        let names: Vec<_> = runner.stack().iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["Chain", "Yielder"]);
+        assert_eq!(runner.stack()[0].child_index, None);
+        assert_eq!(runner.stack()[1].child_index, Some(0));
+        assert_eq!(runner.stack()[0].priority, Priority::Save);
+        assert_eq!(runner.stack()[1].age, 0.5);
+
+        // The `Yielder` returns, then the `Quitter` aborts the whole `Chain`, and
+        // the `NullStrategy` takes over.
+        tick(&mut runner, &mut eeg, 11.0);
+        let names: Vec<_> = runner.stack().iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["NullBehavior"]);
+        assert_eq!(runner.stack()[0].age, 0.0);
+
+        let history = eeg.stack_history.as_ref().unwrap();
+        assert!(history.was_inside("Yielder", 0.7));
+        assert!(!history.was_inside("Yielder", 1.0));
+        assert!(history.was_inside("NullBehavior", 1.0));
+    }
 }
@@ -3,7 +3,8 @@ use crate::{
         ball::{BallFrame, BallPredictor, BallTrajectory},
         intercept::{naive_intercept_penalty, NaiveIntercept},
     },
-    strategy::{game::Game, Goal},
+    params,
+    strategy::{game::Game, Goal, MatchState},
     utils::{Wall, WallRayCalculator},
 };
 use common::prelude::*;
@@ -27,12 +28,10 @@ pub struct Scenario<'a> {
     enemy_shoot_score_seconds: LazyCell<f32>,
     slightly_panicky_retreat: LazyCell<bool>,
     very_panicky_retreat: LazyCell<bool>,
+    match_state: LazyCell<MatchState>,
 }
 
 impl<'a> Scenario<'a> {
-    pub const POSSESSION_CONTESTABLE: f32 = 0.5;
-    pub const POSSESSION_SATURATED: f32 = 5.0;
-
     pub fn new(
         game: &'a Game<'_>,
         ball_predictor: &'a dyn BallPredictor,
@@ -52,6 +51,7 @@ impl<'a> Scenario<'a> {
             enemy_shoot_score_seconds: LazyCell::new(),
             slightly_panicky_retreat: LazyCell::new(),
             very_panicky_retreat: LazyCell::new(),
+            match_state: LazyCell::new(),
         }
     }
 
@@ -88,6 +88,13 @@ impl<'a> Scenario<'a> {
         *self.possession.borrow().unwrap()
     }
 
+    /// The clock and the score, and how much risk they call for.
+    pub fn match_state(&self) -> MatchState {
+        *self
+            .match_state
+            .borrow_with(|| MatchState::new(self.packet, self.game.team))
+    }
+
     fn race(&self) {
         let blitz_me = simulate_ball_blitz(self.ball_prediction(), self.game.me());
         let blitz_enemy = self
@@ -102,7 +109,7 @@ impl<'a> Scenario<'a> {
             _ => {
                 // To avoid mexican standoffs, just pretend we have full possession so we go
                 // for the ball.
-                Self::POSSESSION_SATURATED
+                params::POSSESSION_SATURATED.get()
             }
         };
 
@@ -282,6 +289,7 @@ fn simulate_ball_blitz(
         ball_vel: ball.vel,
         car_loc: ball.loc,
         car_speed: ball.vel.norm(),
+        car_boost: sim.boost(),
         data: (),
     })
 }
@@ -8,31 +8,49 @@ use crate::{
         taunt::{PodiumBlastoff, PodiumSpew, PodiumStare, SaltWhileDemolished, TurtleSpin},
         PreKickoff,
     },
+    params,
     routing::{
         behavior::FollowRoute,
-        plan::WallIntercept,
+        plan::{GetDollar, WallIntercept},
         recover::{IsSkidding, MatchIsEnded, RoundIsNotActive},
     },
-    strategy::{scenario::Scenario, strategy::Strategy, Behavior, Context, Priority},
+    strategy::{
+        strategy::Strategy, Adapter, Behavior, Context, Priority, UtilityCandidate, UtilitySelector,
+    },
     utils::Wall,
 };
 use common::prelude::*;
-use derive_new::new;
 use nameof::name_of_type;
 use vec_box::vec_box;
 
-#[derive(new)]
-pub struct Soccar;
+/// Scores within this margin of the running behavior's score are not worth
+/// switching for.
+const HYSTERESIS: f32 = 0.1;
+
+pub struct Soccar {
+    selector: UtilitySelector,
+}
+
+impl Soccar {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            selector: UtilitySelector::new(candidates(), HYSTERESIS),
+        }
+    }
+}
 
 impl Strategy for Soccar {
     fn baseline(&mut self, ctx: &mut Context<'_>) -> Box<dyn Behavior> {
         // If we have no wheel contact, i.e. we're floating in the air
         if !ctx.me().OnGround {
+            self.selector.forget();
             return Box::new(Land::new());
         }
 
         // If we have wheel contact, but we're not on the ground
         if !GetToFlatGround::on_flat_ground(ctx.me()) {
+            self.selector.forget();
             return Box::new(TryChoose::new(Priority::Idle, vec_box![
                 Chain::new(Priority::Strike, vec_box![
                     FollowRoute::new(WallIntercept::new().forbid_goal_walls(true))
@@ -43,31 +61,7 @@ impl Strategy for Soccar {
             ]));
         }
 
-        match ctx.scenario.push_wall() {
-            Wall::OwnGoal | Wall::OwnBackWall => {
-                ctx.eeg.log(
-                    name_of_type!(Soccar),
-                    "path to ball extrapolates to back wall",
-                );
-                return Box::new(Defense::new());
-            }
-            _ => {}
-        }
-
-        if ctx.scenario.slightly_panicky_retreat()
-            && ctx.scenario.possession() < Scenario::POSSESSION_CONTESTABLE
-        {
-            ctx.eeg
-                .log(name_of_type!(Soccar), "slightly_panicky_retreat");
-            return Box::new(Defense::new());
-        }
-
-        if Defense::enemy_can_attack(ctx) {
-            ctx.eeg.log(name_of_type!(Soccar), "enemy_can_attack");
-            return Box::new(Defense::new());
-        }
-
-        Box::new(Offense::new())
+        self.selector.choose(ctx)
     }
 
     fn interrupt(
@@ -85,6 +79,7 @@ impl Strategy for Soccar {
                 } else {
                     While::new(MatchIsEnded, PodiumBlastoff::new())
                 };
+                self.selector.forget();
                 return Some(Box::new(celebrate));
             }
             return None;
@@ -95,98 +90,185 @@ impl Strategy for Soccar {
         // goes too slow.
         if current.priority() < Priority::Force && PreKickoff::is_kickoff(&ctx.packet.GameBall) {
             ctx.eeg.log(name_of_type!(Soccar), "forcing kickoff");
+            self.selector.forget();
             return Some(Box::new(Chain::new(Priority::Force, vec![Box::new(
                 PreKickoff::new(),
             )])));
         }
 
-        if current.priority() < Priority::Strike
-            && Defense::enemy_can_shoot(ctx)
-            && GetToFlatGround::on_flat_ground(ctx.me())
-            && !IsSkidding.evaluate(&ctx.me().into())
-            && ctx.scenario.possession().abs() < Scenario::POSSESSION_CONTESTABLE
-        {
-            ctx.eeg.log(
-                name_of_type!(Soccar),
-                format!(
-                    "enemy can shoot, possession = {:.2}, going for 50/50",
-                    ctx.scenario.possession(),
-                ),
-            );
-            return Some(Box::new(Chain::new(Priority::Strike, vec![Box::new(
-                FiftyFifty::new(),
-            )])));
-        }
+        self.selector.reconsider(ctx, current)
+    }
+}
 
-        if current.priority() < Priority::Save
-            && ctx
-                .scenario
-                .impending_concede()
-                .map(|b| b.t < 5.0)
-                .unwrap_or(false)
-            && GetToFlatGround::on_flat_ground(&ctx.me())
-            && !IsSkidding.evaluate(&ctx.me().into())
-        {
-            ctx.eeg
-                .log(name_of_type!(Soccar), "impending concede, trying to save");
-            return Some(Box::new(Chain::new(Priority::Save, vec![Box::new(
-                Defense::new(),
-            )])));
-        }
+/// Offense comes first, since it's the fallback when nothing else applies.
+fn candidates() -> Vec<UtilityCandidate> {
+    vec![
+        UtilityCandidate::new(
+            name_of_type!(Offense),
+            Priority::Idle,
+            |_ctx| 0.2,
+            |_ctx| Box::new(Offense::new()),
+        ),
+        UtilityCandidate::new(
+            name_of_type!(Defense),
+            Priority::Idle,
+            score_defense,
+            |_ctx| Box::new(Defense::new()),
+        ),
+        UtilityCandidate::new(
+            name_of_type!(GetDollar),
+            Priority::Idle,
+            score_boost_grab,
+            |ctx| {
+                let (ctx, eeg) = ctx.split();
+                Box::new(FollowRoute::new(GetDollar::smart(&ctx, eeg)))
+            },
+        ),
+        UtilityCandidate::new(
+            name_of_type!(FiftyFifty),
+            Priority::Strike,
+            score_fifty_fifty,
+            |_ctx| Box::new(FiftyFifty::new()),
+        ),
+        UtilityCandidate::new("Save", Priority::Save, score_save, |_ctx| {
+            Box::new(Defense::new())
+        }),
+        UtilityCandidate::new("Retreat", Priority::Defense, score_retreat, |_ctx| {
+            Box::new(Defense::new())
+        }),
+        UtilityCandidate::new("Taunt", Priority::Taunt, score_taunt, build_taunt),
+    ]
+}
 
-        if current.priority() < Priority::Defense
-            && Defense::enemy_can_shoot(ctx)
-            && GetToFlatGround::on_flat_ground(ctx.me())
-            && !IsSkidding.evaluate(&ctx.me().into())
-            && ctx.scenario.possession() < -Scenario::POSSESSION_CONTESTABLE
-        {
-            ctx.eeg.log(
-                name_of_type!(Soccar),
-                format!(
-                    "enemy can shoot, possession = {:.2}, going to defense",
-                    ctx.scenario.possession(),
-                ),
-            );
-            return Some(Box::new(Chain::new(Priority::Defense, vec![Box::new(
-                Defense::new(),
-            )])));
-        }
+fn can_drive(ctx: &mut Context<'_>) -> bool {
+    GetToFlatGround::on_flat_ground(ctx.me()) && !IsSkidding.evaluate(&ctx.me().into())
+}
 
-        if current.priority() < Priority::Defense && ctx.scenario.very_panicky_retreat() {
-            ctx.eeg.log(name_of_type!(Soccar), "very_panicky_retreat");
-            return Some(Box::new(Chain::new(Priority::Defense, vec![Box::new(
-                Defense::new(),
-            )])));
-        }
+fn score_defense(ctx: &mut Context<'_>) -> f32 {
+    match ctx.scenario.push_wall() {
+        Wall::OwnGoal | Wall::OwnBackWall => return 0.35,
+        _ => {}
+    }
+    if ctx.scenario.slightly_panicky_retreat()
+        && ctx.scenario.possession() < params::POSSESSION_CONTESTABLE.get()
+    {
+        return 0.3;
+    }
+    if Defense::enemy_can_attack(ctx) {
+        return 0.3;
+    }
+    0.0
+}
+
+/// Worth a detour only when we're nearly empty and the enemy isn't about to
+/// touch the ball.
+fn score_boost_grab(ctx: &mut Context<'_>) -> f32 {
+    const LOW_BOOST: f32 = 20.0;
+    const ENEMY_SLACK: f32 = 3.0;
+
+    let boost = ctx.me().Boost as f32;
+    if boost >= LOW_BOOST {
+        return 0.0;
+    }
+    let enemy_time = ctx
+        .scenario
+        .enemy_intercept()
+        .map(|(_, i)| i.time)
+        .unwrap_or(ENEMY_SLACK);
+    if enemy_time < ENEMY_SLACK {
+        return 0.0;
+    }
+    0.25 * (1.0 - boost / LOW_BOOST)
+}
 
-        if current.priority() < Priority::Taunt && ctx.me().Demolished {
-            return Some(Box::new(SaltWhileDemolished::new()));
+/// Best when possession is dead even.
+fn score_fifty_fifty(ctx: &mut Context<'_>) -> f32 {
+    let possession = ctx.scenario.possession().abs();
+    if possession >= params::POSSESSION_CONTESTABLE.get()
+        || ctx.scenario.match_state().risk < FiftyFifty::MIN_RISK
+        || !Defense::enemy_can_shoot(ctx)
+        || !can_drive(ctx)
+    {
+        return 0.0;
+    }
+    0.7 + 0.3 * (1.0 - possession / params::POSSESSION_CONTESTABLE.get())
+}
+
+/// More urgent the sooner the ball would go in.
+fn score_save(ctx: &mut Context<'_>) -> f32 {
+    const HORIZON: f32 = 5.0;
+
+    let t = some_or_else!(ctx.scenario.impending_concede().map(|b| b.t), {
+        return 0.0;
+    });
+    if t >= HORIZON || !can_drive(ctx) {
+        return 0.0;
+    }
+    0.6 + 0.3 * (1.0 - t / HORIZON)
+}
+
+fn score_retreat(ctx: &mut Context<'_>) -> f32 {
+    if ctx.scenario.possession() < -params::POSSESSION_CONTESTABLE.get()
+        && Defense::enemy_can_shoot(ctx)
+        && can_drive(ctx)
+    {
+        let deficit = (-ctx.scenario.possession() - params::POSSESSION_CONTESTABLE.get())
+            / (params::POSSESSION_SATURATED.get() - params::POSSESSION_CONTESTABLE.get());
+        return 0.45 + 0.15 * deficit.min(1.0);
+    }
+    if ctx.scenario.very_panicky_retreat() {
+        return 0.45;
+    }
+    0.0
+}
+
+#[derive(Copy, Clone)]
+enum Taunt {
+    Salt,
+    Unstoppable,
+    ScoringVerySoon,
+    SpinBetweenRounds,
+    WaitBetweenRounds,
+}
+
+/// Pick the taunt that fits the moment, along with its score.
+fn choose_taunt(ctx: &mut Context<'_>) -> Option<(f32, Taunt)> {
+    if ctx.me().Demolished {
+        Some((1.0, Taunt::Salt))
+    } else if UnstoppableScore.evaluate(ctx) && commanding_lead(ctx) {
+        Some((0.95, Taunt::Unstoppable))
+    } else if ScoringVerySoon.evaluate(ctx) {
+        Some((0.9, Taunt::ScoringVerySoon))
+    } else if !ctx.packet.GameInfo.RoundActive {
+        if commanding_lead(ctx) && ball_in_enemy_half(ctx) {
+            Some((0.85, Taunt::SpinBetweenRounds))
+        } else {
+            Some((0.85, Taunt::WaitBetweenRounds))
         }
-        if current.priority() < Priority::Taunt
-            && UnstoppableScore.evaluate(ctx)
-            && commanding_lead(ctx)
-        {
+    } else {
+        None
+    }
+}
+
+fn score_taunt(ctx: &mut Context<'_>) -> f32 {
+    choose_taunt(ctx).map_or(0.0, |(score, _)| score)
+}
+
+fn build_taunt(ctx: &mut Context<'_>) -> Box<dyn Behavior> {
+    let taunt = choose_taunt(ctx).map_or(Taunt::WaitBetweenRounds, |(_, taunt)| taunt);
+    match taunt {
+        Taunt::Salt => Box::new(Adapter::new(SaltWhileDemolished::new())),
+        Taunt::Unstoppable => {
             let spin = TurtleSpin::new().quick_chat_probability(0.75);
-            return Some(Box::new(While::new(UnstoppableScore, spin)));
-        }
-        if current.priority() < Priority::Taunt && ScoringVerySoon.evaluate(ctx) {
-            // Maybe do some wacky twists and stuff that might look cool.
-            let spin = TurtleSpin::new();
-            return Some(Box::new(While::new(ScoringVerySoon, spin)));
+            Box::new(While::new(UnstoppableScore, spin))
         }
-        if current.priority() < Priority::Taunt && !ctx.packet.GameInfo.RoundActive {
-            let behavior = if commanding_lead(ctx) && ball_in_enemy_half(ctx) {
-                While::new(RoundIsNotActive, TurtleSpin::new())
-            } else {
-                While::new(
-                    RoundIsNotActive,
-                    Yielder::new(9999.0, Default::default()).priority(Priority::Taunt),
-                )
-            };
-            return Some(Box::new(behavior));
-        }
-
-        None
+        // Maybe do some wacky twists and stuff that might look cool.
+        Taunt::ScoringVerySoon => Box::new(While::new(ScoringVerySoon, TurtleSpin::new())),
+        Taunt::SpinBetweenRounds => Box::new(While::new(RoundIsNotActive, TurtleSpin::new())),
+        Taunt::WaitBetweenRounds => Box::new(While::new(
+            RoundIsNotActive,
+            Yielder::new(9999.0, Default::default()).priority(Priority::Taunt),
+        )),
     }
 }
 
@@ -195,7 +277,7 @@ fn ball_in_enemy_half(ctx: &mut Context<'_>) -> bool {
         < (ctx.packet.GameBall.Physics.loc_2d() - ctx.game.own_goal().center_2d).norm()
 }
 
-struct UnstoppableScore;
+pub struct UnstoppableScore;
 
 impl Predicate for UnstoppableScore {
     fn name(&self) -> &str {
@@ -213,7 +295,7 @@ impl Predicate for UnstoppableScore {
     }
 }
 
-struct ScoringVerySoon;
+pub struct ScoringVerySoon;
 
 impl Predicate for ScoringVerySoon {
     fn name(&self) -> &str {
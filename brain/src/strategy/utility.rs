@@ -0,0 +1,202 @@
+use crate::{
+    behavior::higher_order::Chain,
+    eeg::{color, Drawable},
+    strategy::{Behavior, Context, Priority},
+};
+use nameof::name_of_type;
+
+/// A behavior the `UtilitySelector` can choose, along with how much it wants
+/// to be chosen.
+///
+/// Both functions are plain `fn`s so that existing behaviors can be adapted
+/// with a closure, without touching the behaviors themselves.
+pub struct UtilityCandidate {
+    name: &'static str,
+    priority: Priority,
+    score: fn(&mut Context<'_>) -> f32,
+    build: fn(&mut Context<'_>) -> Box<dyn Behavior>,
+}
+
+impl UtilityCandidate {
+    /// `score` should return a value in `[0, 1]`, where 0 means "not
+    /// applicable right now". The candidate will only interrupt behaviors
+    /// with a lower `priority` than its own.
+    pub fn new(
+        name: &'static str,
+        priority: Priority,
+        score: fn(&mut Context<'_>) -> f32,
+        build: fn(&mut Context<'_>) -> Box<dyn Behavior>,
+    ) -> Self {
+        Self {
+            name,
+            priority,
+            score,
+            build,
+        }
+    }
+}
+
+/// Pick whichever candidate scores highest, but stick with the incumbent
+/// unless a challenger beats it by a margin.
+pub struct UtilitySelector {
+    candidates: Vec<UtilityCandidate>,
+    hysteresis: f32,
+    /// The candidate whose behavior is (as far as we know) currently running.
+    active: Option<usize>,
+}
+
+impl UtilitySelector {
+    /// The first candidate is the fallback if nothing scores above zero.
+    pub fn new(candidates: Vec<UtilityCandidate>, hysteresis: f32) -> Self {
+        assert!(!candidates.is_empty());
+        Self {
+            candidates,
+            hysteresis,
+            active: None,
+        }
+    }
+
+    /// Choose a fresh behavior, e.g. because the previous one finished.
+    pub fn choose(&mut self, ctx: &mut Context<'_>) -> Box<dyn Behavior> {
+        let scores = self.score_all(ctx);
+        let index = pick(&scores, None, 0.0).unwrap_or(0);
+        self.print(ctx, &scores, Some(index));
+        self.activate(ctx, index)
+    }
+
+    /// Replace `current` if another candidate has become clearly better.
+    pub fn reconsider(
+        &mut self,
+        ctx: &mut Context<'_>,
+        current: &dyn Behavior,
+    ) -> Option<Box<dyn Behavior>> {
+        let scores = self.score_all(ctx);
+        let priorities: Vec<_> = self.candidates.iter().map(|c| c.priority).collect();
+        let eligible = eligible_scores(&scores, &priorities, current.priority(), self.active);
+        let index =
+            pick(&eligible, self.active, self.hysteresis).filter(|&i| Some(i) != self.active);
+        self.print(ctx, &scores, index.or(self.active));
+
+        let index = index?;
+        ctx.eeg.log(
+            name_of_type!(UtilitySelector),
+            format!(
+                "switching to {} ({:.2})",
+                self.candidates[index].name, scores[index]
+            ),
+        );
+        Some(self.activate(ctx, index))
+    }
+
+    /// Call this when something other than the selector replaces the current
+    /// behavior.
+    pub fn forget(&mut self) {
+        self.active = None;
+    }
+
+    fn score_all(&self, ctx: &mut Context<'_>) -> Vec<f32> {
+        self.candidates.iter().map(|c| (c.score)(ctx)).collect()
+    }
+
+    fn print(&self, ctx: &mut Context<'_>, scores: &[f32], chosen: Option<usize>) {
+        for (i, (candidate, &score)) in self.candidates.iter().zip(scores).enumerate() {
+            let color = if Some(i) == chosen {
+                color::GREEN
+            } else {
+                color::WHITE
+            };
+            ctx.eeg.draw(Drawable::print(
+                format!("{}: {:.2}", candidate.name, score),
+                color,
+            ));
+        }
+    }
+
+    fn activate(&mut self, ctx: &mut Context<'_>, index: usize) -> Box<dyn Behavior> {
+        self.active = Some(index);
+        let candidate = &self.candidates[index];
+        let behavior = (candidate.build)(ctx);
+        if candidate.priority == Priority::Idle {
+            behavior
+        } else {
+            Box::new(Chain::new(candidate.priority, vec![behavior]))
+        }
+    }
+}
+
+/// Zero out the candidates which can't interrupt a behavior running at
+/// `current` priority, so they don't stand in the way of ones that can. The
+/// incumbent keeps its score, since it is what's already running.
+fn eligible_scores(
+    scores: &[f32],
+    priorities: &[Priority],
+    current: Priority,
+    incumbent: Option<usize>,
+) -> Vec<f32> {
+    scores
+        .iter()
+        .zip(priorities)
+        .enumerate()
+        .map(|(i, (&score, &priority))| {
+            if Some(i) == incumbent || current < priority {
+                score
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+/// Return the best-scoring index, unless it fails to beat the incumbent by
+/// more than `hysteresis`, in which case return the incumbent. Scores of zero
+/// never win, and an incumbent scoring zero is always replaced.
+fn pick(scores: &[f32], incumbent: Option<usize>, hysteresis: f32) -> Option<usize> {
+    let (best, &best_score) = scores
+        .iter()
+        .enumerate()
+        .filter(|&(_, &s)| s > 0.0)
+        .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())?;
+    match incumbent {
+        Some(i) if scores[i] > 0.0 && best_score <= scores[i] + hysteresis => Some(i),
+        _ => Some(best),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strategy::{
+        utility::{eligible_scores, pick},
+        Priority,
+    };
+
+    #[test]
+    fn picks_the_best() {
+        assert_eq!(pick(&[0.1, 0.5, 0.3], None, 0.1), Some(1));
+    }
+
+    #[test]
+    fn zero_never_wins() {
+        assert_eq!(pick(&[0.0, 0.0], None, 0.0), None);
+        assert_eq!(pick(&[0.0, 0.0], Some(1), 0.1), None);
+    }
+
+    #[test]
+    fn hysteresis_keeps_the_incumbent() {
+        assert_eq!(pick(&[0.4, 0.45], Some(0), 0.1), Some(0));
+        assert_eq!(pick(&[0.4, 0.55], Some(0), 0.1), Some(1));
+    }
+
+    #[test]
+    fn outranked_candidates_do_not_block_others() {
+        // The top scorer can't interrupt defense, but the save can.
+        let scores = [0.2, 0.9, 0.7];
+        let priorities = [Priority::Idle, Priority::Idle, Priority::Save];
+        let eligible = eligible_scores(&scores, &priorities, Priority::Defense, Some(0));
+        assert_eq!(pick(&eligible, Some(0), 0.1), Some(2));
+    }
+
+    #[test]
+    fn inapplicable_incumbent_is_replaced() {
+        assert_eq!(pick(&[0.0, 0.05], Some(0), 0.1), Some(1));
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{
+    eeg::EEG,
+    helpers::ball::ChipBallPrediction,
+    strategy::{Context, Game, Runner, Scenario, Soccar},
+    tune::{
+        sim::{Goal, Simulation},
+        Case,
+    },
+};
+use common::rl;
+use std::fmt::{self, Write};
+
+/// The brain decides once per this many physics ticks, which matches the rate
+/// the framework sends packets.
+const TICKS_PER_DECISION: usize = 2;
+
+/// How much each outcome counts towards a case's fitness.
+#[derive(Copy, Clone)]
+pub struct Weights {
+    pub goal_scored: f32,
+    pub goal_conceded: f32,
+    /// Per second until our first touch (or for the whole case, if we never
+    /// touch the ball).
+    pub time_to_first_touch: f32,
+}
+
+impl Default for Weights {
+    fn default() -> Self {
+        Self {
+            goal_scored: 1.0,
+            goal_conceded: 3.0,
+            time_to_first_touch: 0.1,
+        }
+    }
+}
+
+pub struct CaseResult {
+    pub name: String,
+    pub goal: Option<Goal>,
+    pub first_touch: Option<f32>,
+    pub fitness: f32,
+}
+
+/// The results of running every case with one set of parameters.
+pub struct Evaluation {
+    pub cases: Vec<CaseResult>,
+}
+
+impl Evaluation {
+    /// The total fitness across all cases. Higher is better.
+    pub fn fitness(&self) -> f32 {
+        self.cases.iter().map(|c| c.fitness).sum()
+    }
+
+    pub fn goals(&self, goal: Goal) -> usize {
+        self.cases.iter().filter(|c| c.goal == Some(goal)).count()
+    }
+}
+
+impl fmt::Display for Evaluation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut table = String::new();
+        for case in &self.cases {
+            let goal = match case.goal {
+                Some(Goal::Scored) => "scored",
+                Some(Goal::Conceded) => "conceded",
+                None => "-",
+            };
+            let touch = match case.first_touch {
+                Some(t) => format!("{:.2}s", t),
+                None => "-".to_string(),
+            };
+            writeln!(
+                table,
+                "  {:<40} {:>8} {:>8} {:>8.3}",
+                case.name, goal, touch, case.fitness,
+            )?;
+        }
+        writeln!(
+            f,
+            "fitness {:.3}, scored {}, conceded {}",
+            self.fitness(),
+            self.goals(Goal::Scored),
+            self.goals(Goal::Conceded),
+        )?;
+        write!(f, "{}", table)
+    }
+}
+
+/// Run every case with the current parameters.
+///
+/// Parameters are global, so evaluations can't run concurrently.
+pub fn evaluate(cases: &[Case], weights: &Weights) -> Evaluation {
+    Evaluation {
+        cases: cases.iter().map(|c| run_case(c, weights)).collect(),
+    }
+}
+
+/// Play out one case with the standard soccar strategy.
+pub fn run_case(case: &Case, weights: &Weights) -> CaseResult {
+    let mut sim = Simulation::new(case);
+    let mut runner = Runner::new(Soccar::new());
+    let ball_predictor = ChipBallPrediction::new();
+    let mut eeg = EEG::new();
+    let mut last_quick_chat = 0.0;
+
+    while sim.elapsed() < case.duration && sim.goal().is_none() {
+        let packet = sim.packet();
+        let game = Game::fake_soccar(&packet, 0);
+        let scenario = Scenario::new(&game, &ball_predictor, &packet);
+        eeg.begin(&packet);
+        let mut ctx = Context::new(&game, &packet, &scenario, &mut eeg, &mut last_quick_chat);
+        let input = runner.execute_old(&mut ctx);
+        eeg.show(&packet);
+
+        for _ in 0..TICKS_PER_DECISION {
+            sim.step(&input, rl::PHYSICS_DT);
+        }
+    }
+
+    let goal = sim.goal();
+    let first_touch = sim.first_touch();
+    let mut fitness = -weights.time_to_first_touch * first_touch.unwrap_or(case.duration);
+    match goal {
+        Some(Goal::Scored) => fitness += weights.goal_scored,
+        Some(Goal::Conceded) => fitness -= weights.goal_conceded,
+        None => {}
+    }
+    CaseResult {
+        name: case.name.clone(),
+        goal,
+        first_touch,
+        fitness,
+    }
+}
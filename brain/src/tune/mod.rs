@@ -0,0 +1,19 @@
+//! Offline parameter tuning.
+//!
+//! Play out a suite of cases in a crude in-process simulation, score the
+//! outcomes, and search for the `params` which score best. See the `tune`
+//! binary.
+
+pub use self::{
+    evaluate::{evaluate, run_case, CaseResult, Evaluation, Weights},
+    scenario::TestScenario,
+    search::{best, search, Dimension, Method, SearchSpace, Trial},
+    sim::{Goal, Simulation},
+    suite::{standard_suite, Case},
+};
+
+mod evaluate;
+mod scenario;
+mod search;
+mod sim;
+mod suite;
@@ -3,6 +3,8 @@ use common::prelude::*;
 use nalgebra::{Point3, Rotation3, UnitQuaternion, Vector3};
 use std::{f32::consts::PI, fs::File, path::Path};
 
+/// The starting state of a 1v1 situation. Used by the integration tests, and
+/// by the offline tuner.
 pub struct TestScenario {
     pub ball_loc: Point3<f32>,
     pub ball_rot: Rotation3<f32>,
@@ -49,7 +51,7 @@ impl TestScenario {
         let tick = RecordingTick::parse(file)
             .find(|r| time <= r.time && r.time < time + 1.0)
             .unwrap();
-        let result = Self {
+        Self {
             ball_loc: tick.ball.loc,
             ball_rot: tick.ball.rot.to_rotation_matrix(),
             ball_vel: tick.ball.vel,
@@ -63,9 +65,7 @@ impl TestScenario {
             enemy_vel: tick.players[1].state.vel,
             enemy_ang_vel: tick.players[1].state.ang_vel,
             ..Default::default()
-        };
-        println!("{}", result.to_source());
-        result
+        }
     }
 
     pub fn ball(&self) -> RecordingRigidBodyState {
@@ -95,7 +95,8 @@ impl TestScenario {
         }
     }
 
-    fn to_source(&self) -> String {
+    /// Rust source which recreates this scenario, for pasting into a test.
+    pub fn to_source(&self) -> String {
         format!(
             "TestScenario {{
             ball_loc: Point3::new({}, {}, {}),
@@ -0,0 +1,287 @@
+use crate::params::{self, ParamError};
+use serde_derive::Deserialize;
+use std::{cmp::Ordering, f32::consts::PI, fs, path::Path};
+
+/// One parameter to search over, and the range to search.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Dimension {
+    pub name: String,
+    pub min: f32,
+    pub max: f32,
+    /// Round to whole numbers, for integer parameters.
+    #[serde(default)]
+    pub integer: bool,
+}
+
+impl Dimension {
+    /// Map `x` from `[0, 1]` into this dimension's range.
+    fn denormalize(&self, x: f32) -> f32 {
+        let value = self.min + (self.max - self.min) * x;
+        if self.integer {
+            value.round()
+        } else {
+            value
+        }
+    }
+
+    fn normalize(&self, value: f32) -> f32 {
+        ((value - self.min) / (self.max - self.min))
+            .max(0.0)
+            .min(1.0)
+    }
+}
+
+/// The parameters to tune. The file format is a JSON array:
+///
+/// ```json
+/// [
+///     {"name": "POSSESSION_CONTESTABLE", "min": 0.25, "max": 1.0},
+///     {"name": "SLOW_FRAME_MS", "min": 4, "max": 16, "integer": true}
+/// ]
+/// ```
+#[derive(Clone, Debug)]
+pub struct SearchSpace {
+    pub dimensions: Vec<Dimension>,
+}
+
+impl SearchSpace {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ParamError> {
+        let json = fs::read_to_string(path).map_err(ParamError::Io)?;
+        Self::from_json(&json)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, ParamError> {
+        let dimensions: Vec<Dimension> = serde_json::from_str(json).map_err(ParamError::Syntax)?;
+        let known = params::all();
+        for dim in &dimensions {
+            if !known.iter().any(|p| p.name() == dim.name) {
+                return Err(ParamError::Unknown {
+                    name: dim.name.clone(),
+                });
+            }
+            if dim.min >= dim.max {
+                return Err(ParamError::Invalid {
+                    name: dim.name.clone(),
+                    value: format!("{}..{}", dim.min, dim.max),
+                    reason: "min must be less than max".to_string(),
+                });
+            }
+        }
+        Ok(Self { dimensions })
+    }
+
+    /// The current value of each parameter, clamped into range.
+    pub fn current(&self) -> Vec<f32> {
+        let known = params::all();
+        self.dimensions
+            .iter()
+            .map(|dim| {
+                let param = known.iter().find(|p| p.name() == dim.name).unwrap();
+                let value: f32 = param.value().parse().unwrap_or(dim.min);
+                let value = value.max(dim.min).min(dim.max);
+                if dim.integer {
+                    value.round()
+                } else {
+                    value
+                }
+            })
+            .collect()
+    }
+
+    /// Set every parameter in the space to the given values.
+    pub fn apply(&self, values: &[f32]) -> Result<(), ParamError> {
+        for (dim, &value) in self.dimensions.iter().zip(values) {
+            params::set_by_name(&dim.name, &value.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Method {
+    /// Sample uniformly from the whole space.
+    Random,
+    /// A cross-entropy-style evolution strategy: sample a population around a
+    /// mean, then move the mean and shrink or grow the spread (separately for
+    /// each dimension) based on the best members.
+    Evolve,
+}
+
+/// One evaluated point in the space.
+#[derive(Clone, Debug)]
+pub struct Trial {
+    pub values: Vec<f32>,
+    pub fitness: f32,
+}
+
+/// Search `space` for the values which maximize `fitness`, starting from
+/// `start`. Every trial is returned, in order; the first is `start` itself.
+pub fn search(
+    space: &SearchSpace,
+    start: &[f32],
+    method: Method,
+    budget: usize,
+    seed: u64,
+    mut fitness: impl FnMut(&[f32]) -> f32,
+) -> Vec<Trial> {
+    const POPULATION: usize = 8;
+    const ELITE: usize = 3;
+    const MIN_SPREAD: f32 = 0.02;
+
+    let mut rng = Rng::new(seed);
+    let mut trials = Vec::with_capacity(budget);
+    let mut run = |values: Vec<f32>, trials: &mut Vec<Trial>| {
+        let fitness = fitness(&values);
+        trials.push(Trial { values, fitness });
+    };
+
+    run(start.to_vec(), &mut trials);
+
+    let mut mean: Vec<f32> = space
+        .dimensions
+        .iter()
+        .zip(start)
+        .map(|(dim, &v)| dim.normalize(v))
+        .collect();
+    let mut spread = vec![0.3; mean.len()];
+
+    while trials.len() < budget {
+        let generation_start = trials.len();
+        let size = POPULATION.min(budget - trials.len());
+        for _ in 0..size {
+            let values = space
+                .dimensions
+                .iter()
+                .enumerate()
+                .map(|(i, dim)| {
+                    let x = match method {
+                        Method::Random => rng.uniform(),
+                        Method::Evolve => (mean[i] + spread[i] * rng.normal()).max(0.0).min(1.0),
+                    };
+                    dim.denormalize(x)
+                })
+                .collect();
+            run(values, &mut trials);
+        }
+
+        if method == Method::Evolve {
+            let mut generation: Vec<_> = trials[generation_start..].iter().collect();
+            generation.sort_by(|a, b| compare(b, a));
+            let elite = &generation[..ELITE.min(generation.len())];
+            for (i, dim) in space.dimensions.iter().enumerate() {
+                let xs: Vec<_> = elite.iter().map(|t| dim.normalize(t.values[i])).collect();
+                let n = xs.len() as f32;
+                mean[i] = xs.iter().sum::<f32>() / n;
+                let variance = xs.iter().map(|x| (x - mean[i]).powi(2)).sum::<f32>() / n;
+                spread[i] = variance.sqrt().max(MIN_SPREAD);
+            }
+        }
+    }
+
+    trials
+}
+
+/// The trial with the highest fitness.
+pub fn best(trials: &[Trial]) -> Option<&Trial> {
+    trials.iter().max_by(|a, b| compare(a, b))
+}
+
+fn compare(a: &Trial, b: &Trial) -> Ordering {
+    a.fitness.partial_cmp(&b.fitness).unwrap_or(Ordering::Equal)
+}
+
+/// A small, seedable xorshift generator, so runs are reproducible.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        // xorshift gets stuck at zero.
+        Rng(seed.wrapping_mul(0x9E3779B97F4A7C15) | 1)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Uniform in `[0, 1)`.
+    fn uniform(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Standard normal, via Box-Muller.
+    fn normal(&mut self) -> f32 {
+        let u1 = 1.0 - self.uniform();
+        let u2 = self.uniform();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        params::ParamError,
+        tune::search::{best, search, Method, SearchSpace},
+    };
+
+    #[test]
+    fn parses_the_space() {
+        let space = SearchSpace::from_json(
+            r#"[
+                {"name": "POSSESSION_CONTESTABLE", "min": 0.25, "max": 1.0},
+                {"name": "SLOW_FRAME_MS", "min": 4, "max": 16, "integer": true}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(space.dimensions.len(), 2);
+        assert_eq!(space.current(), vec![0.5, 8.0]);
+
+        match SearchSpace::from_json(r#"[{"name": "NOPE", "min": 0, "max": 1}]"#) {
+            Err(ParamError::Unknown { name }) => assert_eq!(name, "NOPE"),
+            _ => panic!("expected an error"),
+        }
+        match SearchSpace::from_json(r#"[{"name": "SLOW_FRAME_MS", "min": 4, "max": 4}]"#) {
+            Err(ParamError::Invalid { .. }) => {}
+            _ => panic!("expected an error"),
+        }
+    }
+
+    #[test]
+    fn evolve_finds_the_peak() {
+        let space = SearchSpace::from_json(
+            r#"[
+                {"name": "POSSESSION_CONTESTABLE", "min": 0, "max": 10},
+                {"name": "POSSESSION_SATURATED", "min": 0, "max": 10}
+            ]"#,
+        )
+        .unwrap();
+        let peak = |v: &[f32]| -(v[0] - 7.0).powi(2) - (v[1] - 2.0).powi(2);
+
+        let trials = search(&space, &[5.0, 5.0], Method::Evolve, 200, 1, peak);
+        assert_eq!(trials.len(), 200);
+        assert_eq!(trials[0].values, vec![5.0, 5.0]);
+        let winner = best(&trials).unwrap();
+        assert!((winner.values[0] - 7.0).abs() < 0.5, "{:?}", winner);
+        assert!((winner.values[1] - 2.0).abs() < 0.5, "{:?}", winner);
+
+        // The same seed gives the same search.
+        let again = search(&space, &[5.0, 5.0], Method::Evolve, 200, 1, peak);
+        assert_eq!(best(&again).unwrap().values, winner.values);
+    }
+
+    #[test]
+    fn random_stays_in_range() {
+        let space = SearchSpace::from_json(
+            r#"[{"name": "SLOW_FRAME_MS", "min": 4, "max": 16, "integer": true}]"#,
+        )
+        .unwrap();
+        let trials = search(&space, &[8.0], Method::Random, 50, 7, |v| v[0]);
+        for trial in &trials {
+            assert!(trial.values[0] >= 4.0 && trial.values[0] <= 16.0);
+            assert_eq!(trial.values[0], trial.values[0].round());
+        }
+    }
+}
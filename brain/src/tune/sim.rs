@@ -0,0 +1,350 @@
+use crate::tune::Case;
+use chip::Ball;
+use collect::RecordingRigidBodyState;
+use common::{
+    halfway_house::{
+        BallInfo, GameInfo, LiveDataPacket, Physics, PlayerInfo, PlayerInput, Rotator, TeamInfo,
+    },
+    prelude::*,
+    rl,
+};
+use nalgebra::{clamp, Point3, UnitQuaternion, Vector3};
+use simulate::{ball_car_distance, Car1D};
+
+/// How much of the closing speed between car and ball ends up in the ball
+/// after a touch. This is a guess, not a measurement: it gets the direction
+/// right and the speed in the right ballpark, nothing more.
+const TOUCH_RESTITUTION: f32 = 1.5;
+/// Braking deceleration, which `Car1D` doesn't model.
+const BRAKE_ACCEL: f32 = 3500.0;
+/// How much tighter the car turns with the handbrake held.
+const HANDBRAKE_TURN: f32 = 1.5;
+
+/// The first goal of a simulation, from our point of view.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Goal {
+    Scored,
+    Conceded,
+}
+
+/// A crude, deterministic, in-process 1v1 simulation.
+///
+/// The ball is simulated properly (with `chip`), but everything else is rough:
+///
+/// - Our car only drives on the ground. Jumps, dodges and aerials are ignored.
+/// - Touches use a made-up `TOUCH_RESTITUTION`, so the speed of a hit (and
+///   whether a shot goes in) is not trustworthy.
+/// - The enemy replays its recorded path without reacting to anything, so it
+///   never challenges, shoots or defends on purpose.
+///
+/// That makes it good for parameters about getting to the ball and where to
+/// wait on the ground, and useless for ones about shooting, aerials, or
+/// reading the enemy's attacks. Keep the search space to the former.
+///
+/// We are always the blue team (player 0), so we defend the negative-y goal.
+pub struct Simulation {
+    start_time: f32,
+    elapsed: f32,
+    ball: Ball,
+    car: SimCar,
+    enemy: Vec<(f32, RecordingRigidBodyState)>,
+    first_touch: Option<f32>,
+    goal: Option<Goal>,
+}
+
+impl Simulation {
+    pub fn new(case: &Case) -> Self {
+        let mut ball = Ball::new();
+        ball.set_pos(case.ball.loc);
+        ball.set_vel(case.ball.vel);
+        ball.set_omega(case.ball.ang_vel);
+
+        Self {
+            start_time: case.start_time,
+            elapsed: 0.0,
+            ball,
+            car: SimCar::new(&case.car, case.boost),
+            enemy: case.enemy.clone(),
+            first_touch: None,
+            goal: None,
+        }
+    }
+
+    /// Seconds since the start of the simulation.
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// The time of our first touch, in seconds since the start.
+    pub fn first_touch(&self) -> Option<f32> {
+        self.first_touch
+    }
+
+    /// Once a goal is scored, the simulation stops.
+    pub fn goal(&self) -> Option<Goal> {
+        self.goal
+    }
+
+    pub fn packet(&self) -> LiveDataPacket {
+        let enemy = self.enemy_state();
+        let enemy = PlayerInfo {
+            Physics: physics(enemy.loc, &enemy.rot, enemy.vel, enemy.ang_vel),
+            OnGround: enemy.loc.z < 50.0,
+            Team: 1,
+            Boost: 100,
+            ..Default::default()
+        };
+        LiveDataPacket {
+            GameCars: vec![self.car.player_info(), enemy].into_iter().collect(),
+            NumCars: 2,
+            GameBall: BallInfo {
+                Physics: physics(
+                    self.ball.pos(),
+                    &UnitQuaternion::identity(),
+                    self.ball.vel(),
+                    Vector3::zeros(),
+                ),
+            },
+            GameInfo: GameInfo {
+                TimeSeconds: self.start_time + self.elapsed,
+                GameTimeRemaining: 300.0,
+                RoundActive: true,
+                ..Default::default()
+            },
+            Teams: vec![
+                TeamInfo {
+                    TeamIndex: 0,
+                    Score: 0,
+                },
+                TeamInfo {
+                    TeamIndex: 1,
+                    Score: 0,
+                },
+            ]
+            .into_iter()
+            .collect(),
+            NumTeams: 2,
+        }
+    }
+
+    pub fn step(&mut self, input: &PlayerInput, dt: f32) {
+        if self.goal.is_some() {
+            return;
+        }
+
+        self.elapsed += dt;
+        self.car.step(input, dt);
+        self.ball.step(dt);
+
+        if touch(
+            &mut self.ball,
+            self.car.loc,
+            self.car.quat(),
+            self.car.vel(),
+        ) && self.first_touch.is_none()
+        {
+            self.first_touch = Some(self.elapsed);
+        }
+        let enemy = self.enemy_state().clone();
+        touch(&mut self.ball, enemy.loc, enemy.rot, enemy.vel);
+
+        self.goal = goal(self.ball.pos());
+    }
+
+    /// The enemy's latest recorded state. Once the recording runs out, the
+    /// enemy stays where it is.
+    fn enemy_state(&self) -> &RecordingRigidBodyState {
+        let now = self.start_time + self.elapsed;
+        let index = self.enemy.iter().rposition(|&(t, _)| t <= now).unwrap_or(0);
+        &self.enemy[index].1
+    }
+}
+
+/// A car which can only drive on the ground.
+struct SimCar {
+    loc: Point3<f32>,
+    yaw: f32,
+    speed: f32,
+    boost: f32,
+}
+
+impl SimCar {
+    fn new(state: &RecordingRigidBodyState, boost: f32) -> Self {
+        let yaw = state.rot.to_rotation_matrix().yaw();
+        let forward = Vector3::new(yaw.cos(), yaw.sin(), 0.0);
+        Self {
+            loc: Point3::new(state.loc.x, state.loc.y, rl::OCTANE_NEUTRAL_Z),
+            yaw,
+            speed: clamp(state.vel.dot(&forward), 0.0, rl::CAR_MAX_SPEED),
+            boost: clamp(boost, 0.0, 100.0),
+        }
+    }
+
+    fn quat(&self) -> UnitQuaternion<f32> {
+        UnitQuaternion::from_axis_angle(&Vector3::z_axis(), self.yaw)
+    }
+
+    fn vel(&self) -> Vector3<f32> {
+        Vector3::new(self.yaw.cos(), self.yaw.sin(), 0.0) * self.speed
+    }
+
+    fn step(&mut self, input: &PlayerInput, dt: f32) {
+        let turn = if input.Handbrake { HANDBRAKE_TURN } else { 1.0 };
+        let yaw_rate = self.speed * chip::max_curvature(self.speed) * input.Steer * turn;
+        self.yaw += clamp(
+            yaw_rate,
+            -rl::CAR_MAX_ANGULAR_VELOCITY,
+            rl::CAR_MAX_ANGULAR_VELOCITY,
+        ) * dt;
+
+        let distance = self.advance(input, dt);
+        self.loc += Vector3::new(self.yaw.cos(), self.yaw.sin(), 0.0) * distance;
+        self.loc.x = clamp(self.loc.x, -rl::FIELD_MAX_X, rl::FIELD_MAX_X);
+        self.loc.y = clamp(self.loc.y, -rl::FIELD_MAX_Y, rl::FIELD_MAX_Y);
+    }
+
+    /// Update speed and boost, and return the distance travelled. `Car1D` only
+    /// supports all-or-nothing inputs, so round to the nearest one it knows.
+    fn advance(&mut self, input: &PlayerInput, dt: f32) -> f32 {
+        if input.Throttle <= -0.5 && !input.Boost {
+            let speed = (self.speed - BRAKE_ACCEL * dt).max(0.0);
+            let distance = (self.speed + speed) / 2.0 * dt;
+            self.speed = speed;
+            return distance;
+        }
+
+        let throttle = if input.Boost || input.Throttle >= 0.5 {
+            1.0
+        } else {
+            0.0
+        };
+        let mut car1d = Car1D::new().with_speed(self.speed).with_boost(self.boost);
+        car1d.advance(dt, throttle, input.Boost);
+        self.speed = car1d.speed();
+        self.boost = car1d.boost().max(0.0);
+        car1d.distance()
+    }
+
+    fn player_info(&self) -> PlayerInfo {
+        PlayerInfo {
+            Physics: physics(self.loc, &self.quat(), self.vel(), Vector3::zeros()),
+            OnGround: true,
+            Team: 0,
+            Boost: self.boost as i32,
+            ..Default::default()
+        }
+    }
+}
+
+/// If the car is touching the ball, bounce the ball off it and return true.
+fn touch(
+    ball: &mut Ball,
+    car_loc: Point3<f32>,
+    car_rot: UnitQuaternion<f32>,
+    car_vel: Vector3<f32>,
+) -> bool {
+    if ball_car_distance(ball.pos(), car_loc, car_rot) > 0.0 {
+        return false;
+    }
+    let normal = (ball.pos() - car_loc).normalize();
+    let closing = (car_vel - ball.vel()).dot(&normal);
+    if closing > 0.0 {
+        ball.set_vel(ball.vel() + normal * closing * TOUCH_RESTITUTION);
+    }
+    true
+}
+
+fn goal(ball_loc: Point3<f32>) -> Option<Goal> {
+    if ball_loc.y.abs() < rl::FIELD_MAX_Y + rl::BALL_RADIUS {
+        return None;
+    }
+    if ball_loc.y < 0.0 {
+        Some(Goal::Conceded)
+    } else {
+        Some(Goal::Scored)
+    }
+}
+
+fn physics(
+    loc: Point3<f32>,
+    rot: &UnitQuaternion<f32>,
+    vel: Vector3<f32>,
+    ang_vel: Vector3<f32>,
+) -> Physics {
+    let (pitch, yaw, roll) = rot.to_rotation_matrix().to_unreal_angles();
+    Physics {
+        Location: vector3(loc.coords),
+        Rotation: Rotator {
+            Pitch: pitch,
+            Yaw: yaw,
+            Roll: roll,
+        },
+        Velocity: vector3(vel),
+        AngularVelocity: vector3(ang_vel),
+    }
+}
+
+fn vector3(v: Vector3<f32>) -> common::halfway_house::Vector3 {
+    common::halfway_house::Vector3 {
+        X: v.x,
+        Y: v.y,
+        Z: v.z,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tune::{
+        sim::{Goal, Simulation},
+        Case, TestScenario,
+    };
+    use common::{prelude::*, rl};
+    use nalgebra::{Point3, Rotation3, Vector3};
+    use std::f32::consts::PI;
+
+    fn run(case: &Case) -> Simulation {
+        let mut sim = Simulation::new(case);
+        while sim.elapsed() < case.duration && sim.goal().is_none() {
+            let input = common::halfway_house::PlayerInput {
+                Throttle: 1.0,
+                ..Default::default()
+            };
+            sim.step(&input, rl::PHYSICS_DT);
+        }
+        sim
+    }
+
+    #[test]
+    fn ball_rolls_into_our_goal() {
+        let case = Case::from_test_scenario(
+            "own goal",
+            &TestScenario {
+                ball_loc: Point3::new(0.0, -4000.0, 92.74),
+                ball_vel: Vector3::new(0.0, -2000.0, 0.0),
+                car_loc: Point3::new(3000.0, 0.0, 17.01),
+                ..Default::default()
+            },
+            3.0,
+        );
+        let sim = run(&case);
+        assert_eq!(sim.goal(), Some(Goal::Conceded));
+        assert_eq!(sim.first_touch(), None);
+    }
+
+    #[test]
+    fn driving_into_the_ball_touches_it() {
+        let case = Case::from_test_scenario(
+            "push",
+            &TestScenario {
+                ball_loc: Point3::new(0.0, 1000.0, 92.74),
+                car_loc: Point3::new(0.0, 0.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, PI / 2.0, 0.0),
+                ..Default::default()
+            },
+            3.0,
+        );
+        let sim = run(&case);
+        assert!(sim.first_touch().is_some());
+        assert!(sim.packet().GameBall.Physics.vel().y > 0.0);
+    }
+}
@@ -0,0 +1,136 @@
+use crate::tune::TestScenario;
+use brain_test_data::{recordings, OneVOneScenario};
+use collect::RecordingRigidBodyState;
+use nalgebra::{Point3, Vector3};
+
+/// How long to play out each case in the standard suite, in seconds.
+const DURATION: f32 = 6.0;
+
+/// One situation for the tuner to play out.
+#[derive(Clone)]
+pub struct Case {
+    pub name: String,
+    /// The game clock at the start of the case.
+    pub start_time: f32,
+    pub ball: RecordingRigidBodyState,
+    pub car: RecordingRigidBodyState,
+    pub boost: f32,
+    /// The enemy's path, as pairs of game time and state.
+    pub enemy: Vec<(f32, RecordingRigidBodyState)>,
+    /// How long to play out the case, in seconds.
+    pub duration: f32,
+}
+
+impl Case {
+    pub fn from_test_scenario(
+        name: impl Into<String>,
+        scenario: &TestScenario,
+        duration: f32,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            start_time: 0.0,
+            ball: scenario.ball(),
+            car: scenario.car(),
+            boost: f32::from(scenario.boost),
+            enemy: vec![(0.0, scenario.enemy())],
+            duration,
+        }
+    }
+
+    /// Start from the beginning of a recording. The ball is free from the
+    /// start, and the enemy follows its recorded path.
+    pub fn from_recording(
+        name: impl Into<String>,
+        scenario: &OneVOneScenario<'_>,
+        duration: f32,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            start_time: scenario.times[0],
+            ball: scenario.ball_states[0].clone(),
+            car: scenario.car_initial_state.clone(),
+            boost: 100.0,
+            enemy: scenario
+                .times
+                .iter()
+                .cloned()
+                .zip(scenario.enemy_states.iter().cloned())
+                .collect(),
+            duration,
+        }
+    }
+}
+
+/// The cases the tuner runs by default. These mostly stay on the ground, since
+/// that's all the simulation handles.
+pub fn standard_suite() -> Vec<Case> {
+    vec![
+        Case::from_recording(
+            "clear_defensive_ball",
+            &recordings::CLEAR_DEFENSIVE_BALL,
+            DURATION,
+        ),
+        Case::from_recording(
+            "dont_allow_long_shot",
+            &recordings::DONT_ALLOW_LONG_SHOT,
+            DURATION,
+        ),
+        Case::from_recording(
+            "let_the_ball_enter_our_corner",
+            &recordings::LET_THE_BALL_ENTER_OUR_CORNER,
+            DURATION,
+        ),
+        Case::from_recording(
+            "save_ball_rolling_towards_box",
+            &recordings::SAVE_BALL_ROLLING_TOWARDS_BOX,
+            DURATION,
+        ),
+        Case::from_recording(
+            "transition_from_defense_to_save",
+            &recordings::TRANSITION_FROM_DEFENSE_TO_SAVE,
+            DURATION,
+        ),
+        Case::from_recording(
+            "dont_delay_shot_without_possession",
+            &recordings::DONT_DELAY_SHOT_WITHOUT_POSSESSION,
+            DURATION,
+        ),
+        Case::from_recording(
+            "catching_up_to_the_play",
+            &recordings::CATCHING_UP_TO_THE_PLAY,
+            DURATION,
+        ),
+        Case::from_test_scenario(
+            "open_net",
+            &TestScenario {
+                ball_loc: Point3::new(-500.0, 3000.0, 92.74),
+                car_loc: Point3::new(0.0, 1000.0, 17.01),
+                enemy_loc: Point3::new(3000.0, 5000.0, 17.01),
+                ..Default::default()
+            },
+            DURATION,
+        ),
+        Case::from_test_scenario(
+            "race_for_a_loose_ball",
+            &TestScenario {
+                ball_loc: Point3::new(1500.0, 0.0, 92.74),
+                ball_vel: Vector3::new(0.0, -500.0, 0.0),
+                car_loc: Point3::new(0.0, -3000.0, 17.01),
+                enemy_loc: Point3::new(2000.0, 3000.0, 17.01),
+                ..Default::default()
+            },
+            DURATION,
+        ),
+        Case::from_test_scenario(
+            "ball_rolling_towards_our_goal",
+            &TestScenario {
+                ball_loc: Point3::new(800.0, -1000.0, 92.74),
+                ball_vel: Vector3::new(-200.0, -1200.0, 0.0),
+                car_loc: Point3::new(-2000.0, -2000.0, 17.01),
+                ..Default::default()
+            },
+            DURATION,
+        ),
+    ]
+}
@@ -1,9 +1,10 @@
 use common::prelude::*;
 use derive_new::new;
 use nalgebra::{Isometry3, Point2, Point3, Unit, UnitComplex, UnitQuaternion, Vector2, Vector3};
+use serde_derive::{Deserialize, Serialize};
 use std::ops::Mul;
 
-#[derive(Copy, Clone, new)]
+#[derive(Copy, Clone, new, Serialize, Deserialize)]
 pub struct Flattener {
     transform: Isometry3<f32>,
 }
@@ -3,6 +3,7 @@ use common::{math::fractionality, prelude::*};
 use euclid::{TypedPoint3D, TypedVector3D};
 use nalgebra::{Isometry3, Point2, Point3, Unit, UnitQuaternion, Vector2, Vector3};
 use plane_split::{Line as TypedLine, Plane as TypedPlane};
+use serde_derive::{Deserialize, Serialize};
 use std::f32::consts::PI;
 
 pub mod flattener;
@@ -85,7 +86,7 @@ impl From<TypedLine<f32, ()>> for Line {
     }
 }
 
-#[derive(Copy, Clone, PartialEq)]
+#[derive(Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Plane {
     pub normal: Unit<Vector3<f32>>,
     pub offset: f32,
@@ -125,7 +126,8 @@ impl Plane {
         plane.intersect(&other).map(Into::into)
     }
 
-    /// Returns a transformation which "unfolds" this plane along its intersection with another plane, such that the two planes are coplanar.
+    /// Returns a transformation which "unfolds" this plane along its
+    /// intersection with another plane, such that the two planes are coplanar.
     ///
     /// Returns `Err(())` if the planes are parallel.
     pub fn unfold(&self, target: &Plane) -> Result<Isometry3<f32>, ()> {
@@ -1,4 +1,4 @@
-use crate::eeg::EEG;
+use crate::{eeg::EEG, params};
 use nalgebra::Point3;
 use nameof::name_of_type;
 
@@ -9,7 +9,6 @@ pub struct InterceptMemory {
 }
 
 impl InterceptMemory {
-    const LOC_THRESHOLD: f32 = 100.0;
     const TIME_THRESHOLD: f32 = 0.1;
 
     pub fn new() -> Self {
@@ -48,7 +47,7 @@ impl InterceptMemory {
         let current = some_or_else!(self.current, {
             return InterceptMemoryAction::Trust;
         });
-        if (loc - current).norm() < Self::LOC_THRESHOLD {
+        if (loc - current).norm() < params::INTERCEPT_MEMORY_LOC_THRESHOLD.get() {
             return InterceptMemoryAction::Trust;
         }
         let (watch_time, watch_loc) = some_or_else!(self.watch, {
@@ -57,7 +56,7 @@ impl InterceptMemory {
         if time - watch_time >= Self::TIME_THRESHOLD {
             return InterceptMemoryAction::Replace;
         }
-        if (loc - watch_loc).norm() >= Self::LOC_THRESHOLD {
+        if (loc - watch_loc).norm() >= params::INTERCEPT_MEMORY_LOC_THRESHOLD.get() {
             return InterceptMemoryAction::Watch(watch_time, loc);
         }
         InterceptMemoryAction::Wait
@@ -358,14 +358,86 @@ impl SimpleScenario for Jump {
     }
 }
 
+/// A jump with a variable hold time and an optional second jump. This is the
+/// data a double jump model would be baked from. Times are in frames (1/120 s)
+/// so the file names stay tidy.
+pub struct DoubleJump {
+    hold_frames: i32,
+    /// The number of frames between releasing the first jump and pressing the
+    /// second, or `None` for a single jump.
+    gap_frames: Option<i32>,
+}
+
+impl DoubleJump {
+    pub fn new(hold_frames: i32, gap_frames: Option<i32>) -> Self {
+        Self {
+            hold_frames,
+            gap_frames,
+        }
+    }
+
+    pub fn all() -> Vec<Self> {
+        let mut result = Vec::new();
+        for &hold_frames in &[3, 6, 12, 24] {
+            result.push(Self::new(hold_frames, None));
+            for &gap_frames in &[2, 12, 30, 60] {
+                result.push(Self::new(hold_frames, Some(gap_frames)));
+            }
+        }
+        result
+    }
+}
+
+impl SimpleScenario for DoubleJump {
+    fn name(&self) -> String {
+        match self.gap_frames {
+            Some(gap) => format!("double_jump_hold_{}_gap_{}", self.hold_frames, gap),
+            None => format!("double_jump_hold_{}", self.hold_frames),
+        }
+    }
+
+    fn step(
+        &mut self,
+        time: f32,
+        _packet: &common::halfway_house::LiveDataPacket,
+    ) -> SimpleScenarioStepResult {
+        let start = 1.0;
+        if time < start {
+            return SimpleScenarioStepResult::Ignore(Default::default());
+        }
+        if time >= start + 2.5 {
+            return SimpleScenarioStepResult::Finish;
+        }
+
+        let frame = ((time - start) * 120.0) as i32;
+        let first = frame < self.hold_frames;
+        let second = match self.gap_frames {
+            Some(gap) => frame >= self.hold_frames + gap && frame < self.hold_frames + gap + 6,
+            None => false,
+        };
+        SimpleScenarioStepResult::Write(common::halfway_house::PlayerInput {
+            Jump: first || second,
+            ..Default::default()
+        })
+    }
+}
+
 /// I didn't bother saving a CSV of this because I don't need the detailed data.
 /// Here are the high-level numbers:
 ///
 /// * The forward dodge impulse is exactly 500 uu/s.
 /// * The time from dodge to landing always ends up between 1.2 and 1.25
 ///   seconds. (In game I will round this up to 1.333333 to be safe.)
+///
+/// `Dodge::directional` varies the stick angle too, for the model in
+/// `simulate::CarDodge`. `Dodge::directional_set` is the full grid of speeds
+/// and angles worth baking. Only dodges to the right are collected, since the
+/// left side is a mirror image.
 pub struct Dodge {
     start_speed: f32,
+    /// The stick angle in degrees, where 0 means straight forward and positive
+    /// angles are to the right.
+    angle: i32,
     phase: DodgePhase,
 }
 
@@ -379,16 +451,35 @@ enum DodgePhase {
 
 impl Dodge {
     pub fn new(start_speed: f32) -> Self {
+        Self::directional(start_speed, 0)
+    }
+
+    pub fn directional(start_speed: f32, angle: i32) -> Self {
         Self {
             start_speed,
+            angle,
             phase: DodgePhase::Accelerate,
         }
     }
+
+    pub fn directional_set() -> Vec<Self> {
+        let mut result = Vec::new();
+        for &start_speed in &[0.0, 500.0, 1000.0, 1500.0, 2000.0] {
+            for angle in (0..=180).step_by(45) {
+                result.push(Self::directional(start_speed, angle));
+            }
+        }
+        result
+    }
 }
 
 impl Scenario for Dodge {
     fn name(&self) -> String {
-        format!("dodge_speed_{}", self.start_speed)
+        if self.angle == 0 {
+            format!("dodge_speed_{}", self.start_speed)
+        } else {
+            format!("dodge_speed_{}_angle_{}", self.start_speed, self.angle)
+        }
     }
 
     fn step(
@@ -441,8 +532,10 @@ impl Scenario for Dodge {
                     return self.step(rlbot, time, packet);
                 }
 
+                let angle = (self.angle as f32).to_radians();
                 let input = common::halfway_house::PlayerInput {
-                    Pitch: -1.0,
+                    Pitch: -angle.cos(),
+                    Yaw: angle.sin(),
                     Jump: true,
                     ..Default::default()
                 };
@@ -462,6 +555,151 @@ impl Scenario for Dodge {
     }
 }
 
+/// A diagonal dodge that is canceled right away, while boosting the entire
+/// time. The dodge goes to the left; the right side is a mirror image.
+pub struct SpeedFlip {
+    start_speed: f32,
+    phase: SpeedFlipPhase,
+}
+
+enum SpeedFlipPhase {
+    Accelerate,
+    Jump(f32),
+    Wait(f32),
+    Dodge(f32),
+    Cancel(f32),
+    Land(f32),
+}
+
+impl SpeedFlip {
+    pub fn new(start_speed: f32) -> Self {
+        Self {
+            start_speed,
+            phase: SpeedFlipPhase::Accelerate,
+        }
+    }
+}
+
+impl Scenario for SpeedFlip {
+    fn name(&self) -> String {
+        format!("speed_flip_speed_{}", self.start_speed)
+    }
+
+    fn initial_state(&self) -> rlbot::DesiredGameState {
+        let mut state = game_state_default();
+        state.car_states[0]
+            .as_mut()
+            .unwrap()
+            .physics
+            .as_mut()
+            .unwrap()
+            .location = Some(rlbot::Vector3Partial::new().x(0.0).y(-5000.0).z(17.01));
+        state
+    }
+
+    fn step(
+        &mut self,
+        rlbot: &rlbot::RLBot,
+        time: f32,
+        packet: &common::halfway_house::LiveDataPacket,
+    ) -> Result<ScenarioStepResult, Box<dyn Error>> {
+        match self.phase {
+            SpeedFlipPhase::Accelerate => {
+                if packet.GameCars[0].Physics.vel().norm() >= self.start_speed {
+                    self.phase = SpeedFlipPhase::Jump(time);
+                    return self.step(rlbot, time, packet);
+                }
+
+                let input = common::halfway_house::PlayerInput {
+                    Throttle: (self.start_speed / 1000.0).min(1.0),
+                    Boost: self.start_speed > rl::CAR_NORMAL_SPEED,
+                    ..Default::default()
+                };
+                rlbot.update_player_input(0, &translate_player_input(&input))?;
+                Ok(ScenarioStepResult::Ignore)
+            }
+            SpeedFlipPhase::Jump(start) => {
+                if time - start >= 0.05 {
+                    self.phase = SpeedFlipPhase::Wait(time);
+                    return self.step(rlbot, time, packet);
+                }
+
+                let input = common::halfway_house::PlayerInput {
+                    Throttle: 1.0,
+                    Jump: true,
+                    Boost: true,
+                    ..Default::default()
+                };
+                rlbot.update_player_input(0, &translate_player_input(&input))?;
+                Ok(ScenarioStepResult::Write)
+            }
+            SpeedFlipPhase::Wait(start) => {
+                if time - start >= 2.0 / 120.0 {
+                    self.phase = SpeedFlipPhase::Dodge(time);
+                    return self.step(rlbot, time, packet);
+                }
+
+                let input = common::halfway_house::PlayerInput {
+                    Throttle: 1.0,
+                    Boost: true,
+                    ..Default::default()
+                };
+                rlbot.update_player_input(0, &translate_player_input(&input))?;
+                Ok(ScenarioStepResult::Write)
+            }
+            SpeedFlipPhase::Dodge(start) => {
+                if time - start >= 0.05 {
+                    self.phase = SpeedFlipPhase::Cancel(time);
+                    return self.step(rlbot, time, packet);
+                }
+
+                let input = common::halfway_house::PlayerInput {
+                    Throttle: 1.0,
+                    Pitch: -1.0,
+                    Yaw: -1.0,
+                    Jump: true,
+                    Boost: true,
+                    ..Default::default()
+                };
+                rlbot.update_player_input(0, &translate_player_input(&input))?;
+                Ok(ScenarioStepResult::Write)
+            }
+            SpeedFlipPhase::Cancel(start) => {
+                if packet.GameCars[0].OnGround {
+                    self.phase = SpeedFlipPhase::Land(time);
+                    return self.step(rlbot, time, packet);
+                }
+                if time - start >= 2.0 {
+                    return Ok(ScenarioStepResult::Finish);
+                }
+
+                let input = common::halfway_house::PlayerInput {
+                    Throttle: 1.0,
+                    Pitch: 1.0,
+                    Roll: 1.0,
+                    Boost: true,
+                    ..Default::default()
+                };
+                rlbot.update_player_input(0, &translate_player_input(&input))?;
+                Ok(ScenarioStepResult::Write)
+            }
+            SpeedFlipPhase::Land(start) => {
+                if time - start >= 0.5 {
+                    return Ok(ScenarioStepResult::Finish);
+                }
+
+                let input = common::halfway_house::PlayerInput {
+                    Throttle: 1.0,
+                    Boost: true,
+                    ..Default::default()
+                };
+                rlbot.update_player_input(0, &translate_player_input(&input))?;
+                Ok(ScenarioStepResult::Write)
+            }
+        }
+    }
+}
+
 #[derive(Copy, Clone)]
 pub enum AirAxis {
     Pitch,
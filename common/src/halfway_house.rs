@@ -50,6 +50,8 @@ pub struct BallInfo {
 pub struct GameInfo {
     pub TimeSeconds: f32,
     pub GameTimeRemaining: f32,
+    pub OverTime: bool,
+    pub UnlimitedTime: bool,
     pub RoundActive: bool,
     pub MatchEnded: bool,
 }
@@ -123,6 +125,8 @@ fn deserialize_game_info(info: rlbot::flat::GameInfo<'_>) -> GameInfo {
     GameInfo {
         TimeSeconds: info.secondsElapsed(),
         GameTimeRemaining: info.gameTimeRemaining(),
+        OverTime: info.isOvertime(),
+        UnlimitedTime: info.isUnlimitedTime(),
         RoundActive: info.isRoundActive(),
         MatchEnded: info.isMatchEnded(),
     }
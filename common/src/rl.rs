@@ -97,6 +97,11 @@ pub const CAR_JUMP_ACCEL: f32 = CAR_JUMP_FORCE / CAR_MASS;
 /// This value was determined using data from `collect`.
 pub const BOOST_DEPLETION: f32 = 100.0 / 3.0;
 
+/// The acceleration added by boosting, regardless of wheel contact.
+///
+/// Source: https://github.com/RLBot/RLBot/wiki/Useful-Game-Values
+pub const BOOST_ACCEL: f32 = 991.666;
+
 /// The velocity increase when dodging forward.
 pub const DODGE_FORWARD_IMPULSE: f32 = 500.0;
 
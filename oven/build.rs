@@ -5,10 +5,12 @@
 use common::prelude::*;
 use nalgebra::{UnitComplex, UnitQuaternion};
 use std::{
+    collections::HashMap,
     env,
+    f32::consts::PI,
     fs::File,
     io::{Read, Write},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 /// Load data from the CSVs in `data`, and generate constants so the data is
@@ -41,6 +43,17 @@ fn main() {
 
         compile_csv(&basename, r, &mut out, legacy);
     }
+
+    compile_grid(
+        &csv_dir,
+        &GridSpec {
+            name: "powerslide_turn",
+            params: &["speed", "throttle"],
+            rot_step: PI / 64.0,
+            rot_max: 2.0 * PI,
+        },
+        &mut out,
+    );
 }
 
 fn compile_csv(name: &str, mut csv: csv::Reader<impl Read>, w: &mut impl Write, legacy: bool) {
@@ -89,13 +102,9 @@ fn compile_csv(name: &str, mut csv: csv::Reader<impl Read>, w: &mut impl Write,
         })
         .collect::<Vec<_>>();
 
-    let player0_rot_2d = player0_rot.iter().map(|r| r.to_2d()).collect::<Vec<_>>();
-    let player0_rot_2d_angle_cum = player0_rot_2d
+    let player0_rot_2d_angle_cum = rot_2d_angle_cum(&player0_rot)
         .iter()
-        .scan(0.0, |state, rot| {
-            *state += UnitComplex::new(*state).angle_to(rot);
-            Some(state.to_source())
-        })
+        .map(|x| x.to_source())
         .collect::<Vec<_>>();
 
     writeln!(w, "pub mod {} {{", name).unwrap();
@@ -127,12 +136,203 @@ fn compile_csv(name: &str, mut csv: csv::Reader<impl Read>, w: &mut impl Write,
     writeln!(w, "}}\n").unwrap();
 }
 
+fn rot_2d_angle_cum(rot: &[UnitQuaternion<f32>]) -> Vec<f32> {
+    rot.iter()
+        .map(|r| r.to_2d())
+        .scan(0.0, |state, rot| {
+            *state += UnitComplex::new(*state).angle_to(&rot);
+            Some(*state)
+        })
+        .collect()
+}
+
+/// A family of CSVs which sweep over some parameters, with file names like
+/// `{name}_speed_100_throttle_1.csv`. Each recording is resampled by how far
+/// the car has rotated, and the results are stacked into one grid, so the data
+/// can be interpolated in every dimension at runtime (see
+/// `simulate::GridTable`).
+///
+/// The generated axes are the parameters in file name order, followed by
+/// `ROT`. The values are row-major, with the last axis varying fastest. Cells
+/// where the car never rotated that far are `NAN`.
+struct GridSpec {
+    name: &'static str,
+    params: &'static [&'static str],
+    rot_step: f32,
+    rot_max: f32,
+}
+
+struct Recording {
+    time: Vec<f32>,
+    loc_x: Vec<f32>,
+    loc_y: Vec<f32>,
+    vel_x: Vec<f32>,
+    vel_y: Vec<f32>,
+    rot_cum: Vec<f32>,
+}
+
+fn compile_grid(csv_dir: &Path, spec: &GridSpec, w: &mut impl Write) {
+    let mut recordings = HashMap::new();
+    for entry in csv_dir.read_dir().unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().unwrap().to_str() != Some("csv") {
+            continue;
+        }
+        let stem = path.file_stem().unwrap().to_str().unwrap();
+        let prefix = format!("{}_", spec.name);
+        if !stem.starts_with(&prefix) {
+            continue;
+        }
+        let rest = &stem[prefix.len()..];
+        let parts = rest.split('_').collect::<Vec<_>>();
+        assert_eq!(parts.len(), spec.params.len() * 2, "{}", stem);
+        let key = spec
+            .params
+            .iter()
+            .zip(parts.chunks(2))
+            .map(|(&param, pair)| {
+                assert_eq!(pair[0], param, "{}", stem);
+                pair[1].parse::<f32>().unwrap()
+            })
+            .collect::<Vec<_>>();
+        recordings.insert(grid_key(&key), (key, read_recording(&path)));
+    }
+
+    let mut axes = vec![Vec::<f32>::new(); spec.params.len()];
+    for (key, _) in recordings.values() {
+        for (axis, &x) in axes.iter_mut().zip(key) {
+            if !axis.contains(&x) {
+                axis.push(x);
+            }
+        }
+    }
+    for axis in &mut axes {
+        axis.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    }
+    let rot_axis = (0..)
+        .map(|i| i as f32 * spec.rot_step)
+        .take_while(|&r| r <= spec.rot_max + 1e-3)
+        .collect::<Vec<_>>();
+
+    let mut time = Vec::new();
+    let mut loc_x = Vec::new();
+    let mut loc_y = Vec::new();
+    let mut vel_x = Vec::new();
+    let mut vel_y = Vec::new();
+    for key in cartesian_product(&axes) {
+        let (_, rec) = recordings
+            .get(&grid_key(&key))
+            .unwrap_or_else(|| panic!("{} is missing {:?}", spec.name, key));
+        for &rot in &rot_axis {
+            match resample_by_rot(rec, rot) {
+                Some((i, ratio)) => {
+                    let at = |xs: &[f32]| xs[i] + (xs[(i + 1).min(xs.len() - 1)] - xs[i]) * ratio;
+                    time.push(at(&rec.time) - rec.time[0]);
+                    loc_x.push(at(&rec.loc_x) - rec.loc_x[0]);
+                    loc_y.push(at(&rec.loc_y) - rec.loc_y[0]);
+                    vel_x.push(at(&rec.vel_x));
+                    vel_y.push(at(&rec.vel_y));
+                }
+                None => {
+                    for xs in &mut [&mut time, &mut loc_x, &mut loc_y, &mut vel_x, &mut vel_y] {
+                        xs.push(f32::NAN);
+                    }
+                }
+            }
+        }
+    }
+
+    macro_rules! write_array {
+        ($name:expr, $items:expr $(,)?) => {
+            writeln!(w, "    pub const {}: &[f32] = &[", $name).unwrap();
+            for x in $items {
+                writeln!(w, "        {},", x.to_source()).unwrap();
+            }
+            writeln!(w, "    ];\n").unwrap();
+        };
+    }
+
+    writeln!(w, "pub mod {} {{", spec.name).unwrap();
+    for (param, axis) in spec.params.iter().zip(&axes) {
+        write_array!(param.to_uppercase(), axis);
+    }
+    write_array!("ROT", &rot_axis);
+    write_array!("TIME", &time);
+    write_array!("CAR_LOC_X", &loc_x);
+    write_array!("CAR_LOC_Y", &loc_y);
+    write_array!("CAR_VEL_X", &vel_x);
+    write_array!("CAR_VEL_Y", &vel_y);
+    writeln!(w, "}}\n").unwrap();
+}
+
+fn read_recording(path: &Path) -> Recording {
+    let mut csv = csv::ReaderBuilder::new().from_reader(File::open(path).unwrap());
+    let headers = csv.headers().unwrap().clone();
+    let rows: Vec<_> = csv.records().map(Result::unwrap).collect();
+    let col = |name: &str| -> Vec<f32> {
+        let column = headers.iter().position(|h| h == name).unwrap();
+        rows.iter()
+            .map(|row| row[column].parse().unwrap())
+            .collect()
+    };
+
+    let rot = col("player0_rot_x")
+        .into_iter()
+        .zip(col("player0_rot_y"))
+        .zip(col("player0_rot_z"))
+        .zip(col("player0_rot_w"))
+        .map(|(((x, y), z), w)| UnitQuaternion::xyzw(x, y, z, w))
+        .collect::<Vec<_>>();
+
+    Recording {
+        time: col("time"),
+        loc_x: col("player0_loc_x"),
+        loc_y: col("player0_loc_y"),
+        vel_x: col("player0_vel_x"),
+        vel_y: col("player0_vel_y"),
+        rot_cum: rot_2d_angle_cum(&rot),
+    }
+}
+
+/// Find the first moment the car has rotated by `rot`, as a fractional index.
+fn resample_by_rot(rec: &Recording, rot: f32) -> Option<(usize, f32)> {
+    let rel = |i: usize| rec.rot_cum[i] - rec.rot_cum[0];
+    let i = (0..rec.rot_cum.len()).find(|&i| rel(i) >= rot)?;
+    if i == 0 {
+        return Some((0, 0.0));
+    }
+    let ratio = (rot - rel(i - 1)) / (rel(i) - rel(i - 1));
+    Some((i - 1, ratio))
+}
+
+/// Floats can't be hashed, so key the recordings by their exact bits.
+fn grid_key(key: &[f32]) -> Vec<u32> {
+    key.iter().map(|x| x.to_bits()).collect()
+}
+
+fn cartesian_product(axes: &[Vec<f32>]) -> Vec<Vec<f32>> {
+    axes.iter().fold(vec![vec![]], |acc, axis| {
+        acc.into_iter()
+            .flat_map(|prefix| {
+                axis.iter().map(move |&x| {
+                    let mut key = prefix.clone();
+                    key.push(x);
+                    key
+                })
+            })
+            .collect()
+    })
+}
+
 trait ToSource {
     fn to_source(&self) -> String;
 }
 
 impl ToSource for f32 {
     fn to_source(&self) -> String {
+        if self.is_nan() {
+            return "std::f32::NAN".to_string();
+        }
         floatify(self.to_string())
     }
 }
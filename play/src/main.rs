@@ -3,11 +3,11 @@
 #![warn(clippy::all)]
 
 use crate::{banner::Banner, hacketeer::Hacketeer};
-use brain::{Brain, EEG};
+use brain::{Brain, Registry, StrategyConfig, EEG};
 use chrono::Local;
 use collect::Collector;
 use common::{ext::ExtendRLBot, halfway_house::translate_player_input};
-use std::{error::Error, fs, panic, path::PathBuf, thread::sleep, time::Duration};
+use std::{env, error::Error, fs, panic, path::PathBuf, thread::sleep, time::Duration};
 
 mod banner;
 mod built;
@@ -34,6 +34,9 @@ fn main() {
         player_index,
     } = parse_args().expect("Error parsing command-line arguments");
 
+    let strategy = load_strategy();
+    load_params(log_to_stdout);
+
     let rlbot = rlbot::init_with_options(init_options).expect("Could not initialize RLBot");
     let rlbot: &rlbot::RLBot = Box::leak(Box::new(rlbot));
 
@@ -48,6 +51,7 @@ fn main() {
             log_game_data,
             log_to_stdout,
             show_window,
+            strategy.clone(),
         );
     };
 
@@ -85,6 +89,28 @@ fn parse_args() -> Result<StartArgs, ()> {
     }
 }
 
+/// If `FORMULA_NONE_STRATEGY` names a strategy file, load it. Do this up
+/// front so a broken file is reported before the match starts.
+fn load_strategy() -> Option<StrategyConfig> {
+    let path = env::var_os("FORMULA_NONE_STRATEGY")?;
+    match StrategyConfig::load(&path, &Registry::standard()) {
+        Ok(config) => {
+            println!("Using strategy {}", path.to_string_lossy());
+            Some(config)
+        }
+        Err(error) => panic!("Error loading {}: {}", path.to_string_lossy(), error),
+    }
+}
+
+fn load_params(verbose: bool) {
+    if let Err(error) = brain::params::load_overrides() {
+        panic!("Error loading parameters: {}", error);
+    }
+    if verbose {
+        print!("Parameters: {}", brain::params::dump());
+    }
+}
+
 struct StartArgs {
     init_options: rlbot::InitOptions,
     should_start_match: bool,
@@ -125,13 +151,15 @@ fn run_bot(
     log_game_data: bool,
     log_to_stdout: bool,
     show_window: bool,
+    strategy: Option<StrategyConfig>,
 ) {
     let field_info = wait_for_field_info(rlbot);
-    let brain = match Brain::infer_game_mode(field_info) {
-        rlbot::GameMode::Soccer => Brain::soccar(),
-        rlbot::GameMode::Dropshot => Brain::dropshot(rlbot),
-        rlbot::GameMode::Hoops => Brain::hoops(rlbot),
-        mode => panic!("unexpected game mode {:?}", mode),
+    let brain = match (Brain::infer_game_mode(field_info), strategy) {
+        (rlbot::GameMode::Soccer, Some(strategy)) => Brain::soccar_with_strategy(strategy),
+        (rlbot::GameMode::Soccer, None) => Brain::soccar(),
+        (rlbot::GameMode::Dropshot, _) => Brain::dropshot(rlbot),
+        (rlbot::GameMode::Hoops, _) => Brain::hoops(rlbot),
+        (mode, _) => panic!("unexpected game mode {:?}", mode),
     };
 
     let collector = if log_game_data {
@@ -0,0 +1,119 @@
+use crate::car_forward_dodge::{DODGE_IMPULSE, JUMP_TIME, LANDING_TIME, WAIT_TIME};
+use common::{physics, rl};
+use nalgebra::{UnitComplex, Vector2};
+
+/// A dodge in an arbitrary stick direction, starting from flat ground.
+///
+/// This is the general form of `CarForwardDodge`, and uses the same timings and
+/// impulse, just pointed in the direction of the stick. The real game gives
+/// sideways and backward dodges a somewhat larger impulse at speed; that isn't
+/// modeled.
+pub struct CarDodge;
+
+impl CarDodge {
+    /// Simulate a dodge. `start_rot` is the car's yaw, and `direction` is the
+    /// stick angle relative to the car, where 0° means straight forward and
+    /// positive angles are to the right (the same convention as the `Dodge`
+    /// behavior).
+    pub fn calc_2d(
+        start_vel: Vector2<f32>,
+        start_rot: UnitComplex<f32>,
+        direction: UnitComplex<f32>,
+    ) -> CarDodge2D {
+        let forward_axis = physics::car_forward_axis_2d(start_rot);
+        let right_axis = physics::car_right_axis_2d(start_rot);
+        let impulse = (forward_axis.into_inner() * direction.cos_angle()
+            + right_axis.into_inner() * direction.sin_angle())
+            * DODGE_IMPULSE;
+
+        let end_vel = start_vel + impulse;
+        let end_vel = if end_vel.norm() > rl::CAR_MAX_SPEED {
+            end_vel.normalize() * rl::CAR_MAX_SPEED
+        } else {
+            end_vel
+        };
+        let end_offset = start_vel * (JUMP_TIME + WAIT_TIME) + end_vel * LANDING_TIME;
+
+        CarDodge2D {
+            start_vel,
+            direction,
+            impulse,
+            end_offset,
+            end_vel,
+            jump_duration: JUMP_TIME,
+            wait_duration: WAIT_TIME,
+            dodge_duration: LANDING_TIME,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct CarDodge2D {
+    pub start_vel: Vector2<f32>,
+    pub direction: UnitComplex<f32>,
+    /// The velocity change from the dodge itself, in world coordinates.
+    pub impulse: Vector2<f32>,
+    /// The displacement from the start of the jump until the car lands.
+    pub end_offset: Vector2<f32>,
+    pub end_vel: Vector2<f32>,
+    pub jump_duration: f32,
+    pub wait_duration: f32,
+    pub dodge_duration: f32,
+}
+
+impl CarDodge2D {
+    pub fn duration(&self) -> f32 {
+        self.jump_duration + self.wait_duration + self.dodge_duration
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{car_dodge::CarDodge, CarForwardDodge};
+    use common::rl;
+    use nalgebra::{UnitComplex, Vector2};
+    use std::f32::consts::PI;
+
+    #[test]
+    fn forward_matches_forward_dodge() {
+        for &speed in &[0.0, 500.0, 1000.0, 2000.0] {
+            let vel = Vector2::new(speed, 0.0);
+            let dodge = CarDodge::calc_2d(vel, UnitComplex::identity(), UnitComplex::identity());
+            let forward = CarForwardDodge::calc_1d(speed);
+            assert!((dodge.end_vel.x - forward.end_speed).abs() < 1.0);
+            assert!((dodge.end_offset.x - forward.end_dist).abs() < 1.0);
+            assert!(dodge.end_vel.y.abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn side_dodge_goes_right() {
+        let vel = Vector2::new(1000.0, 0.0);
+        let dodge = CarDodge::calc_2d(vel, UnitComplex::identity(), UnitComplex::new(PI / 2.0));
+        assert!((dodge.end_vel.y - 500.0).abs() < 1.0);
+        assert!((dodge.end_vel.x - 1000.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn backward_dodge_slows_down() {
+        let vel = Vector2::new(1000.0, 0.0);
+        let dodge = CarDodge::calc_2d(vel, UnitComplex::identity(), UnitComplex::new(PI));
+        assert!((dodge.end_vel.x - 500.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn rotated_car() {
+        let rot = UnitComplex::new(PI / 2.0);
+        let vel = Vector2::new(0.0, 1000.0);
+        let dodge = CarDodge::calc_2d(vel, rot, UnitComplex::identity());
+        assert!(dodge.end_vel.x.abs() < 1.0);
+        assert!((dodge.end_vel.y - 1500.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn capped_at_max_speed() {
+        let vel = Vector2::new(rl::CAR_MAX_SPEED, 0.0);
+        let dodge = CarDodge::calc_2d(vel, UnitComplex::identity(), UnitComplex::new(PI / 4.0));
+        assert!(dodge.end_vel.norm() <= rl::CAR_MAX_SPEED + 1.0);
+    }
+}
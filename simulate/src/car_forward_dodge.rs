@@ -1,9 +1,10 @@
 use common::rl;
+use serde_derive::{Deserialize, Serialize};
 
-const JUMP_TIME: f32 = 6.0 / 120.0;
-const WAIT_TIME: f32 = 6.0 / 120.0;
-const LANDING_TIME: f32 = 1.2;
-const DODGE_IMPULSE: f32 = 500.0;
+pub(crate) const JUMP_TIME: f32 = 6.0 / 120.0;
+pub(crate) const WAIT_TIME: f32 = 6.0 / 120.0;
+pub(crate) const LANDING_TIME: f32 = 1.2;
+pub(crate) const DODGE_IMPULSE: f32 = 500.0;
 
 pub struct CarForwardDodge;
 
@@ -24,7 +25,7 @@ impl CarForwardDodge {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct CarForwardDodge1D {
     pub start_speed: f32,
     pub end_dist: f32,
@@ -1,16 +1,15 @@
-#![allow(clippy::float_cmp)]
-
+use crate::math::GridTable;
 use common::{
     physics::{car_forward_axis_2d, CAR_LOCAL_FORWARD_AXIS_2D},
     prelude::*,
 };
-use lazy_static::lazy_static;
 use nalgebra::{Point2, UnitComplex, Vector2};
 use oven::data;
+use serde_derive::{Deserialize, Serialize};
 
 pub struct CarPowerslideTurn;
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct CarPowerslideTurnBlueprint {
     pub start_loc: Point2<f32>,
     pub start_rot: UnitComplex<f32>,
@@ -75,29 +74,30 @@ impl CarPowerslideTurn {
         target_rot_by: f32,
     ) -> Option<CarPowerslideTurnBlueprint> {
         assert!(start_speed >= 0.0);
-        let speed_index = start_speed as usize / 100;
-        // let lower_speed = speed_index as f32 * 100.0;
-        let lower = TableSet::get(throttle, speed_index);
-        // let upper_speed = ((speed_index + 1) as f32 * 100.0).min(rl::CAR_MAX_SPEED);
-        let upper = TableSet::get(throttle, speed_index + 1);
+        if target_rot_by > *data::powerslide_turn::ROT.last().unwrap() {
+            return None;
+        }
 
-        let start_rot = (lower.rot_2d_angle_cum[0] + upper.rot_2d_angle_cum[0]) / 2.0;
-        let index = lower
-            .rot_2d_angle_cum
-            .iter()
-            .zip(upper.rot_2d_angle_cum)
-            .position(|(l, u)| (l + u) / 2.0 - start_rot >= target_rot_by)?;
-        let index = ((index as f32) * 0.5) as usize;
+        let index = TIME.locate(&[start_speed, throttle, target_rot_by]);
+        let duration = TIME.interpolate_located(&index);
+        // The data has holes where the car never rotates that far.
+        if duration.is_nan() {
+            return None;
+        }
 
-        // TODO: interpolate between `lower` and `upper`
-        let start_time = lower.time[0];
-        let end_time = lower.time[index];
-        let start_loc = lower.loc_2d[0];
-        let end_loc = lower.loc_2d[index];
+        let start_loc = Point2::origin();
+        let end_loc = start_loc
+            + Vector2::new(
+                CAR_LOC_X.interpolate_located(&index),
+                CAR_LOC_Y.interpolate_located(&index),
+            );
         let start_rot = CAR_LOCAL_FORWARD_AXIS_2D.rotation_to(&Vector2::y_axis());
         let end_rot = UnitComplex::new(target_rot_by) * start_rot;
-        let start_vel = lower.vel_2d[0];
-        let end_vel = lower.vel_2d[index];
+        let start_vel = Vector2::new(0.0, start_speed);
+        let end_vel = Vector2::new(
+            CAR_VEL_X.interpolate_located(&index),
+            CAR_VEL_Y.interpolate_located(&index),
+        );
         Some(CarPowerslideTurnBlueprint {
             start_loc,
             start_rot,
@@ -107,257 +107,21 @@ impl CarPowerslideTurn {
             end_loc,
             end_rot,
             end_vel,
-            duration: end_time - start_time,
+            duration,
         })
     }
 }
 
-struct TableSet<'a> {
-    time: &'a [f32],
-    loc_2d: &'a [Point2<f32>],
-    rot_2d_angle_cum: &'a [f32],
-    vel_2d: &'a [Vector2<f32>],
-}
-
-impl<'a> TableSet<'a> {
-    fn get(throttle: f32, speed_index: usize) -> Self {
-        if throttle == 0.0 {
-            Self {
-                time: POWERSLIDE_TURN_THROTTLE_0_TIME[speed_index],
-                loc_2d: POWERSLIDE_TURN_THROTTLE_0_CAR_LOC_2D[speed_index],
-                rot_2d_angle_cum: POWERSLIDE_TURN_THROTTLE_0_CAR_ROT_2D_ANGLE_CUM[speed_index],
-                vel_2d: POWERSLIDE_TURN_THROTTLE_0_CAR_VEL_2D[speed_index],
-            }
-        } else if throttle == 1.0 {
-            Self {
-                time: POWERSLIDE_TURN_THROTTLE_1_TIME[speed_index],
-                loc_2d: POWERSLIDE_TURN_THROTTLE_1_CAR_LOC_2D[speed_index],
-                rot_2d_angle_cum: POWERSLIDE_TURN_THROTTLE_1_CAR_ROT_2D_ANGLE_CUM[speed_index],
-                vel_2d: POWERSLIDE_TURN_THROTTLE_1_CAR_VEL_2D[speed_index],
-            }
-        } else {
-            panic!("throttle not quantized")
-        }
-    }
-}
-
-const POWERSLIDE_TURN_THROTTLE_0_TIME: &[&[f32]] = &[
-    data::powerslide_turn_speed_0_throttle_0::TIME,
-    data::powerslide_turn_speed_100_throttle_0::TIME,
-    data::powerslide_turn_speed_200_throttle_0::TIME,
-    data::powerslide_turn_speed_300_throttle_0::TIME,
-    data::powerslide_turn_speed_400_throttle_0::TIME,
-    data::powerslide_turn_speed_500_throttle_0::TIME,
-    data::powerslide_turn_speed_600_throttle_0::TIME,
-    data::powerslide_turn_speed_700_throttle_0::TIME,
-    data::powerslide_turn_speed_800_throttle_0::TIME,
-    data::powerslide_turn_speed_900_throttle_0::TIME,
-    data::powerslide_turn_speed_1000_throttle_0::TIME,
-    data::powerslide_turn_speed_1100_throttle_0::TIME,
-    data::powerslide_turn_speed_1200_throttle_0::TIME,
-    data::powerslide_turn_speed_1300_throttle_0::TIME,
-    data::powerslide_turn_speed_1400_throttle_0::TIME,
-    data::powerslide_turn_speed_1500_throttle_0::TIME,
-    data::powerslide_turn_speed_1600_throttle_0::TIME,
-    data::powerslide_turn_speed_1700_throttle_0::TIME,
-    data::powerslide_turn_speed_1800_throttle_0::TIME,
-    data::powerslide_turn_speed_1900_throttle_0::TIME,
-    data::powerslide_turn_speed_2000_throttle_0::TIME,
-    data::powerslide_turn_speed_2100_throttle_0::TIME,
-    data::powerslide_turn_speed_2200_throttle_0::TIME,
-    data::powerslide_turn_speed_2299_98_throttle_0::TIME,
-];
-
-const POWERSLIDE_TURN_THROTTLE_1_TIME: &[&[f32]] = &[
-    data::powerslide_turn_speed_0_throttle_1::TIME,
-    data::powerslide_turn_speed_100_throttle_1::TIME,
-    data::powerslide_turn_speed_200_throttle_1::TIME,
-    data::powerslide_turn_speed_300_throttle_1::TIME,
-    data::powerslide_turn_speed_400_throttle_1::TIME,
-    data::powerslide_turn_speed_500_throttle_1::TIME,
-    data::powerslide_turn_speed_600_throttle_1::TIME,
-    data::powerslide_turn_speed_700_throttle_1::TIME,
-    data::powerslide_turn_speed_800_throttle_1::TIME,
-    data::powerslide_turn_speed_900_throttle_1::TIME,
-    data::powerslide_turn_speed_1000_throttle_1::TIME,
-    data::powerslide_turn_speed_1100_throttle_1::TIME,
-    data::powerslide_turn_speed_1200_throttle_1::TIME,
-    data::powerslide_turn_speed_1300_throttle_1::TIME,
-    data::powerslide_turn_speed_1400_throttle_1::TIME,
-    data::powerslide_turn_speed_1500_throttle_1::TIME,
-    data::powerslide_turn_speed_1600_throttle_1::TIME,
-    data::powerslide_turn_speed_1700_throttle_1::TIME,
-    data::powerslide_turn_speed_1800_throttle_1::TIME,
-    data::powerslide_turn_speed_1900_throttle_1::TIME,
-    data::powerslide_turn_speed_2000_throttle_1::TIME,
-    data::powerslide_turn_speed_2100_throttle_1::TIME,
-    data::powerslide_turn_speed_2200_throttle_1::TIME,
-    data::powerslide_turn_speed_2299_98_throttle_1::TIME,
+const AXES: &[&[f32]] = &[
+    data::powerslide_turn::SPEED,
+    data::powerslide_turn::THROTTLE,
+    data::powerslide_turn::ROT,
 ];
-
-lazy_static! {
-    static ref POWERSLIDE_TURN_THROTTLE_0_CAR_LOC_2D: [&'static [Point2<f32>]; 24] = [
-        &*data::powerslide_turn_speed_0_throttle_0::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_100_throttle_0::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_200_throttle_0::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_300_throttle_0::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_400_throttle_0::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_500_throttle_0::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_600_throttle_0::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_700_throttle_0::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_800_throttle_0::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_900_throttle_0::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_1000_throttle_0::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_1100_throttle_0::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_1200_throttle_0::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_1300_throttle_0::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_1400_throttle_0::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_1500_throttle_0::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_1600_throttle_0::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_1700_throttle_0::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_1800_throttle_0::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_1900_throttle_0::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_2000_throttle_0::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_2100_throttle_0::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_2200_throttle_0::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_2299_98_throttle_0::CAR_LOC_2D,
-    ];
-    static ref POWERSLIDE_TURN_THROTTLE_1_CAR_LOC_2D: [&'static [Point2<f32>]; 24] = [
-        &*data::powerslide_turn_speed_0_throttle_1::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_100_throttle_1::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_200_throttle_1::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_300_throttle_1::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_400_throttle_1::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_500_throttle_1::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_600_throttle_1::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_700_throttle_1::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_800_throttle_1::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_900_throttle_1::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_1000_throttle_1::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_1100_throttle_1::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_1200_throttle_1::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_1300_throttle_1::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_1400_throttle_1::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_1500_throttle_1::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_1600_throttle_1::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_1700_throttle_1::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_1800_throttle_1::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_1900_throttle_1::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_2000_throttle_1::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_2100_throttle_1::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_2200_throttle_1::CAR_LOC_2D,
-        &*data::powerslide_turn_speed_2299_98_throttle_1::CAR_LOC_2D,
-    ];
-}
-
-const POWERSLIDE_TURN_THROTTLE_0_CAR_ROT_2D_ANGLE_CUM: &[&[f32]] = &[
-    data::powerslide_turn_speed_0_throttle_0::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_100_throttle_0::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_200_throttle_0::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_300_throttle_0::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_400_throttle_0::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_500_throttle_0::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_600_throttle_0::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_700_throttle_0::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_800_throttle_0::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_900_throttle_0::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_1000_throttle_0::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_1100_throttle_0::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_1200_throttle_0::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_1300_throttle_0::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_1400_throttle_0::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_1500_throttle_0::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_1600_throttle_0::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_1700_throttle_0::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_1800_throttle_0::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_1900_throttle_0::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_2000_throttle_0::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_2100_throttle_0::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_2200_throttle_0::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_2299_98_throttle_0::CAR_ROT_2D_ANGLE_CUM,
-];
-
-const POWERSLIDE_TURN_THROTTLE_1_CAR_ROT_2D_ANGLE_CUM: &[&[f32]] = &[
-    data::powerslide_turn_speed_0_throttle_1::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_100_throttle_1::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_200_throttle_1::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_300_throttle_1::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_400_throttle_1::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_500_throttle_1::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_600_throttle_1::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_700_throttle_1::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_800_throttle_1::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_900_throttle_1::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_1000_throttle_1::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_1100_throttle_1::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_1200_throttle_1::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_1300_throttle_1::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_1400_throttle_1::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_1500_throttle_1::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_1600_throttle_1::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_1700_throttle_1::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_1800_throttle_1::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_1900_throttle_1::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_2000_throttle_1::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_2100_throttle_1::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_2200_throttle_1::CAR_ROT_2D_ANGLE_CUM,
-    data::powerslide_turn_speed_2299_98_throttle_1::CAR_ROT_2D_ANGLE_CUM,
-];
-
-lazy_static! {
-    static ref POWERSLIDE_TURN_THROTTLE_0_CAR_VEL_2D: [&'static [Vector2<f32>]; 24] = [
-        &*data::powerslide_turn_speed_0_throttle_0::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_100_throttle_0::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_200_throttle_0::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_300_throttle_0::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_400_throttle_0::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_500_throttle_0::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_600_throttle_0::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_700_throttle_0::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_800_throttle_0::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_900_throttle_0::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_1000_throttle_0::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_1100_throttle_0::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_1200_throttle_0::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_1300_throttle_0::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_1400_throttle_0::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_1500_throttle_0::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_1600_throttle_0::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_1700_throttle_0::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_1800_throttle_0::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_1900_throttle_0::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_2000_throttle_0::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_2100_throttle_0::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_2200_throttle_0::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_2299_98_throttle_0::CAR_VEL_2D,
-    ];
-    static ref POWERSLIDE_TURN_THROTTLE_1_CAR_VEL_2D: [&'static [Vector2<f32>]; 24] = [
-        &*data::powerslide_turn_speed_0_throttle_1::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_100_throttle_1::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_200_throttle_1::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_300_throttle_1::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_400_throttle_1::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_500_throttle_1::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_600_throttle_1::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_700_throttle_1::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_800_throttle_1::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_900_throttle_1::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_1000_throttle_1::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_1100_throttle_1::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_1200_throttle_1::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_1300_throttle_1::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_1400_throttle_1::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_1500_throttle_1::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_1600_throttle_1::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_1700_throttle_1::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_1800_throttle_1::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_1900_throttle_1::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_2000_throttle_1::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_2100_throttle_1::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_2200_throttle_1::CAR_VEL_2D,
-        &*data::powerslide_turn_speed_2299_98_throttle_1::CAR_VEL_2D,
-    ];
-}
+const TIME: GridTable<'static> = GridTable::new(AXES, data::powerslide_turn::TIME);
+const CAR_LOC_X: GridTable<'static> = GridTable::new(AXES, data::powerslide_turn::CAR_LOC_X);
+const CAR_LOC_Y: GridTable<'static> = GridTable::new(AXES, data::powerslide_turn::CAR_LOC_Y);
+const CAR_VEL_X: GridTable<'static> = GridTable::new(AXES, data::powerslide_turn::CAR_VEL_X);
+const CAR_VEL_Y: GridTable<'static> = GridTable::new(AXES, data::powerslide_turn::CAR_VEL_Y);
 
 #[cfg(test)]
 mod tests {
@@ -384,4 +148,27 @@ mod tests {
             90.0_f32.to_radians(),
         );
     }
+
+    #[test]
+    fn interpolates_between_speeds() {
+        let turn = |speed| {
+            CarPowerslideTurn::evaluate(
+                Point2::origin(),
+                UnitComplex::identity(),
+                Vector2::new(speed, 0.0),
+                1.0,
+                45.0_f32.to_radians(),
+            )
+            .unwrap()
+        };
+        let lower = turn(1000.0);
+        let mid = turn(1050.0);
+        let upper = turn(1100.0);
+        let (lo, hi) = if lower.duration < upper.duration {
+            (lower.duration, upper.duration)
+        } else {
+            (upper.duration, lower.duration)
+        };
+        assert!(lo <= mid.duration && mid.duration <= hi);
+    }
 }
@@ -0,0 +1,141 @@
+use crate::{
+    car_forward_dodge::{DODGE_IMPULSE, JUMP_TIME},
+    math::linear_interpolate,
+};
+use common::rl;
+use oven::data;
+use serde_derive::{Deserialize, Serialize};
+
+const WAIT_TIME: f32 = 2.0 / 120.0;
+/// The stick angle of the dodge, where 0° means straight forward.
+pub const SPEED_FLIP_ANGLE: f32 = 0.7853982; // 45°
+
+/// A diagonal dodge whose flip is immediately canceled, while holding boost
+/// the whole way.
+///
+/// Like `CarForwardDodge`, this only models the high-level numbers. The dodge
+/// is the same as `CarDodge` at `SPEED_FLIP_ANGLE`; only the forward part of
+/// the impulse is kept, since the tires scrub off the sideways part when the
+/// car lands. None of this has been checked against the `speed_flip_speed_*`
+/// runs in `collect` yet.
+pub struct CarSpeedFlip;
+
+impl CarSpeedFlip {
+    /// Simulate a speed flip starting at `start_speed` with `start_boost`.
+    /// Returns `None` if there is not enough boost to make it worthwhile.
+    pub fn calc_1d(start_speed: f32, start_boost: f32) -> Option<CarSpeedFlip1D> {
+        let min_boost = (JUMP_TIME + WAIT_TIME) * rl::BOOST_DEPLETION;
+        if start_boost < min_boost {
+            return None;
+        }
+
+        let landing_time = landing_time();
+        let approach = boost_phase(start_speed, start_boost, JUMP_TIME + WAIT_TIME);
+        let dodge_speed =
+            (approach.speed + DODGE_IMPULSE * SPEED_FLIP_ANGLE.cos()).min(rl::CAR_MAX_SPEED);
+        let landing = boost_phase(dodge_speed, approach.boost, landing_time);
+
+        Some(CarSpeedFlip1D {
+            start_speed,
+            start_boost,
+            end_dist: approach.dist + landing.dist,
+            end_speed: landing.speed,
+            end_boost: landing.boost,
+            jump_duration: JUMP_TIME,
+            wait_duration: WAIT_TIME,
+            dodge_duration: landing_time,
+        })
+    }
+}
+
+/// The time from the dodge until the wheels touch the ground again.
+///
+/// The dodge cancels the car's vertical velocity, so from there it falls from
+/// whatever height the jump reached. That height comes from the recorded jump.
+fn landing_time() -> f32 {
+    let dodge_time = data::jump::TIME[0] + JUMP_TIME + WAIT_TIME;
+    let dodge_z = linear_interpolate(data::jump::TIME, data::jump::CAR_LOC_Z, dodge_time);
+    let height = dodge_z - rl::OCTANE_NEUTRAL_Z;
+    (2.0 * height / -rl::GRAVITY).sqrt()
+}
+
+struct BoostPhase {
+    dist: f32,
+    speed: f32,
+    boost: f32,
+}
+
+/// Boost in a straight line for `dt` seconds, ignoring drag. This holds both on
+/// the ground and in the air, since the throttle stops mattering once you are
+/// boosting near top speed.
+fn boost_phase(speed: f32, boost: f32, dt: f32) -> BoostPhase {
+    let boost_time = (boost / rl::BOOST_DEPLETION).min(dt);
+    let accel_time = ((rl::CAR_MAX_SPEED - speed) / rl::BOOST_ACCEL)
+        .max(0.0)
+        .min(boost_time);
+    let top_speed = speed + rl::BOOST_ACCEL * accel_time;
+    let dist = speed * accel_time
+        + 0.5 * rl::BOOST_ACCEL * accel_time * accel_time
+        + top_speed * (dt - accel_time);
+    BoostPhase {
+        dist,
+        speed: top_speed.min(rl::CAR_MAX_SPEED),
+        boost: (boost - boost_time * rl::BOOST_DEPLETION).max(0.0),
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CarSpeedFlip1D {
+    pub start_speed: f32,
+    pub start_boost: f32,
+    pub end_dist: f32,
+    pub end_speed: f32,
+    pub end_boost: f32,
+    pub jump_duration: f32,
+    pub wait_duration: f32,
+    pub dodge_duration: f32,
+}
+
+impl CarSpeedFlip1D {
+    pub fn duration(&self) -> f32 {
+        self.jump_duration + self.wait_duration + self.dodge_duration
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{car_speed_flip::CarSpeedFlip, CarForwardDodge};
+    use common::rl;
+
+    #[test]
+    fn no_boost() {
+        assert!(CarSpeedFlip::calc_1d(0.0, 0.0).is_none());
+    }
+
+    #[test]
+    fn faster_than_forward_dodge() {
+        for &speed in &[0.0, 500.0, 1000.0, 1500.0] {
+            let flip = CarSpeedFlip::calc_1d(speed, 100.0).unwrap();
+            let dodge = CarForwardDodge::calc_1d(speed);
+            assert!(flip.end_speed > dodge.end_speed);
+            assert!(flip.end_boost < flip.start_boost);
+            // The nose stays down, so we're back on the ground much sooner.
+            assert!(flip.duration() < dodge.duration());
+        }
+    }
+
+    #[test]
+    fn landing_time() {
+        // About a third of a second from the first jump input.
+        let flip = CarSpeedFlip::calc_1d(1000.0, 100.0).unwrap();
+        assert!(flip.duration() > 0.25);
+        assert!(flip.duration() < 0.4);
+    }
+
+    #[test]
+    fn capped_at_max_speed() {
+        let flip = CarSpeedFlip::calc_1d(rl::CAR_MAX_SPEED, 100.0).unwrap();
+        assert_eq!(flip.end_speed, rl::CAR_MAX_SPEED);
+        assert!(flip.end_dist <= rl::CAR_MAX_SPEED * flip.duration() + 1.0);
+    }
+}
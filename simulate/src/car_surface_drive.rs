@@ -0,0 +1,278 @@
+use crate::car1d::Car1D;
+use common::rl;
+
+const DT: f32 = 1.0 / 120.0;
+/// The force which keeps the wheels planted on whatever surface they are
+/// touching. If gravity pulls the car away from the surface harder than this,
+/// it falls off.
+pub const STICKY_ACCEL: f32 = 325.0;
+/// The approximate radius of the curved transitions between the floor and the
+/// walls.
+pub const CORNER_RADIUS: f32 = 256.0;
+
+/// The shape of the surface underneath the car at one point along its path.
+#[derive(Copy, Clone, Debug)]
+pub struct SurfaceSample {
+    /// The sine of the angle the path climbs. `1.0` means driving straight up a
+    /// wall, and `-1.0` straight down.
+    pub slope: f32,
+    /// The z component of the surface normal. `1.0` is the floor, `0.0` is a
+    /// wall, and `-1.0` is the ceiling.
+    pub normal_z: f32,
+}
+
+impl SurfaceSample {
+    pub const FLOOR: Self = Self {
+        slope: 0.0,
+        normal_z: 1.0,
+    };
+
+    fn lerp(self, other: Self, t: f32) -> Self {
+        Self {
+            slope: self.slope + (other.slope - self.slope) * t,
+            normal_z: self.normal_z + (other.normal_z - self.normal_z) * t,
+        }
+    }
+}
+
+enum Section {
+    Flat(SurfaceSample),
+    Transition(SurfaceSample, SurfaceSample),
+}
+
+/// A path over the floor, walls and ceiling, flattened into one dimension.
+pub struct SurfacePath {
+    sections: Vec<(f32, Section)>,
+}
+
+impl Default for SurfacePath {
+    fn default() -> Self {
+        Self {
+            sections: Vec::new(),
+        }
+    }
+}
+
+impl SurfacePath {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drive `length` uu over a flat surface.
+    pub fn flat(mut self, length: f32, surface: SurfaceSample) -> Self {
+        self.sections.push((length, Section::Flat(surface)));
+        self
+    }
+
+    /// Drive through one of the curved corners between two surfaces. The
+    /// surface blends smoothly from `from` to `to` over `length` uu.
+    pub fn transition(mut self, length: f32, from: SurfaceSample, to: SurfaceSample) -> Self {
+        self.sections.push((length, Section::Transition(from, to)));
+        self
+    }
+
+    pub fn length(&self) -> f32 {
+        self.sections.iter().map(|(length, _)| length).sum()
+    }
+
+    pub fn sample(&self, mut distance: f32) -> SurfaceSample {
+        for (length, section) in &self.sections {
+            if distance <= *length {
+                return match *section {
+                    Section::Flat(surface) => surface,
+                    Section::Transition(from, _) if *length <= 0.0 => from,
+                    Section::Transition(from, to) => from.lerp(to, distance / length),
+                };
+            }
+            distance -= length;
+        }
+        match self.sections.last() {
+            Some((_, Section::Flat(surface))) | Some((_, Section::Transition(_, surface))) => {
+                *surface
+            }
+            None => SurfaceSample::FLOOR,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum CarSurfaceDriveError {
+    /// Gravity pulled the car off the surface.
+    FellOff,
+    /// The car ran out of speed while climbing.
+    Stalled,
+}
+
+/// Like `Car1D`, but driving over a `SurfacePath`. The component of gravity
+/// along the path slows the car down going up a wall and speeds it up coming
+/// down, and the car falls off if gravity pulls it away from the surface harder
+/// than `STICKY_ACCEL`.
+pub struct CarSurfaceDrive {
+    time: f32,
+    distance: f32,
+    speed: f32,
+    boost: f32,
+}
+
+impl Default for CarSurfaceDrive {
+    fn default() -> Self {
+        Self {
+            time: 0.0,
+            distance: 0.0,
+            speed: 0.0,
+            boost: 100.0,
+        }
+    }
+}
+
+impl CarSurfaceDrive {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed.max(0.0).min(rl::CAR_MAX_SPEED);
+        self
+    }
+
+    pub fn with_boost(mut self, boost: f32) -> Self {
+        self.boost = boost;
+        self
+    }
+
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    pub fn distance(&self) -> f32 {
+        self.distance
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    pub fn boost(&self) -> f32 {
+        self.boost
+    }
+
+    /// Drive along `path` until reaching its end.
+    pub fn advance_path(
+        &mut self,
+        path: &SurfacePath,
+        throttle: f32,
+        boost: bool,
+    ) -> Result<(), CarSurfaceDriveError> {
+        let target = path.length();
+        while self.distance < target {
+            let surface = path.sample(self.distance);
+            if rl::GRAVITY * surface.normal_z > STICKY_ACCEL {
+                return Err(CarSurfaceDriveError::FellOff);
+            }
+
+            let mut car = Car1D::new().with_speed(self.speed).with_boost(self.boost);
+            car.advance(DT, throttle, boost);
+
+            let gravity = rl::GRAVITY * surface.slope;
+            let speed = (car.speed() + gravity * DT).min(rl::CAR_MAX_SPEED);
+            if speed <= 0.0 {
+                return Err(CarSurfaceDriveError::Stalled);
+            }
+            let step = car.distance() + 0.5 * gravity * DT * DT;
+
+            if self.distance + step >= target {
+                let frac = (target - self.distance) / step;
+                self.time += DT * frac;
+                self.speed += (speed - self.speed) * frac;
+                self.distance = target;
+            } else {
+                self.time += DT;
+                self.speed = speed;
+                self.distance += step;
+            }
+            self.boost = car.boost();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        car_surface_drive::{CarSurfaceDrive, SurfacePath, SurfaceSample},
+        Car1D,
+    };
+
+    const WALL_UP: SurfaceSample = SurfaceSample {
+        slope: 1.0,
+        normal_z: 0.0,
+    };
+    const WALL_DOWN: SurfaceSample = SurfaceSample {
+        slope: -1.0,
+        normal_z: 0.0,
+    };
+    const CEILING: SurfaceSample = SurfaceSample {
+        slope: 0.0,
+        normal_z: -1.0,
+    };
+
+    #[test]
+    fn floor_matches_car1d() {
+        let path = SurfacePath::new().flat(2000.0, SurfaceSample::FLOOR);
+        let mut sim = CarSurfaceDrive::new().with_speed(500.0).with_boost(0.0);
+        sim.advance_path(&path, 1.0, false).unwrap();
+
+        let mut car = Car1D::new().with_speed(500.0).with_boost(0.0);
+        car.advance_by_distance(2000.0, 1.0, false);
+        assert!((sim.time() - car.time()).abs() < 0.02);
+        assert!((sim.speed() - car.speed()).abs() < 20.0);
+    }
+
+    #[test]
+    fn climbing_is_slower() {
+        let floor = SurfacePath::new().flat(1000.0, SurfaceSample::FLOOR);
+        let up = SurfacePath::new().flat(1000.0, WALL_UP);
+        let down = SurfacePath::new().flat(1000.0, WALL_DOWN);
+
+        let run = |path| {
+            let mut sim = CarSurfaceDrive::new().with_speed(1000.0).with_boost(0.0);
+            sim.advance_path(path, 1.0, false).unwrap();
+            sim
+        };
+        let floor = run(&floor);
+        let up = run(&up);
+        let down = run(&down);
+        assert!(up.time() > floor.time());
+        assert!(down.time() < floor.time());
+        assert!(up.speed() < floor.speed());
+        assert!(down.speed() > floor.speed());
+    }
+
+    #[test]
+    fn transition_onto_wall() {
+        let path = SurfacePath::new()
+            .flat(500.0, SurfaceSample::FLOOR)
+            .transition(400.0, SurfaceSample::FLOOR, WALL_UP)
+            .flat(500.0, WALL_UP);
+        assert_eq!(path.length(), 1400.0);
+        assert!((path.sample(700.0).slope - 0.5).abs() < 1e-3);
+
+        let mut sim = CarSurfaceDrive::new().with_speed(1500.0);
+        sim.advance_path(&path, 1.0, true).unwrap();
+        assert_eq!(sim.distance(), 1400.0);
+    }
+
+    #[test]
+    fn stalls_on_wall() {
+        let path = SurfacePath::new().flat(3000.0, WALL_UP);
+        let mut sim = CarSurfaceDrive::new().with_speed(500.0).with_boost(0.0);
+        assert!(sim.advance_path(&path, 0.0, false).is_err());
+    }
+
+    #[test]
+    fn falls_off_ceiling() {
+        let path = SurfacePath::new().flat(500.0, CEILING);
+        let mut sim = CarSurfaceDrive::new().with_speed(1000.0);
+        assert!(sim.advance_path(&path, 1.0, false).is_err());
+    }
+}
@@ -6,17 +6,26 @@
 pub use crate::{
     car::{Car, CarSimulateError},
     car1d::Car1D,
+    car_dodge::{CarDodge, CarDodge2D},
     car_forward_dodge::{CarForwardDodge, CarForwardDodge1D},
     car_powerslide_turn::{CarPowerslideTurn, CarPowerslideTurnBlueprint},
+    car_speed_flip::{CarSpeedFlip, CarSpeedFlip1D, SPEED_FLIP_ANGLE},
+    car_surface_drive::{
+        CarSurfaceDrive, CarSurfaceDriveError, SurfacePath, SurfaceSample, CORNER_RADIUS,
+        STICKY_ACCEL,
+    },
     collision::ball_car_distance,
-    math::linear_interpolate,
+    math::{linear_interpolate, GridTable},
 };
 
 mod car;
 mod car1d;
+mod car_dodge;
 mod car_forward_dodge;
 pub mod car_jump;
 mod car_powerslide_turn;
 pub mod car_single_jump;
+mod car_speed_flip;
+mod car_surface_drive;
 mod collision;
 mod math;
@@ -36,6 +36,68 @@ pub fn linear_interpolate_use_index(ys: &[f32], (i, ratio): FractionalIndex) ->
     ys[i] + (ys[i + 1] - ys[i]) * ratio
 }
 
+/// A table of samples on a rectilinear grid, with multilinear interpolation
+/// between them. The axes need not be evenly spaced. `values` is row-major, so
+/// the last axis varies fastest; this is the layout `oven` generates.
+///
+/// Points outside the grid are clamped to its edges. `NAN` cells propagate to
+/// any point which touches them, which callers can use to mark holes in the
+/// data.
+#[derive(Copy, Clone)]
+pub struct GridTable<'a> {
+    axes: &'a [&'a [f32]],
+    values: &'a [f32],
+}
+
+impl<'a> GridTable<'a> {
+    pub const fn new(axes: &'a [&'a [f32]], values: &'a [f32]) -> Self {
+        Self { axes, values }
+    }
+
+    pub fn axes(&self) -> &'a [&'a [f32]] {
+        self.axes
+    }
+
+    pub fn interpolate(&self, point: &[f32]) -> f32 {
+        self.interpolate_located(&self.locate(point))
+    }
+
+    /// Like [`linear_interpolate_find_index`], this lets you reuse one lookup
+    /// for several tables which share the same axes. Pair with
+    /// [`GridTable::interpolate_located`].
+    pub fn locate(&self, point: &[f32]) -> Vec<FractionalIndex> {
+        assert_eq!(point.len(), self.axes.len());
+        self.axes
+            .iter()
+            .zip(point)
+            .map(|(axis, &x)| linear_interpolate_find_index(axis, x))
+            .collect()
+    }
+
+    pub fn interpolate_located(&self, indices: &[FractionalIndex]) -> f32 {
+        assert_eq!(indices.len(), self.axes.len());
+        let mut result = 0.0;
+        'corners: for corner in 0..1 << indices.len() {
+            let mut offset = 0;
+            let mut weight = 1.0;
+            for (dim, (&(i, ratio), axis)) in indices.iter().zip(self.axes).enumerate() {
+                let (index, w) = if corner >> dim & 1 == 0 {
+                    (i, 1.0 - ratio)
+                } else {
+                    (i + 1, ratio)
+                };
+                if w == 0.0 {
+                    continue 'corners;
+                }
+                offset = offset * axis.len() + index;
+                weight *= w;
+            }
+            result += weight * self.values[offset];
+        }
+        result
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::math;
@@ -78,4 +140,31 @@ mod tests {
             assert_eq!(two_step, one_step);
         }
     }
+
+    #[test]
+    fn grid_table() {
+        let axes: &[&[f32]] = &[&[0.0, 1.0], &[0.0, 10.0, 20.0]];
+        #[rustfmt::skip]
+        let values = &[
+            0.0, 1.0, 2.0,
+            10.0, 11.0, 12.0,
+        ];
+        let table = math::GridTable::new(axes, values);
+        assert_eq!(table.interpolate(&[0.0, 0.0]), 0.0);
+        assert_eq!(table.interpolate(&[1.0, 20.0]), 12.0);
+        assert_eq!(table.interpolate(&[0.5, 5.0]), 5.5);
+        assert_eq!(table.interpolate(&[0.25, 15.0]), 4.0);
+        // Clamped outside the grid.
+        assert_eq!(table.interpolate(&[-1.0, 30.0]), 2.0);
+    }
+
+    #[test]
+    fn grid_table_nan_holes() {
+        let axes: &[&[f32]] = &[&[0.0, 1.0, 2.0]];
+        let values = &[0.0, 1.0, std::f32::NAN];
+        let table = math::GridTable::new(axes, values);
+        assert_eq!(table.interpolate(&[0.5]), 0.5);
+        assert_eq!(table.interpolate(&[1.0]), 1.0);
+        assert!(table.interpolate(&[1.5]).is_nan());
+    }
 }